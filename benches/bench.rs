@@ -1,6 +1,26 @@
-use boostvoronoi;
 use boostvoronoi::builder::{to_points, to_segments, Builder};
 use criterion::{criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the heap allocations, see `bench_3` and `bench_4`
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
 
 type I = i32;
 type F = f64;
@@ -126,5 +146,151 @@ fn bench_1(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches1, bench_1);
+#[cfg(test)]
+/// Builds a few thousand random segments.
+fn bench_2(c: &mut Criterion) {
+    use rand::{Rng, SeedableRng};
+    // one random segment inside each cell of a 60x60 grid, so they never intersect
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let mut segments = Vec::<[I; 4]>::new();
+    for x in 0..60 {
+        for y in 0..60 {
+            let mut random_point = || {
+                [
+                    x * 100 + rng.gen_range(5..95),
+                    y * 100 + rng.gen_range(5..95),
+                ]
+            };
+            let (start, end) = loop {
+                let (start, end) = (random_point(), random_point());
+                if start != end {
+                    break (start, end);
+                }
+            };
+            segments.push([start[0], start[1], end[0], end[1]]);
+        }
+    }
+    let segments = to_segments::<I, I>(&segments);
+    c.bench_function("bench_2 random segments", |b| {
+        b.iter(|| {
+            let mut vb = Builder::<I, F>::default();
+            vb.with_segments(segments.iter()).expect("bench_2");
+            vb.build().expect("bench_2")
+        })
+    });
+}
+
+#[cfg(test)]
+/// Evaluates the circle events of nearly collinear segments, i.e. segments tangent to a huge
+/// circle. These always fall back to the exact (ExtendedInt) formulas.
+/// Also prints the number of heap allocations per evaluation.
+fn bench_3(c: &mut Criterion) {
+    use boostvoronoi::geometry::{Line, Point};
+    use boostvoronoi::predicates::{circle_event, Site};
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let mut triples = Vec::<[Site<I>; 3]>::new();
+    for _ in 0..1000 {
+        let (cx, cy) = (
+            rng.gen_range(0..1_000_000) as F,
+            rng.gen_range(0..1_000_000) as F,
+        );
+        let radius = 1.0e8 + rng.gen_range(0..1000) as F;
+        let angle = rng.gen_range(0..1000) as F * 1e-6;
+        let segment = |k: usize| {
+            let a = angle + k as F * 2e-5;
+            let (x, y) = (cx + radius * a.cos(), cy + radius * a.sin());
+            let (dx, dy) = (1000.0 * a.sin(), -1000.0 * a.cos());
+            Site::Segment(Line::new(
+                Point {
+                    x: (x + dx).round() as I,
+                    y: (y + dy).round() as I,
+                },
+                Point {
+                    x: (x - dx).round() as I,
+                    y: (y - dy).round() as I,
+                },
+            ))
+        };
+        triples.push([segment(0), segment(1), segment(2)]);
+    }
+    let evaluate = |triples: &[[Site<I>; 3]]| {
+        for [s1, s2, s3] in triples.iter() {
+            let _ = circle_event::<I, F>(s1, s2, s3);
+            let _ = circle_event::<I, F>(s3, s2, s1);
+        }
+    };
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    evaluate(&triples);
+    println!(
+        "bench_3: {:.2} heap allocations per circle event",
+        (ALLOCATIONS.load(Ordering::Relaxed) - before) as F / (2 * triples.len()) as F
+    );
+    c.bench_function("bench_3 collinear segment triples", |b| {
+        b.iter(|| evaluate(&triples))
+    });
+}
+
+#[cfg(test)]
+/// Builds 50k random points. Also prints the number of heap allocations per build.
+fn bench_4(c: &mut Criterion) {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let points: Vec<[I; 2]> = (0..50_000)
+        .map(|_| [rng.gen_range(0..1_000_000), rng.gen_range(0..1_000_000)])
+        .collect();
+    let points = to_points::<I, I>(&points);
+    let build = || {
+        let mut vb = Builder::<I, F>::default();
+        vb.with_vertices(points.iter()).expect("bench_4");
+        vb.build().expect("bench_4")
+    };
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let output = build();
+    println!(
+        "bench_4: {} heap allocations, {} cells, {} vertices, {} edges",
+        ALLOCATIONS.load(Ordering::Relaxed) - before,
+        output.cells().len(),
+        output.vertices().len(),
+        output.edges().len()
+    );
+    c.bench_function("bench_4 random points", |b| b.iter(build));
+}
+
+#[cfg(test)]
+/// Compares `Exactness::Full` with `Exactness::LazyOnly` on 1000 random, short segments.
+fn bench_5(c: &mut Criterion) {
+    use boostvoronoi::builder::Exactness;
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let mut segments: Vec<[I; 4]> = Vec::new();
+    while segments.len() < 1000 {
+        let (x, y) = (rng.gen_range(0..10_000), rng.gen_range(0..10_000));
+        let s = [x, y, x + rng.gen_range(1..50), y + rng.gen_range(1..50)];
+        // skip segments touching (or crossing) an earlier one, the input would be invalid
+        let disjoint =
+            |o: &[I; 4]| s[2] + 1 < o[0] || o[2] + 1 < s[0] || s[3] + 1 < o[1] || o[3] + 1 < s[1];
+        if segments.iter().all(disjoint) {
+            segments.push(s);
+        }
+    }
+    let segments = to_segments::<I, I>(&segments);
+    let build = |exactness: Exactness| {
+        let mut vb = Builder::<I, F>::default();
+        vb.exactness(exactness)
+            .with_segments(segments.iter())
+            .expect("bench_5");
+        vb.build().expect("bench_5")
+    };
+    println!(
+        "bench_5: {} exact circle events",
+        build(Exactness::Full).stats().exact_circle_events
+    );
+    let mut group = c.benchmark_group("bench_5 random segments");
+    let _ = group.bench_function("Full", |b| b.iter(|| build(Exactness::Full)));
+    let _ = group.bench_function("LazyOnly", |b| b.iter(|| build(Exactness::LazyOnly)));
+    group.finish();
+}
+
+criterion_group!(benches1, bench_1, bench_2, bench_3, bench_4, bench_5);
 criterion_main!(benches1);
@@ -4,6 +4,7 @@ extern crate version_check as rustc;
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(feature, values(\"map_first_last\"))");
     if let Some(is_feature_flaggable) = rustc::is_feature_flaggable() {
         // enable the "map_first_last" feature if using +nightly
         if is_feature_flaggable {
@@ -7,9 +7,9 @@ type F = f64; // this is the float output type (circle event coordinates)
 
 fn main() -> Result<(), BvError> {
     // Only unique Points will be used. Points should not intersect lines
-    let p = vec![Point::from([9_i32, 10])];
+    let p = [Point::from([9_i32, 10])];
     // Lines may only intersect at the endpoints.
-    let s = vec![Line::from([10_i32, 11, 12, 33])];
+    let s = [Line::from([10_i32, 11, 12, 33])];
     let mut vb = Builder::<I, F>::default();
     // you will have to keep track of the input geometry. it will be referenced as
     // input geometry indices in the output.
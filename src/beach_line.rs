@@ -18,6 +18,7 @@ mod test3;
 mod tests1;
 
 use super::circle_event as VC;
+use super::cpp_map;
 use super::diagram as VD;
 use super::predicate as VP;
 use super::site_event as VSE;
@@ -28,16 +29,17 @@ use crate::predicate::NodeComparisonPredicate;
 use crate::BvError;
 #[allow(unused_imports)]
 use crate::{t, tln};
+use alloc::format;
+use alloc::rc::Rc;
+use core::cell::{Cell, RefCell};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
 #[allow(unused_imports)]
-use itertools::Itertools;
-use std::cell::{Cell, RefCell};
-use std::cmp::Ordering;
-use std::fmt;
-use std::hash::{Hash, Hasher};
+use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::Neg;
 #[allow(unused_imports)]
-use std::ops::Bound::{Excluded, Included, Unbounded};
-use std::ops::Neg;
-use std::rc::Rc;
+use itertools::Itertools;
 
 /// debug utility function, prints beach line index
 #[allow(dead_code)]
@@ -234,6 +236,7 @@ where
         let node = bl_borrow.get(beachline_index.0);
 
         if node.is_err() {
+            #[cfg(feature = "std")]
             eprintln!("Failed to retrieve beach line key : {}", beachline_index.0);
             //panic!();
             return Err(BvError::InternalError(format!(
@@ -1,14 +1,15 @@
 #![allow(unused_imports)]
 #![allow(dead_code)]
+#![allow(clippy::multiple_bound_locations)]
 use super::super::beach_line as VB;
 use super::super::geometry::Point;
 use super::super::predicate as VP;
 use super::super::site_event as VSE;
 use super::super::InputType;
 use super::super::OutputType;
+use core::ops::Neg;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::ops::Neg;
 
 lazy_static! {
     static ref RE_PREDICATE: Regex =
@@ -1,16 +1,17 @@
 #![allow(unused_imports)]
 #![allow(dead_code)]
+#![allow(clippy::multiple_bound_locations)]
 use super::super::beach_line as VB;
 use super::super::geometry::Point;
 use super::super::predicate as VP;
 use super::super::site_event as VSE;
 use super::super::InputType;
 use super::super::OutputType;
+use core::ops::Neg;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::fs::File;
 use std::io::{self, prelude::*, BufReader};
-use std::ops::Neg;
 use std::path::Path;
 
 lazy_static! {
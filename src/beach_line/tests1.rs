@@ -1,4 +1,5 @@
 #![allow(unused_imports)]
+#![allow(clippy::bool_assert_comparison, clippy::useless_vec)]
 use super::super::beach_line as VB;
 use super::super::builder::Builder;
 use super::super::diagram as VD;
@@ -7,9 +8,9 @@ use super::super::predicate as VP;
 use super::super::site_event as VSE;
 use super::super::BvError;
 use super::{BeachLine, BeachLineIndex, BeachLineNodeData, BeachLineNodeKey};
-use std::cmp::Ordering;
-use std::collections::BTreeMap;
-use std::ops::Bound::{Excluded, Included, Unbounded};
+use alloc::collections::BTreeMap;
+use core::cmp::Ordering;
+use core::ops::Bound::{Excluded, Included, Unbounded};
 
 //#[ignore]
 #[test]
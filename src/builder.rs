@@ -13,24 +13,62 @@
 
 use super::beach_line as VB;
 use super::circle_event as VC;
+use super::ctypes as CT;
 use super::diagram as VD;
 use super::end_point as VEP;
 use super::predicate as VP;
 use super::site_event as VSE;
-use super::BvError;
+use super::visual_utils as VU;
+use super::TypeConverter2 as TC2;
+use super::{BvError, DegeneracyWarning, DegenerateKind};
 
 use super::geometry::{Line, Point};
-use std::collections::BinaryHeap;
-use std::ops::Neg;
-use std::rc::Rc;
+use alloc::collections::{BTreeSet, BinaryHeap};
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Neg;
 
 use super::{InputType, OutputType};
+use crate::cpp_map::PIterator;
 use crate::{t, tln};
-use cpp_map::PIterator;
 
+mod farthest;
+mod intersection;
 #[cfg(test)]
 mod tests;
 
+pub use intersection::{find_intersecting_segments, IntersectionCheck};
+
+/// A snapshot of the input of a builder, see `Builder::dump_input()` and
+/// `Builder::load_input()`. The points and segments are stored in source index order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputSet<I: InputType> {
+    pub points: Vec<Point<I>>,
+    pub segments: Vec<Line<I>>,
+}
+
+/// How the circle events are evaluated, see `Builder::exactness()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Exactness {
+    /// Circle events with a too large error estimate are recomputed with exact arithmetic.
+    #[default]
+    Full,
+    /// Circle events are only evaluated with the lazy (f64) arithmetic, never recomputed.
+    LazyOnly,
+}
+
+/// The three sites of a potential circle event and the beach line node of the (site2, site3)
+/// bisector, see `Builder::activate_circle_event()`.
+type CircleCandidate<I, F> = (
+    VSE::SiteEvent<I, F>,
+    VSE::SiteEvent<I, F>,
+    VSE::SiteEvent<I, F>,
+    VB::BeachLineIndex,
+);
+
 /// GENERAL INFO:
 /// The sweepline algorithm implementation to compute Voronoi diagram of
 /// points and non-intersecting segments (excluding endpoints).
@@ -40,6 +78,11 @@ mod tests;
 /// CONTRACT:
 /// 1) Input geometries should have integral (e.g. int32, int64) coordinate type.
 /// 2) Input geometries should not intersect except their endpoints.
+/// 3) Input coordinates must be within ±`max_safe_coordinate()`. For the default `i32` input
+///    that is every value except `i32::MIN`, the products of coordinate differences that do
+///    not fit in `i64` are evaluated with exact big integer arithmetic. Points outside of the
+///    range are rejected with `BvError::CoordinateOutOfRange` when they are added, before
+///    the sweep.
 ///
 /// IMPLEMENTATION DETAILS:
 /// Each input point creates one input site. Each input segment creates three
@@ -85,7 +128,30 @@ where
     // The number of input sites if points and segments are counted as one.
     // (segments generates two site events so we can't use the lenght of the list)
     index_: usize,
-    segments_added_: bool, // make sure eventual vertices are added before segments
+    // The number of input points, points are indexed before the segments
+    num_points_: usize,
+    strict_: bool, // reject input that requires the degenerate-handling branches
+    // collects the degenerate-handling branches taken, see build_with_warnings()
+    warnings_: Option<Vec<DegeneracyWarning>>,
+    // the [x, y, lower_x] and the sites of the last circle event, see check_cocircular_sites()
+    previous_circle_: Option<([f64; 3], [usize; 3])>,
+    snap_distance_: Option<F>,
+    dedup_points_: bool,
+    intersection_check_: Option<IntersectionCheck>,
+    // (merged source index, kept source index) of the snapped points
+    snapped_sites_: Vec<(VD::SourceIndex, VD::SourceIndex)>,
+    // the site events removed by snap_points() and init_sites_queue(), build() puts them back
+    removed_site_events_: Vec<VSE::SiteEvent<I, F>>,
+    farthest_point_: bool,
+    drop_secondary_edges_: bool,
+    ulps_: u64,
+    // the settings of the predicates during the build, see sweep_()
+    predicate_settings_: VP::PredicateSettings,
+    // the factor float input is multiplied with before it is rounded, see with_scale()
+    scale_: f64,
+    exactness_: Exactness,
+    #[cfg(feature = "never_recompute")]
+    never_recompute_: bool,
     #[cfg(feature = "console_debug")]
     debug_circle_counter_: isize, // Just for debugging purposes
     #[cfg(feature = "console_debug")]
@@ -108,7 +174,23 @@ where
             debug_circle_counter_: 0,
             #[cfg(feature = "console_debug")]
             debug_site_counter_: 0,
-            segments_added_: false,
+            num_points_: 0,
+            strict_: false,
+            warnings_: None,
+            previous_circle_: None,
+            snap_distance_: None,
+            dedup_points_: false,
+            intersection_check_: None,
+            snapped_sites_: Vec::new(),
+            removed_site_events_: Vec::new(),
+            farthest_point_: false,
+            drop_secondary_edges_: false,
+            ulps_: VP::DEFAULT_ULPS,
+            predicate_settings_: VP::PredicateSettings::default(),
+            scale_: 1.0,
+            exactness_: Exactness::Full,
+            #[cfg(feature = "never_recompute")]
+            never_recompute_: false,
         }
     }
 }
@@ -118,68 +200,863 @@ where
     I: InputType + Neg<Output = I>,
     F: OutputType + Neg<Output = F>,
 {
+    /// Returns the largest coordinate magnitude the builder accepts for the input type `I`.
+    ///
+    /// The predicates convert coordinates, and the differences of coordinates, to `i64` and
+    /// `f64`. The lazy (fast) evaluation of the predicates assumes that those conversions are
+    /// exact, which holds as long as `|coordinate| <= 2^52`. So for `i32` the entire range
+    /// (except `i32::MIN`) is safe, while `i64` input is limited to `±2^52`.
+    /// `with_vertices()` and `with_segments()` return `BvError::CoordinateOutOfRange` if a
+    /// coordinate exceeds the bound, scale your data to fit before adding it.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// assert_eq!(Builder::<i32, f64>::max_safe_coordinate(), i32::MAX);
+    /// assert_eq!(Builder::<i64, f64>::max_safe_coordinate(), 1_i64 << 52);
+    /// ```
+    pub fn max_safe_coordinate() -> I {
+        const MAX_EXACT_F64: i64 = 1 << 52;
+        num::cast::<i64, I>(MAX_EXACT_F64).unwrap_or_else(I::max_value)
+    }
+
+    /// Returns `BvError::CoordinateOutOfRange` if the point is outside of the safe range.
+    fn check_coordinate_range(point: &Point<I>) -> Result<(), BvError> {
+        let max = Self::max_safe_coordinate();
+        let in_range = |v: I| v <= max && v >= -max;
+        if in_range(point.x) && in_range(point.y) {
+            Ok(())
+        } else {
+            Err(BvError::CoordinateOutOfRange(format!(
+                "The input point ({},{}) is outside of the safe range ±{}",
+                point.x, point.y, max
+            )))
+        }
+    }
+
+    /// Returns `BvError::CoordinateOutOfRange` if an endpoint is outside of the safe range, and
+    /// `BvError::DegenerateInput` if the segment has identical endpoints. `index` is the source
+    /// index the segment would get.
+    fn check_segment(segment: &Line<I>, index: VD::SourceIndex) -> Result<(), BvError> {
+        Self::check_coordinate_range(&segment.start)?;
+        Self::check_coordinate_range(&segment.end)?;
+        if segment.start == segment.end {
+            return Err(BvError::DegenerateInput {
+                kind: DegenerateKind::ZeroLengthSegment,
+                indices: vec![index],
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds the input points.
+    /// `with_vertices()` and `with_segments()` can be called any number of times and in any
+    /// order. The points are always indexed before the segments: the source index of a point
+    /// is its position among all the points, and the source index of a segment is the number
+    /// of points plus its position among all the segments. So adding points after segments
+    /// shifts the source indices of the segments.
+    /// Nothing is added if any of the points is rejected.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// # use boostvoronoi::diagram::SourceCategory;
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_segments(to_segments::<i32, i32>(&[[0, 0, 0, 10]]).iter())?;
+    /// vb.with_vertices(to_points::<i32, i32>(&[[5, 5]]).iter())?;
+    /// let output = vb.build()?;
+    /// for cell in output.cell_iter() {
+    ///     let cell = cell.get();
+    ///     let expected = if cell.source_category() == SourceCategory::SinglePoint { 0 } else { 1 };
+    ///     assert_eq!(cell.source_index(), expected);
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
     pub fn with_vertices<'a, T>(&mut self, vertices: T) -> Result<(), BvError>
     where
         I: 'a,
         T: Iterator<Item = &'a Point<I>>,
     {
-        if self.segments_added_ {
-            return Err(BvError::VerticesGoesFirst(
-                "Vertices should be added before segments".to_string(),
-            ));
-        }
+        self.with_vertices_owned(vertices.copied())
+    }
+
+    /// Adds the input points, just like `with_vertices()` but the points are taken by value.
+    /// This lets a lazy iterator adapter feed the builder directly, without collecting the
+    /// points into a `Vec` first.
+    /// ```
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::builder::Builder;
+    /// let coords = [geo::Coordinate { x: 0, y: 0 }, geo::Coordinate { x: 10, y: 0 }];
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices_owned(coords.iter().map(|c| Point { x: c.x, y: c.y }))?;
+    /// assert_eq!(vb.build()?.num_cells(), 2);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn with_vertices_owned<It: IntoIterator<Item = Point<I>>>(
+        &mut self,
+        vertices: It,
+    ) -> Result<(), BvError> {
+        let mut sites = Vec::new();
         for v in vertices {
-            let mut s = VSE::SiteEvent::<I, F>::new_3(*v, *v, self.index_);
+            Self::check_coordinate_range(&v)?;
+            let mut s = VSE::SiteEvent::<I, F>::new_3(v, v, self.num_points_ + sites.len());
             s.or_source_category(&VD::ColorBits::SINGLE_POINT__BIT);
-            self.site_events_.push(s);
-            self.index_ += 1;
+            sites.push(s);
+        }
+        let added = sites.len();
+        if self.index_ > self.num_points_ {
+            // make room for the new points in front of the segments
+            for s in self.site_events_.iter_mut() {
+                if s.source_category() != VD::ColorBits::SINGLE_POINT__BIT {
+                    s.set_initial_index(s.initial_index() + added);
+                }
+            }
         }
+        self.site_events_.extend(sites);
+        self.num_points_ += added;
+        self.index_ += added;
         Ok(())
     }
 
+    /// Adds the input segments, see `with_vertices()` for how the source indices are assigned.
+    /// A segment with identical endpoints is rejected with `BvError::DegenerateInput` of the
+    /// kind `DegenerateKind::ZeroLengthSegment`, naming the source index of the segment.
+    /// Nothing is added if any of the segments is rejected.
     pub fn with_segments<'a, T>(&mut self, segments: T) -> Result<(), BvError>
     where
         I: 'a,
         T: Iterator<Item = &'a Line<I>>,
     {
+        self.with_segments_owned(segments.copied())
+    }
+
+    /// Adds the input segments, just like `with_segments()` but the segments are taken by
+    /// value, see `with_vertices_owned()`.
+    /// ```
+    /// # use boostvoronoi::geometry::{Line, Point};
+    /// # use boostvoronoi::builder::Builder;
+    /// // a staircase, generated lazily
+    /// let steps = (0..4).flat_map(|i| {
+    ///     let (a, b) = (Point { x: i * 10, y: i * 10 }, Point { x: i * 10 + 10, y: i * 10 });
+    ///     [Line::new(a, b), Line::new(b, Point { x: b.x, y: b.y + 10 })]
+    /// });
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_segments_owned(steps)?;
+    /// // 8 segments and 9 distinct endpoints
+    /// assert_eq!(vb.build()?.num_cells(), 17);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn with_segments_owned<It: IntoIterator<Item = Line<I>>>(
+        &mut self,
+        segments: It,
+    ) -> Result<(), BvError> {
         type Cb = VD::ColorBits;
+        let mut sites = Vec::new();
         for s in segments {
+            let index = self.index_ + sites.len() / 3;
+            Self::check_segment(&s, index)?;
             let p1 = s.start;
             let p2 = s.end;
-            let mut s1 = VSE::SiteEvent::<I, F>::new_3(p1, p1, self.index_);
+            let mut s1 = VSE::SiteEvent::<I, F>::new_3(p1, p1, index);
             s1.or_source_category(&Cb::SEGMENT_START_POINT__BIT);
-            let mut s2 = VSE::SiteEvent::new_3(p2, p2, self.index_);
+            let mut s2 = VSE::SiteEvent::new_3(p2, p2, index);
             s2.or_source_category(&Cb::SEGMENT_END_POINT__BIT);
 
-            self.site_events_.push(s1);
-            self.site_events_.push(s2);
+            sites.push(s1);
+            sites.push(s2);
             let s3 = if VP::PointComparisonPredicate::<I>::point_comparison_predicate(&p1, &p2) {
-                let mut s3 = VSE::SiteEvent::<I, F>::new_3(p1, p2, self.index_);
+                let mut s3 = VSE::SiteEvent::<I, F>::new_3(p1, p2, index);
                 s3.or_source_category(&Cb::INITIAL_SEGMENT);
                 s3
             } else {
-                let mut s3 = VSE::SiteEvent::<I, F>::new_3(p2, p1, self.index_);
+                let mut s3 = VSE::SiteEvent::<I, F>::new_3(p2, p1, index);
                 s3.or_source_category(&Cb::REVERSE_SEGMENT);
                 s3
             };
-            self.site_events_.push(s3);
-            self.index_ += 1;
+            sites.push(s3);
         }
-        self.segments_added_ = true;
+        self.index_ += sites.len() / 3;
+        self.site_events_.extend(sites);
         Ok(())
     }
 
+    /// Adds the segments between consecutive `vertices` of a polyline. If `closed` is true the
+    /// ring is closed with a segment from the last vertex back to the first one.
+    /// A polyline needs at least 2 vertices and a closed polygon at least 3, otherwise
+    /// `BvError::ValueError` is returned. Consecutive coincident vertices (including the last
+    /// and the first vertex of a closed ring) are rejected with `BvError::DegenerateInput` of
+    /// the kind `DegenerateKind::ZeroLengthSegment`, nothing is added in that case.
+    /// The segments get consecutive source indices in vertex order, the closing segment being
+    /// the last one. Call this once per ring to add several rings, e.g. a polygon with holes.
+    /// ```
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::builder::Builder;
+    /// let square = vec![
+    ///     Point { x: 0_i32, y: 0 },
+    ///     Point { x: 10, y: 0 },
+    ///     Point { x: 10, y: 10 },
+    ///     Point { x: 0, y: 10 },
+    /// ];
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_polygon(&square, true).unwrap();
+    /// let diagram = vb.build().unwrap();
+    /// // 4 segments, each with its own cell, and 4 corner cells
+    /// assert_eq!(diagram.num_cells(), 8);
+    /// ```
+    pub fn with_polygon(&mut self, vertices: &[Point<I>], closed: bool) -> Result<(), BvError> {
+        let min_vertices = if closed { 3 } else { 2 };
+        if vertices.len() < min_vertices {
+            return Err(BvError::ValueError(format!(
+                "A {} needs at least {} vertices, got {}",
+                if closed { "closed polygon" } else { "polyline" },
+                min_vertices,
+                vertices.len()
+            )));
+        }
+        let mut segments: Vec<Line<I>> =
+            vertices.windows(2).map(|w| Line::new(w[0], w[1])).collect();
+        if closed {
+            segments.push(Line::new(vertices[vertices.len() - 1], vertices[0]));
+        }
+        if let Some(i) = segments.iter().position(|s| s.start == s.end) {
+            return Err(BvError::DegenerateInput {
+                kind: DegenerateKind::ZeroLengthSegment,
+                indices: vec![self.index_ + i],
+            });
+        }
+        self.with_segments(segments.iter())
+    }
+
+    /// Adds the input points given as `(x, y)` tuples, see `with_vertices()`.
+    pub fn with_point_tuples<It: IntoIterator<Item = (I, I)>>(
+        &mut self,
+        points: It,
+    ) -> Result<(), BvError> {
+        self.with_vertices_owned(points.into_iter().map(|(x, y)| Point { x, y }))
+    }
+
+    /// Adds the input segments given as `(x1, y1, x2, y2)` tuples, see `with_segments()`.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// let points: Vec<(i32, i32)> = vec![(5, 5)];
+    /// let segments: Vec<(i32, i32, i32, i32)> = vec![(0, 0, 10, 0), (10, 0, 10, 10)];
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_point_tuples(points)?;
+    /// vb.with_segment_tuples(segments)?;
+    /// // 1 point, 2 segments and 3 segment endpoints
+    /// assert_eq!(vb.build()?.num_cells(), 6);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn with_segment_tuples<It: IntoIterator<Item = (I, I, I, I)>>(
+        &mut self,
+        segments: It,
+    ) -> Result<(), BvError> {
+        self.with_segments_owned(
+            segments
+                .into_iter()
+                .map(|(x1, y1, x2, y2)| Line::new(Point { x: x1, y: y1 }, Point { x: x2, y: y2 })),
+        )
+    }
+
+    /// Sets the factor float input is multiplied with before it is rounded to the integer
+    /// input type, see `with_float_vertices()` and `with_float_segments()`. Use `unscale()` to
+    /// map output coordinates back to the units of the float input. The default is `1.0`.
+    ///
+    /// Every coordinate is rounded to the nearest integer after scaling, so the input is
+    /// quantized to a grid of `1/factor` units: coordinates closer than that may collapse into
+    /// the same point, and every coordinate moves by up to `0.5/factor`. The output vertices
+    /// are computed from the quantized input, pick a factor that leaves enough resolution
+    /// while keeping the scaled coordinates within `max_safe_coordinate()`.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_scale(1000.0)
+    ///     .with_float_vertices(&[[0.0, 0.0], [1.0, 0.0], [0.5, 1.0]])?;
+    /// let diagram = vb.build()?;
+    /// let v = diagram.vertices()[0].get();
+    /// assert!((vb.unscale(v.x()) - 0.5).abs() < 1e-9);
+    /// assert!((vb.unscale(v.y()) - 0.375).abs() < 1e-9);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn with_scale(&mut self, factor: f64) -> &mut Self {
+        self.scale_ = factor;
+        self
+    }
+
+    /// Returns the factor set by `with_scale()`.
+    pub fn scale(&self) -> f64 {
+        self.scale_
+    }
+
+    /// Multiplies `value` with the scale factor and rounds it to the nearest `I`.
+    fn quantize(&self, value: f64) -> Result<I, BvError> {
+        if !(self.scale_.is_finite() && self.scale_ > 0.0) {
+            return Err(BvError::ValueError(format!(
+                "The scale factor must be finite and positive, got {}",
+                self.scale_
+            )));
+        }
+        num::cast::<f64, I>(libm::round(value * self.scale_)).ok_or_else(|| {
+            BvError::CoordinateOutOfRange(format!(
+                "The coordinate {} scaled by {} can't be represented by the input type",
+                value, self.scale_
+            ))
+        })
+    }
+
+    /// Adds float input points, multiplied with the scale factor and rounded to the nearest
+    /// integer. See `with_scale()` and `with_vertices()`.
+    pub fn with_float_vertices(&mut self, points: &[[f64; 2]]) -> Result<(), BvError> {
+        let points = points
+            .iter()
+            .map(|p| {
+                Ok(Point {
+                    x: self.quantize(p[0])?,
+                    y: self.quantize(p[1])?,
+                })
+            })
+            .collect::<Result<Vec<_>, BvError>>()?;
+        self.with_vertices(points.iter())
+    }
+
+    /// Adds float input segments `[x1, y1, x2, y2]`, multiplied with the scale factor and
+    /// rounded to the nearest integer. A segment shorter than the quantization grid may end
+    /// up with identical endpoints, which is rejected just like in `with_segments()`.
+    /// See `with_scale()`.
+    pub fn with_float_segments(&mut self, segments: &[[f64; 4]]) -> Result<(), BvError> {
+        let segments = segments
+            .iter()
+            .map(|s| {
+                Ok(Line::new(
+                    Point {
+                        x: self.quantize(s[0])?,
+                        y: self.quantize(s[1])?,
+                    },
+                    Point {
+                        x: self.quantize(s[2])?,
+                        y: self.quantize(s[3])?,
+                    },
+                ))
+            })
+            .collect::<Result<Vec<_>, BvError>>()?;
+        self.with_segments(segments.iter())
+    }
+
+    /// Divides an output coordinate or distance by the scale factor, i.e. maps it back to the
+    /// units of the float input, see `with_scale()`.
+    /// The diagram itself stays in the scaled units of the quantized input sites, so that the
+    /// methods combining the vertices with the input geometry (e.g. `edge_discretize()` or
+    /// `locate()`) stay consistent. Unscale their results instead.
+    pub fn unscale(&self, value: F) -> F {
+        value / TC2::<I, F>::f64_to_f(self.scale_)
+    }
+
+    /// Converts a geo coordinate to a `Point<I>`. Returns `BvError::NumberConversion` if any
+    /// of the coordinate values can't be represented exactly by `I`.
+    #[cfg(feature = "geo")]
+    fn geo_coordinate_to_point<T: geo::CoordNum>(
+        coordinate: &geo::Coordinate<T>,
+    ) -> Result<Point<I>, BvError> {
+        let convert = |v: T| {
+            num::cast::<T, I>(v)
+                .filter(|i| num::cast::<I, T>(*i) == Some(v))
+                .ok_or_else(|| {
+                    BvError::NumberConversion(format!(
+                        "The coordinate ({:?},{:?}) can't be represented by the input type",
+                        coordinate.x, coordinate.y
+                    ))
+                })
+        };
+        Ok(Point {
+            x: convert(coordinate.x)?,
+            y: convert(coordinate.y)?,
+        })
+    }
+
+    /// Adds the points of a `geo::MultiPoint`, see `with_vertices()`.
+    /// The coordinates may be of any numeric type, but they must be exactly representable by
+    /// `I`. I.e. fractional or out of range values are rejected with
+    /// `BvError::NumberConversion`, nothing is added in that case.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// let points: geo::MultiPoint<f64> = vec![(0.0, 0.0), (10.0, 5.0)].into();
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_geo_multipoint(&points)?;
+    /// assert_eq!(vb.build()?.num_cells(), 2);
+    ///
+    /// let fractional: geo::MultiPoint<f64> = vec![(0.5, 0.0)].into();
+    /// assert!(Builder::<i32, f64>::default().with_geo_multipoint(&fractional).is_err());
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn with_geo_multipoint<T: geo::CoordNum>(
+        &mut self,
+        points: &geo::MultiPoint<T>,
+    ) -> Result<(), BvError> {
+        let points = points
+            .iter()
+            .map(|p| Self::geo_coordinate_to_point(&p.0))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.with_vertices(points.iter())
+    }
+
+    /// Adds every line of a `geo::MultiLineString` as segments between consecutive
+    /// coordinates, see `with_segments()`. Closed line strings simply end where they started.
+    /// The coordinates may be of any numeric type, but they must be exactly representable by
+    /// `I`. I.e. fractional or out of range values are rejected with
+    /// `BvError::NumberConversion`, nothing is added in that case.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// let square = geo::LineString::<f64>::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 0.)]);
+    /// let lines = geo::MultiLineString(vec![square]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_geo_multilinestring(&lines)?;
+    /// // 3 segments and 3 corners
+    /// assert_eq!(vb.build()?.num_cells(), 6);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn with_geo_multilinestring<T: geo::CoordNum>(
+        &mut self,
+        lines: &geo::MultiLineString<T>,
+    ) -> Result<(), BvError> {
+        let segments = lines
+            .iter()
+            .flat_map(|line_string| line_string.lines())
+            .map(|line| {
+                Ok(Line::new(
+                    Self::geo_coordinate_to_point(&line.start)?,
+                    Self::geo_coordinate_to_point(&line.end)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, BvError>>()?;
+        self.with_segments(segments.iter())
+    }
+
+    /// Returns the `(low, high)` corners of the smallest axis aligned rectangle containing all
+    /// the input points and segments added so far, or None if there is no input.
+    /// See `Diagram::bounding_box()` for the extent of the output.
+    pub fn input_bounding_box(&self) -> Option<([F; 2], [F; 2])> {
+        let mut aabb = VU::Aabb2::<I, F>::default();
+        for site in self.site_events_.iter() {
+            aabb.update_point(site.point0());
+            aabb.update_point(site.point1());
+        }
+        Some((aabb.get_low()?, aabb.get_high()?))
+    }
+
+    /// Returns the points and segments added so far, in source index order and with the exact
+    /// input coordinates and segment directions. Use this to capture an input for later replay
+    /// with `load_input()`, e.g. in another process when the `serde` feature is enabled.
+    /// Points merged by `with_snap_distance()` are included, they are only left out of the
+    /// diagrams.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(to_points::<i32, i32>(&[[5, 5]]).iter()).unwrap();
+    /// vb.with_segments(to_segments::<i32, i32>(&[[10, 0, 0, 0]]).iter()).unwrap();
+    /// let input = vb.dump_input();
+    ///
+    /// let mut replay = Builder::<i32, f64>::default();
+    /// replay.load_input(&input).unwrap();
+    /// assert_eq!(replay.dump_input(), input);
+    /// assert_eq!(replay.build().unwrap().num_cells(), vb.build().unwrap().num_cells());
+    /// ```
+    pub fn dump_input(&self) -> InputSet<I> {
+        type Cb = VD::ColorBits;
+        let mut sites: Vec<&VSE::SiteEvent<I, F>> = self
+            .site_events_
+            .iter()
+            .filter(|s| s.source_category() != Cb::SEGMENT_START_POINT__BIT)
+            .filter(|s| s.source_category() != Cb::SEGMENT_END_POINT__BIT)
+            .collect();
+        sites.sort_by_key(|s| s.initial_index());
+        let mut rv = InputSet::default();
+        for s in sites {
+            match s.site_geometry() {
+                VD::SiteGeometry::Point(point) => rv.points.push(point),
+                VD::SiteGeometry::Segment(line) => rv.segments.push(line),
+            }
+        }
+        rv
+    }
+
+    /// Adds the points and then the segments of `input`, just like `with_vertices()` followed
+    /// by `with_segments()`. Source indices are assigned in the same order, so a builder loaded
+    /// with the output of `dump_input()` reproduces the original diagram.
+    /// Nothing is added if any of the points or segments is rejected.
+    pub fn load_input(&mut self, input: &InputSet<I>) -> Result<(), BvError> {
+        // the segments are checked up front, so that the points aren't left behind
+        for (i, s) in input.segments.iter().enumerate() {
+            Self::check_segment(s, self.index_ + input.points.len() + i)?;
+        }
+        self.with_vertices(input.points.iter())?;
+        self.with_segments(input.segments.iter())
+    }
+
+    /// In strict mode the builder returns `BvError::DegenerateInput` the first time the
+    /// sweep-line would have to take a degenerate-handling branch, e.g. coincident points,
+    /// collinear point triples, cocircular sites, points on segments or vertical segment
+    /// special cases.
+    /// The default (non-strict) mode silently handles these cases. Exact duplicates of input
+    /// points can be removed up front with `dedup_points()`.
+    /// ```
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::builder::Builder;
+    /// # use boostvoronoi::{BvError, DegenerateKind};
+    /// let p = vec![Point { x: 0_i32, y: 0 }, Point { x: 5, y: 5 }, Point { x: 0, y: 0 }];
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.strict(true).with_vertices(p.iter()).unwrap();
+    /// match vb.build() {
+    ///     Err(BvError::DegenerateInput { kind, indices }) => {
+    ///         assert_eq!(kind, DegenerateKind::CoincidentPoints);
+    ///         assert_eq!(indices, vec![0, 2]);
+    ///     }
+    ///     _ => panic!("expected a DegenerateInput error"),
+    /// }
+    /// ```
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict_ = strict;
+        self
+    }
+
+    /// Builds the farthest-point Voronoi diagram instead of the (nearest-point) Voronoi diagram.
+    ///
+    /// Every point of a cell is farther away from the site of the cell than from any other site.
+    /// Only the points on the convex hull of the input have a cell, the cells of the other
+    /// points are degenerate (they have no edges). All the cells are unbounded, and the edges
+    /// form a tree. The vertex with the largest distance to its sites is the center of the
+    /// smallest circle enclosing the points, unless that circle is defined by only two points.
+    ///
+    /// Only point input is supported, `build()` returns `BvError::ValueError` if any segments
+    /// were added.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// # use boostvoronoi::geometry::Point;
+    /// let points = [[0, 0], [10, 0], [0, 10], [10, 10], [4, 5]];
+    /// let mut b = Builder::<i32, f64>::default();
+    /// b.with_vertices(points.iter().map(|p| Point { x: p[0], y: p[1] }).collect::<Vec<_>>().iter())?;
+    /// let diagram = b.farthest_point(true).build()?;
+    /// // the four corners are co-circular, so there is a single vertex at the center
+    /// assert_eq!(diagram.vertices().len(), 1);
+    /// assert_eq!(diagram.vertex_position(diagram.vertices()[0].get().get_id())?, [5.0, 5.0]);
+    /// let degenerate: Vec<_> = diagram
+    ///     .cells()
+    ///     .iter()
+    ///     .filter(|c| c.get().is_degenerate())
+    ///     .map(|c| c.get().source_index())
+    ///     .collect();
+    /// assert_eq!(degenerate, vec![4]);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn farthest_point(&mut self, farthest_point: bool) -> &mut Self {
+        self.farthest_point_ = farthest_point;
+        self
+    }
+
+    /// Makes `build()` remove the secondary edges, i.e. the edges between an input segment and
+    /// its endpoints, from the diagram. `Diagram::edges()` then only contains primary edges.
+    ///
+    /// Every removed edge is unlinked from the edge loop of its cell, so the remaining edges of
+    /// a cell still form a loop that can be traversed with `next()` and `prev()`. But the loop
+    /// is no longer geometrically connected where a secondary edge was removed, so rotating
+    /// around such a vertex (e.g. `Diagram::edge_rot_next()`) does not give the edges around
+    /// it. Cells left without edges become degenerate and vertices left without edges are
+    /// removed. The remaining edges and vertices are renumbered.
+    /// ```
+    /// # use boostvoronoi::builder::{to_segments, Builder};
+    /// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0], [10, 0, 5, 10], [5, 10, 0, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.drop_secondary_edges(true).with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// output.check_invariants()?;
+    /// assert!(output.edges().iter().all(|e| e.get().is_primary()));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn drop_secondary_edges(&mut self, drop_secondary_edges: bool) -> &mut Self {
+        self.drop_secondary_edges_ = drop_secondary_edges;
+        self
+    }
+
+    /// Sets the robustness threshold of the predicates, in units in the last place (ULPs) of
+    /// an f64. Circle events are first evaluated with f64 arithmetic while the relative error
+    /// is tracked. If the error exceeds the threshold, the circle event is recomputed with
+    /// exact (extended precision) arithmetic. The threshold is also the tolerance used when
+    /// comparing site events with circle events.
+    ///
+    /// A lower value recomputes more often and gives more accurate vertices, at the cost of
+    /// speed. A higher value trades accuracy for speed, very large values make the event
+    /// comparisons meaningless. The default is 64, just like C++ Boost.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 1], [3, 9]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_ulps(8).with_vertices(points.iter())?;
+    /// assert_eq!(vb.build()?.num_vertices(), 1);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn with_ulps(&mut self, ulps: u64) -> &mut Self {
+        self.ulps_ = ulps;
+        self
+    }
+
+    /// Selects how the circle events (the Voronoi vertices) are evaluated. The default is
+    /// `Exactness::Full`, just like C++ Boost.
+    ///
+    /// The circle events are first evaluated with fast f64 arithmetic that also estimates the
+    /// error of the result. With `Exactness::Full`, events with an error above the threshold of
+    /// `with_ulps()` are recomputed with exact (extended precision) arithmetic. With
+    /// `Exactness::LazyOnly` they are never recomputed, which saves time on input where the
+    /// exact path is taken often, see `Diagram::stats()`.
+    ///
+    /// `LazyOnly` is a trade of robustness for speed. The vertices may be less accurate, and on
+    /// near-degenerate input (nearly collinear or cocircular sites, nearly touching segments)
+    /// the events may be processed in the wrong order. This can give a diagram with the wrong
+    /// topology, or a build error. Only use it for well-separated input.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder, Exactness};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 1], [3, 9]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.exactness(Exactness::LazyOnly).with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// assert_eq!(output.num_vertices(), 1);
+    /// assert_eq!(output.stats().exact_circle_events, 0);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn exactness(&mut self, exactness: Exactness) -> &mut Self {
+        self.exactness_ = exactness;
+        self
+    }
+
+    /// A diagnostic switch for benchmarking and profiling, do NOT use this in production.
+    ///
+    /// When enabled, circle events are never recomputed with the exact (extended precision)
+    /// arithmetic, even when the lazy f64 evaluation reports a too large error. This shows how
+    /// much of the build time is spent in the exact path. The resulting vertices may be less
+    /// accurate, and the topology of the diagram may be wrong for difficult input.
+    /// Only available with the `never_recompute` feature.
+    #[cfg(feature = "never_recompute")]
+    pub fn never_recompute(&mut self, never_recompute: bool) -> &mut Self {
+        self.never_recompute_ = never_recompute;
+        self
+    }
+
+    /// Returns true if the circle events are never recomputed with exact arithmetic, see
+    /// `exactness()` and `never_recompute()`.
+    fn lazy_only(&self) -> bool {
+        #[cfg(feature = "never_recompute")]
+        if self.never_recompute_ {
+            return true;
+        }
+        self.exactness_ == Exactness::LazyOnly
+    }
+
+    /// Input points (added by `with_vertices()`) closer than `distance` to each other are
+    /// collapsed into a single site before the diagram is built. This removes near-coincident
+    /// points, e.g. floating point noise in real world data, that would otherwise produce tiny
+    /// cells and very short edges. Segment endpoints are never moved.
+    ///
+    /// The points are processed in ascending x order, and every point is merged into the
+    /// nearest previously kept point within `distance` (if any). The cell of a collapsed group
+    /// refers to the source index of the kept point, the merged indices can be retrieved with
+    /// `snapped_sites()` after `build()`.
+    ///
+    /// The distance is measured in the integer input coordinates. If the input was quantized
+    /// from float data by multiplying with a scale factor, `distance` must be scaled by the
+    /// same factor. A distance smaller than `1.0` has no effect other than removing exact
+    /// duplicates, as distinct integer points are at least one unit apart.
+    /// ```
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::builder::Builder;
+    /// let p = vec![Point { x: 0_i32, y: 0 }, Point { x: 50, y: 0 }, Point { x: 1, y: 1 }];
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_snap_distance(2.0).with_vertices(p.iter()).unwrap();
+    /// let diagram = vb.build().unwrap();
+    /// assert_eq!(diagram.num_cells(), 2);
+    /// assert_eq!(vb.snapped_sites(), &[(2, 0)]);
+    /// ```
+    pub fn with_snap_distance(&mut self, distance: F) -> &mut Self {
+        self.snap_distance_ = Some(distance);
+        self
+    }
+
+    /// Makes `build()` remove input points that are exact duplicates of a previous input
+    /// point, keeping the first one. The removed points are reported by `snapped_sites()`
+    /// just like the points merged by `with_snap_distance()`, and they are not considered
+    /// degenerate input by `strict()` or `build_with_warnings()`.
+    /// By default duplicates are not removed up front; they still collapse into a single
+    /// cell, but strict mode rejects them.
+    /// ```
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::builder::Builder;
+    /// let p = vec![Point { x: 0_i32, y: 0 }, Point { x: 50, y: 0 }, Point { x: 0, y: 0 }];
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.strict(true).dedup_points(true).with_vertices(p.iter()).unwrap();
+    /// let diagram = vb.build().unwrap();
+    /// assert_eq!(diagram.num_cells(), 2);
+    /// assert_eq!(vb.snapped_sites(), &[(2, 0)]);
+    /// ```
+    pub fn dedup_points(&mut self, dedup_points: bool) -> &mut Self {
+        self.dedup_points_ = dedup_points;
+        self
+    }
+
+    /// Returns the `(merged source index, kept source index)` pairs of the points collapsed
+    /// by `with_snap_distance()` or `dedup_points()`, sorted by the merged index. Only valid
+    /// after `build()`.
+    pub fn snapped_sites(&self) -> &[(VD::SourceIndex, VD::SourceIndex)] {
+        &self.snapped_sites_
+    }
+
+    /// Makes `build()` verify that the input segments only touch at their endpoints, using the
+    /// given algorithm. If two segments intersect `BvError::SelfIntersecting` is returned,
+    /// naming the source indices of the pair and a point they have in common. Segments
+    /// touching at their endpoints are fine. See `find_intersecting_segments()`.
+    /// By default no check is done.
+    /// ```
+    /// # use boostvoronoi::builder::{to_segments, Builder, IntersectionCheck};
+    /// # use boostvoronoi::BvError;
+    /// let s = to_segments::<i32, i32>(&[[0, 0, 10, 10], [0, 10, 10, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_intersection_check(IntersectionCheck::SweepLine)
+    ///     .with_segments(s.iter())
+    ///     .unwrap();
+    /// match vb.build() {
+    ///     Err(BvError::SelfIntersecting { seg_a, seg_b, point }) => {
+    ///         assert_eq!((seg_a, seg_b, point), (0, 1, [5.0, 5.0]))
+    ///     }
+    ///     _ => panic!("expected a SelfIntersecting error"),
+    /// }
+    /// ```
+    pub fn with_intersection_check(&mut self, method: IntersectionCheck) -> &mut Self {
+        self.intersection_check_ = Some(method);
+        self
+    }
+
     #[deprecated(since = "0.9.0", note = "Please use the build() function instead")]
     /// Run sweep-line algorithm and fill output data structure.
     pub fn construct(&mut self) -> Result<VD::Diagram<I, F>, BvError> {
         self.build()
     }
 
+    /// Just like `build()`, but also returns a report of the degenerate cases the builder had
+    /// to handle: the same cases strict mode rejects (see `strict()`). Every configuration is
+    /// reported once, in the order it was encountered.
+    /// If strict mode is enabled, the first degenerate case is still returned as an error.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// # use boostvoronoi::{DegeneracyWarning, DegenerateKind};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [5, 5], [10, 10], [20, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let (diagram, warnings) = vb.build_with_warnings()?;
+    /// assert_eq!(diagram.cells().len(), 4);
+    /// assert!(warnings.contains(&DegeneracyWarning {
+    ///     kind: DegenerateKind::CollinearPoints,
+    ///     indices: vec![2, 1, 0],
+    /// }));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn build_with_warnings(
+        &mut self,
+    ) -> Result<(VD::Diagram<I, F>, Vec<DegeneracyWarning>), BvError> {
+        self.warnings_ = Some(Vec::new());
+        let output = self.build();
+        let mut warnings = self.warnings_.take().unwrap_or_default();
+        let output = output?;
+        // the same configuration may be examined more than once by the sweep-line
+        let mut reported = BTreeSet::<DegeneracyWarning>::new();
+        warnings.retain(|w| reported.insert(w.clone()));
+        Ok((output, warnings))
+    }
+
+    /// Called every time a degenerate-handling branch is about to be taken.
+    /// Returns `BvError::DegenerateInput` in strict mode, otherwise the case is recorded if
+    /// warnings are collected.
+    fn report_degenerate(
+        &mut self,
+        kind: DegenerateKind,
+        indices: Vec<usize>,
+    ) -> Result<(), BvError> {
+        if self.strict_ {
+            return Err(BvError::DegenerateInput { kind, indices });
+        }
+        if let Some(warnings) = self.warnings_.as_mut() {
+            warnings.push(DegeneracyWarning { kind, indices });
+        }
+        Ok(())
+    }
+
     /// Run sweep-line algorithm and fill output data structure.
+    ///
+    /// Trivial input gives a well-formed diagram without any vertices:
+    /// * no input: an empty diagram.
+    /// * a single point: one cell without edges.
+    /// * two points: two cells separated by one infinite bisector, i.e. two half-edges.
+    /// * a single segment: three cells (the start point, the end point and the segment) and
+    ///   two infinite secondary edges through the endpoints, i.e. four half-edges.
+    /// * `n` collinear points: `n` cells separated by `n-1` parallel infinite bisectors, i.e.
+    ///   `2(n-1)` half-edges. There are no circle events, so no vertices. The same holds for
+    ///   points and segments that all lie on one line, counting the segment endpoints as points.
+    /// ```
+    /// # use boostvoronoi::builder::{to_segments, Builder};
+    /// assert_eq!(Builder::<i32, f64>::default().build()?.counts(), Default::default());
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_segments(to_segments::<i32, i32>(&[[0, 0, 10, 10]]).iter())?;
+    /// let output = vb.build()?;
+    /// assert_eq!((output.num_cells(), output.num_edges(), output.num_vertices()), (3, 4, 0));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    ///
+    /// `build()` does not consume the input, the builder keeps every point and segment added
+    /// so far. So more input can be added after a build, and the next `build()` runs a new
+    /// sweep over all of it.
+    /// ```
+    /// # use boostvoronoi::builder::{to_segments, Builder};
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_segments(to_segments::<i32, i32>(&[[0, 0, 10, 10]]).iter())?;
+    /// assert_eq!(vb.build()?.num_cells(), 3);
+    /// vb.with_segments(to_segments::<i32, i32>(&[[10, 10, 20, 0]]).iter())?;
+    /// assert_eq!(vb.build()?.num_cells(), 5);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
     pub fn build(&mut self) -> Result<VD::Diagram<I, F>, BvError> {
+        let output = self.sweep_();
+        // The sweep sorts the site events in place, the next build() simply does that again.
+        // Only the merged and the duplicate sites have to be restored.
+        self.site_events_.append(&mut self.removed_site_events_);
+        self.beach_line_.clear();
+        self.circle_events_.clear();
+        self.end_points_.clear();
+        self.previous_circle_ = None;
+        output
+    }
+
+    fn sweep_(&mut self) -> Result<VD::Diagram<I, F>, BvError> {
+        if let Some(method) = self.intersection_check_ {
+            self.check_intersections(method)?;
+        }
+        self.snap_points();
+        self.predicate_settings_ = VP::PredicateSettings::new(self.ulps_, self.lazy_only());
         let mut output: VD::Diagram<I, F> = VD::Diagram::<I, F>::new(self.site_events_.len());
 
+        if self.strict_ || self.warnings_.is_some() {
+            self.check_coincident_sites()?;
+        }
         let mut site_event_iterator_: VSE::SiteEventIndexType = self.init_sites_queue();
+        if self.farthest_point_ {
+            farthest::build_farthest_point(
+                &self.site_events_,
+                &mut output,
+                &self.predicate_settings_,
+            )?;
+            self.finish_build(&mut output);
+            return Ok(output);
+        }
+        if self.site_events_.len() < 2 {
+            // nothing to sweep: an empty diagram or the cell of a single point
+            if let Some(site) = self.site_events_.first() {
+                output.process_single_site_(site);
+            }
+            self.finish_build(&mut output);
+            return Ok(output);
+        }
 
         t!("********************************************************************************");
         tln!("\n->build()");
@@ -217,6 +1094,7 @@ where
                 &self.site_events_[site_event_iterator_],
                 // we checked with !is_empty(), unwrap is safe
                 &self.circle_events_.peek().unwrap().0.get(),
+                self.ulps_,
             ) {
                 self.process_site_event(&mut site_event_iterator_, &mut output)?;
             } else {
@@ -227,24 +1105,249 @@ where
         }
 
         self.beach_line_.clear();
+        self.finish_build(&mut output);
+        Ok(output)
+    }
 
-        // Finish construction.
+    /// Finish construction.
+    fn finish_build(&self, output: &mut VD::Diagram<I, F>) {
+        let exact_circle_events = self.predicate_settings_.exact_recomputations();
+        let segments = self.site_events_.iter().filter(|s| s.is_segment()).count();
+        let points = self
+            .site_events_
+            .iter()
+            .filter(|s| s.source_category() == VD::ColorBits::SINGLE_POINT__BIT)
+            .count();
+        output.set_build_stats_(points, segments, exact_circle_events);
+        output.set_sites_(
+            self.site_events_
+                .iter()
+                .map(|s| s.site_geometry())
+                .collect(),
+        );
         output._build();
-        Ok(output)
+        if self.drop_secondary_edges_ {
+            output.remove_secondary_edges_();
+        }
     }
 
-    pub(crate) fn init_sites_queue(&mut self) -> VSE::SiteEventIndexType {
-        // Sort site events.
+    /// Returns `BvError::SelfIntersecting` if any of the input segments intersect.
+    fn check_intersections(&self, method: IntersectionCheck) -> Result<(), BvError> {
+        let (segments, indices): (Vec<Line<I>>, Vec<usize>) = self
+            .site_events_
+            .iter()
+            .filter(|s| s.is_segment())
+            .map(|s| (Line::new(*s.point0(), *s.point1()), s.initial_index()))
+            .unzip();
+        match find_intersecting_segments(&segments, method) {
+            Some((a, b)) => Err(BvError::SelfIntersecting {
+                seg_a: indices[a],
+                seg_b: indices[b],
+                point: intersection::conflict_point(&segments[a], &segments[b]),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes the point sites within `snap_distance_` of a previously kept point site, or
+    /// only the exact duplicates if `dedup_points_` is set.
+    fn snap_points(&mut self) {
+        self.snapped_sites_.clear();
+        let distance = match self.snap_distance_ {
+            Some(distance) if distance > F::zero() => distance,
+            _ if self.dedup_points_ => F::zero(),
+            _ => return,
+        };
+        let is_single_point = |s: &VSE::SiteEvent<I, F>| {
+            s.is_point() && s.source_category() == VD::ColorBits::SINGLE_POINT__BIT
+        };
+        let mut points: Vec<(F, F, VD::SourceIndex)> = self
+            .site_events_
+            .iter()
+            .filter(|s| is_single_point(s))
+            .map(|s| {
+                (
+                    TC2::<I, F>::i_to_f(s.x0()),
+                    TC2::<I, F>::i_to_f(s.y0()),
+                    s.initial_index(),
+                )
+            })
+            .collect();
+        points.sort_by(|a, b| {
+            a.0.partial_cmp(&b.0)
+                .unwrap()
+                .then(a.1.partial_cmp(&b.1).unwrap())
+                .then(a.2.cmp(&b.2))
+        });
+
+        // the kept points, ordered by x
+        let mut kept: Vec<(F, F, VD::SourceIndex)> = Vec::new();
+        let mut merged = vec![false; self.index_];
+        for p in points {
+            let first = kept.partition_point(|k| k.0 < p.0 - distance);
+            let nearest = kept[first..]
+                .iter()
+                .map(|k| ((k.0 - p.0).hypot(k.1 - p.1), k.2))
+                .filter(|(d, _)| *d < distance || *d == F::zero())
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            if let Some((_, kept_index)) = nearest {
+                merged[p.2] = true;
+                self.snapped_sites_.push((p.2, kept_index));
+            } else {
+                kept.push(p);
+            }
+        }
+        if self.snapped_sites_.is_empty() {
+            return;
+        }
+        self.snapped_sites_.sort_unstable();
+        let (merged_sites, kept_sites): (Vec<_>, Vec<_>) = core::mem::take(&mut self.site_events_)
+            .into_iter()
+            .partition(|s| is_single_point(s) && merged[s.initial_index()]);
+        self.site_events_ = kept_sites;
+        self.removed_site_events_.extend(merged_sites);
+    }
+
+    /// Sorts the site events and reports the pairs of coincident points.
+    /// Coincident segment endpoints are allowed, they are a natural part of polylines.
+    fn check_coincident_sites(&mut self) -> Result<(), BvError> {
         self.site_events_
             .sort_by(VP::EventComparisonPredicate::<I, F>::event_comparison_predicate_ii);
+        let is_single_point = |s: &VSE::SiteEvent<I, F>| {
+            s.is_point() && s.source_category() == VD::ColorBits::SINGLE_POINT__BIT
+        };
+        let coincident: Vec<Vec<usize>> = self
+            .site_events_
+            .windows(2)
+            .filter(|w| w[0] == w[1] && (is_single_point(&w[0]) || is_single_point(&w[1])))
+            .map(|w| {
+                let mut indices = vec![w[0].initial_index(), w[1].initial_index()];
+                indices.sort_unstable();
+                indices
+            })
+            .collect();
+        for indices in coincident {
+            self.report_degenerate(DegenerateKind::CoincidentPoints, indices)?;
+        }
+        Ok(())
+    }
+
+    /// Reports a circle event at the same position as the previous one, i.e. four or more sites
+    /// on one circle. Every circle event creates a vertex, the vertices at the same position
+    /// are merged when the diagram is finished. The zero radius circles at the shared endpoint
+    /// of segments are not reported.
+    fn check_cocircular_sites(
+        &mut self,
+        circle: &VC::CircleEvent,
+        sites: [&VSE::SiteEvent<I, F>; 3],
+    ) -> Result<(), BvError> {
+        if circle.is_site_point() {
+            return Ok(());
+        }
+        let position = [circle.raw_x(), circle.raw_y(), circle.raw_lower_x()];
+        let indices = [
+            sites[0].initial_index(),
+            sites[1].initial_index(),
+            sites[2].initial_index(),
+        ];
+        if let Some((previous_position, previous_indices)) =
+            self.previous_circle_.replace((position, indices))
+        {
+            if position.iter().zip(previous_position.iter()).all(|(a, b)| {
+                CT::UlpComparison::ulp_comparison(*a, *b, 128) == core::cmp::Ordering::Equal
+            }) {
+                let mut indices: Vec<usize> = previous_indices
+                    .iter()
+                    .chain(indices.iter())
+                    .copied()
+                    .collect();
+                indices.sort_unstable();
+                indices.dedup();
+                return self.report_degenerate(DegenerateKind::CocircularSites, indices);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reports a new point site that splits the arc of a segment it lies on, i.e. an input point
+    /// or a segment endpoint touching the interior of another segment.
+    fn check_point_on_segment(
+        &mut self,
+        site_arc: &VSE::SiteEvent<I, F>,
+        site_event: &VSE::SiteEvent<I, F>,
+    ) -> Result<(), BvError> {
+        if !site_arc.is_segment() || site_event.is_segment() {
+            return Ok(());
+        }
+        let (start, end, point) = (site_arc.point0(), site_arc.point1(), site_event.point0());
+        if point == start
+            || point == end
+            || VP::OrientationTest::<I, F>::eval_p(start, end, point) != VP::Orientation::Collinear
+            || point.x < start.x.min(end.x)
+            || point.x > start.x.max(end.x)
+            || point.y < start.y.min(end.y)
+            || point.y > start.y.max(end.y)
+        {
+            return Ok(());
+        }
+        self.report_degenerate(
+            DegenerateKind::PointOnSegment,
+            vec![site_arc.initial_index(), site_event.initial_index()],
+        )
+    }
+
+    /// Sorts the site events in the order of the sweep-line, removes the duplicates and sets
+    /// the sorted indices. Returns the removed duplicates.
+    fn sort_sites(sites: &mut Vec<VSE::SiteEvent<I, F>>) -> Vec<VSE::SiteEvent<I, F>> {
+        // Sort site events.
+        sites.sort_by(VP::EventComparisonPredicate::<I, F>::event_comparison_predicate_ii);
 
         // Remove duplicates.
-        self.site_events_.dedup();
+        let mut duplicates = Vec::new();
+        sites.dedup_by(|a, b| {
+            let duplicate = a == b;
+            if duplicate {
+                duplicates.push(*a);
+            }
+            duplicate
+        });
 
         // Index sites.
-        for (cur, s) in self.site_events_.iter_mut().enumerate() {
+        for (cur, s) in sites.iter_mut().enumerate() {
             s.set_sorted_index(cur);
         }
+        duplicates
+    }
+
+    /// Returns a copy of the site events in the order the sweep-line processes them, for
+    /// debugging purposes. The list is sorted and the duplicates are removed just like
+    /// `build()` does, `SiteEvent::sorted_index()` is the position in the list and the id of
+    /// the cell the site creates. Every input segment is represented by three sites: the two
+    /// endpoints and the segment itself, which goes from its lowest to its highest endpoint
+    /// (see `SiteEvent::new_3()`). The shared endpoint of two segments is one site. Points merged by
+    /// `with_snap_distance()` or `dedup_points()` are still listed.
+    /// The builder is not modified.
+    /// ```
+    /// # use boostvoronoi::builder::{to_segments, Builder};
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_segments(to_segments::<i32, i32>(&[[10, 10, 0, 0], [10, 10, 20, 0]]).iter())?;
+    /// let sites = vb.site_events();
+    /// assert_eq!(sites.len(), 5);
+    /// assert!(sites[1].is_segment() && sites[3].is_segment());
+    /// assert_eq!(sites[1].point0(), sites[0].point0());
+    /// assert_eq!(sites[1].initial_index(), 0);
+    /// assert_eq!(vb.build()?.num_cells(), 5);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn site_events(&self) -> Vec<VSE::SiteEvent<I, F>> {
+        let mut sites = self.site_events_.clone();
+        let _ = Self::sort_sites(&mut sites);
+        sites
+    }
+
+    pub(crate) fn init_sites_queue(&mut self) -> VSE::SiteEventIndexType {
+        let duplicates = Self::sort_sites(&mut self.site_events_);
+        self.removed_site_events_.extend(duplicates);
         #[cfg(feature = "console_debug")]
         {
             tln!("post dedup:");
@@ -282,6 +1385,15 @@ where
                 skip += 1;
             }
 
+            if skip > 1 {
+                let mut indices: Vec<usize> = self.site_events_[..skip]
+                    .iter()
+                    .map(|s| s.initial_index())
+                    .collect();
+                indices.sort_unstable();
+                indices.dedup();
+                self.report_degenerate(DegenerateKind::VerticalCollinearSites, indices)?;
+            }
             if skip == 1 {
                 // Init beach line with the first two sites.
                 self.init_beach_line_default(site_event_iterator_, output)?;
@@ -415,7 +1527,7 @@ where
                 {
                     // we checked with !is_empty(), unwrap is safe
                     let b_it = self.end_points_.pop().unwrap();
-                    let mut b_it = cpp_map::PIterator::new_2(
+                    let mut b_it = PIterator::new_2(
                         Rc::clone(&self.beach_line_.beach_line_),
                         b_it.beachline_index().0,
                     );
@@ -576,19 +1688,22 @@ where
                 // Add candidate circles to the circle event queue.
                 // There could be up to two circle events formed by
                 // a new bisector and the one on the left or right.
-                self.activate_circle_event(site1, site_arc1, site_event, new_node_it)?;
+                let left = (site1, site_arc1, site_event, new_node_it);
 
                 // If the site event is a segment, update its direction.
                 if site_event.is_segment() {
                     let _ = site_event.inverse();
                 }
 
-                self.activate_circle_event(
-                    site_event,
-                    site_arc2,
-                    site3,
-                    VB::BeachLineIndex(right_it.current()),
-                )?;
+                self.activate_circle_events([
+                    Some(left),
+                    Some((
+                        site_event,
+                        site_arc2,
+                        site3,
+                        VB::BeachLineIndex(right_it.current()),
+                    )),
+                ])?;
                 //right_it = new_node_it;
                 right_it = self.beach_line_.get_pointer(new_node_it)?;
             }
@@ -651,6 +1766,7 @@ where
         }
         // Get the C site.
         let site3 = *it_first.get_k()?.right_site();
+        let site2 = *it_first.get_k()?.left_site();
 
         // Get the half-edge corresponding to the second bisector - (B, C).
         let bisector2 = it_first
@@ -674,6 +1790,9 @@ where
 
         // Get the A site.
         let site1 = *it_first.get_k()?.left_site();
+        if self.strict_ || self.warnings_.is_some() {
+            self.check_cocircular_sites(&circle_event, [&site1, &site2, &site3])?;
+        }
         #[allow(clippy::suspicious_operation_groupings)]
         let site3 = if !site1.is_segment() && site3.is_segment() && site3.point1() == site1.point0()
         {
@@ -727,7 +1846,7 @@ where
         // Insert the new bisector into the beach line.
         {
             let edge = output
-                ._insert_new_edge_5(site1, site3, circle_event, bisector1, bisector2)
+                ._insert_new_edge_5(site1, site3, circle_event, bisector1, bisector2)?
                 .0;
             let data = if let Some(ref mut node) = it_first.get_v()?.get() {
                 let _ = node.set_edge_id(edge);
@@ -764,7 +1883,7 @@ where
         // Remove the (B, C) bisector node from the beach line.
         if it_first.current() == it_last.current() {
             // todo: is this correct?
-            println!("------- it_first.next()?");
+            tln!("------- it_first.next()?");
             it_first.next()?;
         }
         #[cfg(feature = "console_debug")]
@@ -792,7 +1911,7 @@ where
 
         // Check new triplets formed by the neighboring arcs
         // to the left for potential circle events.
-        if !self.beach_line_.is_empty() && !it_first.is_at_head()? {
+        let left = if !self.beach_line_.is_empty() && !it_first.is_at_head()? {
             self.circle_events_.deactivate(
                 it_first
                     .get_v()?
@@ -803,33 +1922,27 @@ where
             it_first.prev()?;
 
             let site_l1 = *it_first.get_k()?.left_site();
-            self.activate_circle_event(
-                site_l1,
-                site1,
-                site3,
-                VB::BeachLineIndex(it_last.current()),
-            )?;
-        }
+            Some((site_l1, site1, site3, VB::BeachLineIndex(it_last.current())))
+        } else {
+            None
+        };
 
         // Check the new triplet formed by the neighboring arcs
         // to the right for potential circle events.
 
         it_last.next()?;
 
-        if it_last.is_ok()? {
+        let right = if it_last.is_ok()? {
             let it_last_node = it_last.get_v()?;
             self.circle_events_
                 .deactivate(it_last_node.get().and_then(|x| x.get_circle_event_id()));
 
             let site_r1 = *it_last.get_k()?.right_site();
-            self.activate_circle_event(
-                site1,
-                site3,
-                site_r1,
-                VB::BeachLineIndex(it_last.current()),
-            )?;
-        }
-        Ok(())
+            Some((site1, site3, site_r1, VB::BeachLineIndex(it_last.current())))
+        } else {
+            None
+        };
+        self.activate_circle_events([left, right])
     }
 
     /// Insert new nodes into the beach line. Update the output.
@@ -847,6 +1960,9 @@ where
             site_arc2,
             site_event
         );
+        if self.strict_ || self.warnings_.is_some() {
+            self.check_point_on_segment(&site_arc2, &site_event)?;
+        }
         // Create two new bisectors with opposite directions.
         let new_left_node = VB::BeachLineNodeKey::<I, F>::new_2(site_arc1, site_event);
         let new_right_node =
@@ -934,13 +2050,69 @@ where
         site3: VSE::SiteEvent<I, F>,
         bisector_node: VB::BeachLineIndex,
     ) -> Result<(), BvError> {
-        // Check if the three input sites create a circle event.
-        let c_event = VC::CircleEvent::new_1(bisector_node);
-        let c_event = VC::CircleEventC::new_1(c_event);
+        let (formation, c_event) = Self::evaluate_circle_event(
+            &site1,
+            &site2,
+            &site3,
+            bisector_node,
+            &self.predicate_settings_,
+        );
+        self.push_circle_event(site1, site2, site3, bisector_node, formation, c_event)
+    }
+
+    /// Same as calling `activate_circle_event()` for each of the candidates, in order.
+    fn activate_circle_events(
+        &mut self,
+        candidates: [Option<CircleCandidate<I, F>>; 2],
+    ) -> Result<(), BvError> {
+        for &(site1, site2, site3, bisector_node) in candidates.iter().flatten() {
+            self.activate_circle_event(site1, site2, site3, bisector_node)?;
+        }
+        Ok(())
+    }
+
+    /// Checks if the three input sites create a circle event.
+    fn evaluate_circle_event(
+        site1: &VSE::SiteEvent<I, F>,
+        site2: &VSE::SiteEvent<I, F>,
+        site3: &VSE::SiteEvent<I, F>,
+        bisector_node: VB::BeachLineIndex,
+        settings: &VP::PredicateSettings,
+    ) -> (VP::CircleFormation, VC::CircleEvent) {
+        let c_event = VC::CircleEventC::new_1(VC::CircleEvent::new_1(bisector_node));
+        let formation = VP::CircleFormationFunctor::<I, F>::circle_formation_predicate(
+            site1, site2, site3, &c_event, settings,
+        );
+        (formation, c_event.0.get())
+    }
 
-        if VP::CircleFormationFunctor::<I, F>::circle_formation_predicate(
-            &site1, &site2, &site3, &c_event,
-        ) {
+    /// Adds an evaluated circle event to the event queue, if it was formed.
+    fn push_circle_event(
+        &mut self,
+        site1: VSE::SiteEvent<I, F>,
+        site2: VSE::SiteEvent<I, F>,
+        site3: VSE::SiteEvent<I, F>,
+        bisector_node: VB::BeachLineIndex,
+        formation: VP::CircleFormation,
+        c_event: VC::CircleEvent,
+    ) -> Result<(), BvError> {
+        let c_event = VC::CircleEventC::new_1(c_event);
+        let kind = match formation {
+            VP::CircleFormation::Collinear => Some(DegenerateKind::CollinearPoints),
+            VP::CircleFormation::OutsideVerticalSegment => Some(DegenerateKind::VerticalSegment),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            self.report_degenerate(
+                kind,
+                vec![
+                    site1.initial_index(),
+                    site2.initial_index(),
+                    site3.initial_index(),
+                ],
+            )?;
+        }
+        if formation == VP::CircleFormation::Formed {
             // Add the new circle event to the circle events queue.
             // Update bisector's circle event iterator to point to the
             // new circle event in the circle event queue.
@@ -980,6 +2152,43 @@ where
     }
 }
 
+/// Convenience function: builds the Voronoi diagram of the points and returns the dual Delaunay
+/// triangulation as triangles of input point indices.
+/// Each Voronoi vertex yields one triangle, vertices with more than three incident edges (i.e.
+/// cocircular input points) are split into a triangle fan.
+/// Input containing only collinear points yields no triangles.
+/// Duplicate points are only represented by one of the indices.
+/// ```
+/// # use boostvoronoi::geometry::Point;
+/// # use boostvoronoi::builder::delaunay;
+/// let p = vec![Point { x: 0_i32, y: 0 }, Point { x: 10, y: 0 }, Point { x: 5, y: 10 }];
+/// let mut triangles = delaunay(&p).unwrap();
+/// triangles[0].sort_unstable();
+/// assert_eq!(triangles, vec![[0, 1, 2]]);
+/// ```
+pub fn delaunay<I>(points: &[Point<I>]) -> Result<Vec<[VD::SourceIndex; 3]>, BvError>
+where
+    I: InputType + Neg<Output = I>,
+{
+    let mut vb = Builder::<I, f64>::default();
+    vb.with_vertices(points.iter())?;
+    let diagram = vb.build()?;
+
+    let mut rv = Vec::with_capacity(diagram.num_vertices());
+    for vertex in diagram.vertex_iter() {
+        let incident_edge = vertex.get().get_incident_edge()?;
+        let mut sites = Vec::<VD::SourceIndex>::with_capacity(3);
+        for edge_id in diagram.edge_rot_next_iterator(incident_edge) {
+            let cell_id = diagram.edge_get_cell(edge_id)?;
+            sites.push(diagram.get_cell(cell_id)?.get().source_index());
+        }
+        for i in 1..sites.len().saturating_sub(1) {
+            rv.push([sites[0], sites[i], sites[i + 1]]);
+        }
+    }
+    Ok(rv)
+}
+
 /// Helper function: converts a slice of \[\[integer,integer\]\] into input data for the Builder.
 /// You should use the From traits instead, this function performs a (potentially) redundant type conversion.
 pub fn to_points<I: InputType, F: InputType>(points: &[[I; 2]]) -> Vec<Point<F>> {
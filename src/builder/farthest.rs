@@ -0,0 +1,167 @@
+//! Construction of the farthest-point Voronoi diagram, see `Builder::farthest_point()`.
+//!
+//! Only the points on the convex hull have a (non-empty) cell in the farthest-point diagram.
+//! The diagram is the dual of the farthest-point Delaunay triangulation of the hull, which is
+//! built by repeatedly removing the hull point whose circle, through the point and its two
+//! neighbours, is the largest. That circle contains all the remaining points, so its center is
+//! a vertex of the diagram (Skyum's algorithm). The remaining hull points form a cycle that
+//! plays the role of the beach-line: every pair of neighbours has a bisector edge, and every
+//! removal is a circle event.
+
+use crate::beach_line as VB;
+use crate::circle_event as VC;
+use crate::diagram as VD;
+use crate::predicate as VP;
+use crate::site_event as VSE;
+use crate::{BvError, InputType, OutputType};
+use alloc::collections::BinaryHeap;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Neg;
+use ordered_float::OrderedFloat;
+
+/// Returns the indices of the sites on the convex hull in clockwise order, starting with the
+/// lowest (x, y) site. Points in the interior of a hull edge are not included.
+/// The sites must be sorted by (x, y), and may not contain duplicates.
+fn clockwise_hull<I, F>(sites: &[VSE::SiteEvent<I, F>]) -> Vec<usize>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let is_left_turn = |hull: &[usize], c: usize| {
+        let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+        VP::OrientationTest::<I, F>::eval_p(sites[a].point0(), sites[b].point0(), sites[c].point0())
+            == VP::Orientation::Left
+    };
+    // Andrew's monotone chain, the lower hull then the upper hull in counter-clockwise order
+    let mut hull = Vec::<usize>::with_capacity(sites.len() + 1);
+    for i in 0..sites.len() {
+        while hull.len() >= 2 && !is_left_turn(&hull, i) {
+            let _ = hull.pop();
+        }
+        hull.push(i);
+    }
+    let lower_len = hull.len() + 1;
+    for i in (0..sites.len().saturating_sub(1)).rev() {
+        while hull.len() >= lower_len && !is_left_turn(&hull, i) {
+            let _ = hull.pop();
+        }
+        hull.push(i);
+    }
+    // the first point is repeated at the end
+    let _ = hull.pop();
+    hull[1..].reverse();
+    hull
+}
+
+/// Computes the circle event of three hull sites in clockwise order.
+fn circle<I, F>(
+    site1: &VSE::SiteEvent<I, F>,
+    site2: &VSE::SiteEvent<I, F>,
+    site3: &VSE::SiteEvent<I, F>,
+    settings: &VP::PredicateSettings,
+) -> Result<VC::CircleEvent, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let c_event = VC::CircleEventC::new_1(VC::CircleEvent::new_1(VB::BeachLineIndex(0)));
+    if VP::CircleFormationFunctor::<I, F>::circle_formation_predicate(
+        site1, site2, site3, &c_event, settings,
+    ) != VP::CircleFormation::Formed
+    {
+        return Err(BvError::InternalError(format!(
+            "The hull sites {:?}, {:?} and {:?} did not form a circle. {}:{}",
+            site1.point0(),
+            site2.point0(),
+            site3.point0(),
+            file!(),
+            line!()
+        )));
+    }
+    Ok(c_event.0.get())
+}
+
+/// Builds the farthest-point Voronoi diagram of the sites into `output`.
+/// The sites must be sorted and indexed, i.e. `Builder::init_sites_queue()` must have been
+/// called. Returns `BvError::ValueError` if any of the sites is a segment.
+/// The circle events are evaluated, and counted, with `settings`.
+pub(crate) fn build_farthest_point<I, F>(
+    sites: &[VSE::SiteEvent<I, F>],
+    output: &mut VD::Diagram<I, F>,
+    settings: &VP::PredicateSettings,
+) -> Result<(), BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    if let Some(segment) = sites.iter().find(|s| s.is_segment()) {
+        return Err(BvError::ValueError(format!(
+            "The farthest-point diagram only supports point input, found the segment {:?}-{:?}",
+            segment.point0(),
+            segment.point1()
+        )));
+    }
+    output.process_all_sites_(sites);
+    if sites.len() < 2 {
+        return Ok(());
+    }
+    let hull: Vec<&VSE::SiteEvent<I, F>> = clockwise_hull(sites)
+        .into_iter()
+        .map(|i| &sites[i])
+        .collect();
+    let h = hull.len();
+    if h == 2 {
+        // all the points are collinear, only the two extreme points have a cell
+        let _ = output._insert_bisector(hull[0], hull[1]);
+        return Ok(());
+    }
+
+    // The cycle of the remaining hull sites, the bisector of the site and its next site is
+    // represented by a half-edge that belongs to the site.
+    let mut next: Vec<usize> = (1..=h).map(|i| i % h).collect();
+    let mut prev: Vec<usize> = (0..h).map(|i| (i + h - 1) % h).collect();
+    let mut edge: Vec<VD::EdgeIndex> = (0..h)
+        .map(|i| output._insert_bisector(hull[i], hull[(i + 1) % h]).0)
+        .collect();
+
+    // (radius, hull index of the middle site, generation) of the circle events
+    let mut generation = vec![0_usize; h];
+    let mut circle_events = BinaryHeap::<(OrderedFloat<f64>, usize, usize)>::with_capacity(h);
+    let radius = |c: &VC::CircleEvent| OrderedFloat(c.lower_x().into_inner() - c.raw_x());
+    for i in 0..h {
+        let c = circle(hull[prev[i]], hull[i], hull[next[i]], settings)?;
+        circle_events.push((radius(&c), i, 0));
+    }
+
+    let mut remaining = h;
+    while remaining > 3 {
+        let (_, middle, event_generation) = circle_events.pop().ok_or_else(|| {
+            BvError::InternalError(format!("Ran out of circle events. {}:{}", file!(), line!()))
+        })?;
+        if event_generation != generation[middle] {
+            // the neighbours of the site have changed since the event was created
+            continue;
+        }
+        let (left, right) = (prev[middle], next[middle]);
+        let c = circle(hull[left], hull[middle], hull[right], settings)?;
+        let (new_edge, _) =
+            output._insert_new_edge_5(*hull[left], *hull[right], c, edge[left], edge[middle])?;
+        edge[left] = new_edge;
+        next[left] = right;
+        prev[right] = left;
+        generation[middle] = usize::MAX;
+        remaining -= 1;
+        for i in [left, right] {
+            generation[i] += 1;
+            let c = circle(hull[prev[i]], hull[i], hull[next[i]], settings)?;
+            circle_events.push((radius(&c), i, generation[i]));
+        }
+    }
+    // the three last sites surround the final vertex
+    let a = (0..h).find(|i| generation[*i] != usize::MAX).unwrap_or(0);
+    let (b, c) = (next[a], next[next[a]]);
+    let circle_event = circle(hull[a], hull[b], hull[c], settings)?;
+    output._insert_last_vertex(circle_event, edge[a], edge[b], edge[c])
+}
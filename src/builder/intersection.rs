@@ -0,0 +1,232 @@
+//! Validation of the input segments: segments may only touch at their endpoints.
+
+use crate::geometry::{Line, Point};
+use crate::predicate::{Orientation, OrientationTest};
+use crate::InputType;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::Neg;
+
+/// The algorithm used to look for intersecting input segments.
+/// See `Builder::with_intersection_check()` and `find_intersecting_segments()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum IntersectionCheck {
+    /// Tests every pair of segments, O(n²). Simple, and fast enough for small inputs.
+    #[default]
+    BruteForce,
+    /// A Shamos-Hoey sweep-line, O(n*log(n)) predicate evaluations. Use this for large inputs.
+    /// The sweep-line status is a sorted `Vec`, so every insertion and removal also shifts the
+    /// indices of up to `k` active segments, `k` being the number of segments crossing the
+    /// sweep-line. That is O(n*k) in total, O(n²) in the worst case, but these are plain memory
+    /// moves, much cheaper than the predicates.
+    SweepLine,
+}
+
+#[inline(always)]
+fn orientation<I: InputType + Neg<Output = I>>(
+    p1: &Point<I>,
+    p2: &Point<I>,
+    p3: &Point<I>,
+) -> Orientation {
+    OrientationTest::<I, f64>::eval_p(p1, p2, p3)
+}
+
+/// The sweep-line order, x first then y.
+#[inline(always)]
+fn cmp_points<I: InputType>(a: &Point<I>, b: &Point<I>) -> Ordering {
+    (a.x, a.y).cmp(&(b.x, b.y))
+}
+
+/// Returns the endpoints of the segment in sweep-line order.
+#[inline(always)]
+fn sorted<I: InputType>(s: &Line<I>) -> (Point<I>, Point<I>) {
+    if cmp_points(&s.start, &s.end) == Ordering::Greater {
+        (s.end, s.start)
+    } else {
+        (s.start, s.end)
+    }
+}
+
+/// Returns true if the two segments have any point in common, other than a shared endpoint.
+pub(crate) fn segments_conflict<I: InputType + Neg<Output = I>>(a: &Line<I>, b: &Line<I>) -> bool {
+    let o1 = orientation(&a.start, &a.end, &b.start);
+    let o2 = orientation(&a.start, &a.end, &b.end);
+    if o1 == Orientation::Collinear && o2 == Orientation::Collinear {
+        // the segments are on the same line, they conflict if the overlap has a length
+        let (a0, a1) = sorted(a);
+        let (b0, b1) = sorted(b);
+        let lo = if cmp_points(&a0, &b0) == Ordering::Greater {
+            a0
+        } else {
+            b0
+        };
+        let hi = if cmp_points(&a1, &b1) == Ordering::Less {
+            a1
+        } else {
+            b1
+        };
+        return cmp_points(&lo, &hi) == Ordering::Less;
+    }
+    let o3 = orientation(&b.start, &b.end, &a.start);
+    let o4 = orientation(&b.start, &b.end, &a.end);
+    if o1 == o2 || o3 == o4 {
+        return false;
+    }
+    // the segments intersect in a single point, that is fine if it is a shared endpoint
+    !(a.start == b.start || a.start == b.end || a.end == b.start || a.end == b.end)
+}
+
+/// Returns a point the two conflicting segments have in common, see `segments_conflict()`.
+/// For overlapping collinear segments the lowest (x, y) point of the overlap is returned.
+pub(crate) fn conflict_point<I: InputType + Neg<Output = I>>(a: &Line<I>, b: &Line<I>) -> [f64; 2] {
+    let f = |p: &Point<I>| {
+        [
+            num::cast::<I, f64>(p.x).unwrap(),
+            num::cast::<I, f64>(p.y).unwrap(),
+        ]
+    };
+    let (a0, a1) = sorted(a);
+    let (b0, b1) = sorted(b);
+    if orientation(&a0, &a1, &b0) == Orientation::Collinear
+        && orientation(&a0, &a1, &b1) == Orientation::Collinear
+    {
+        return if cmp_points(&a0, &b0) == Ordering::Greater {
+            f(&a0)
+        } else {
+            f(&b0)
+        };
+    }
+    let ([ax, ay], [bx, by]) = (f(&a0), f(&b0));
+    let (adx, ady) = (f(&a1)[0] - ax, f(&a1)[1] - ay);
+    let (bdx, bdy) = (f(&b1)[0] - bx, f(&b1)[1] - by);
+    let t = ((bx - ax) * bdy - (by - ay) * bdx) / (adx * bdy - ady * bdx);
+    [ax + t * adx, ay + t * ady]
+}
+
+fn brute_force<I: InputType + Neg<Output = I>>(segments: &[Line<I>]) -> Option<(usize, usize)> {
+    for (i, a) in segments.iter().enumerate() {
+        for (j, b) in segments.iter().enumerate().skip(i + 1) {
+            if segments_conflict(a, b) {
+                return Some((i, j));
+            }
+        }
+    }
+    None
+}
+
+/// Shamos-Hoey: only segments that are neighbours along the sweep-line can intersect first.
+/// The sweep-line is kept ordered from below to above, and every time two segments become
+/// neighbours they are tested. Segments sharing an endpoint are removed and inserted at that
+/// endpoint, so they never pass each other.
+/// The status is a `Vec`, found by binary search but updated in O(k) for `k` active segments.
+/// A balanced tree would need an order that depends on the position of the sweep-line.
+fn sweep_line<I: InputType + Neg<Output = I>>(segments: &[Line<I>]) -> Option<(usize, usize)> {
+    let sorted_segments: Vec<(Point<I>, Point<I>)> = segments.iter().map(sorted).collect();
+    // (point, is_insert, segment)
+    let mut events: Vec<(Point<I>, bool, usize)> = Vec::with_capacity(segments.len() * 2);
+    for (i, (l, r)) in sorted_segments.iter().enumerate() {
+        if l != r {
+            events.push((*l, true, i));
+            events.push((*r, false, i));
+        }
+    }
+    // removals before insertions at the same point
+    events.sort_by(|a, b| cmp_points(&a.0, &b.0).then(a.1.cmp(&b.1)));
+
+    let conflict = |a: usize, b: usize| {
+        if segments_conflict(&segments[a], &segments[b]) {
+            Some((a.min(b), a.max(b)))
+        } else {
+            None
+        }
+    };
+    // the active segments, from below to above
+    let mut status = Vec::<usize>::new();
+    let mut inserted = Vec::<usize>::new();
+    let mut event_index = 0;
+    while event_index < events.len() {
+        let p = events[event_index].0;
+        let event_segment = events[event_index].2;
+        // the segments passing through p form a continuous range of the sweep-line
+        let lower = status.partition_point(|s| {
+            let (l, r) = sorted_segments[*s];
+            orientation(&l, &r, &p) == Orientation::Left
+        });
+        let upper = status.partition_point(|s| {
+            let (l, r) = sorted_segments[*s];
+            orientation(&l, &r, &p) != Orientation::Right
+        });
+        for s in status[lower..upper].iter() {
+            if sorted_segments[*s].1 != p {
+                // p is in the interior of an active segment
+                return Some(((*s).min(event_segment), (*s).max(event_segment)));
+            }
+        }
+        let _ = status.drain(lower..upper);
+        while event_index < events.len() && events[event_index].0 == p {
+            if events[event_index].1 {
+                inserted.push(events[event_index].2);
+            }
+            event_index += 1;
+        }
+        if inserted.is_empty() {
+            // the segments below and above the removed ones are now neighbours
+            if lower > 0 && lower < status.len() {
+                if let Some(rv) = conflict(status[lower - 1], status[lower]) {
+                    return Some(rv);
+                }
+            }
+            continue;
+        }
+        // order the new segments by their direction, from below to above
+        let mut collinear: Option<(usize, usize)> = None;
+        inserted.sort_by(|a, b| {
+            match orientation(&p, &sorted_segments[*a].1, &sorted_segments[*b].1) {
+                Orientation::Left => Ordering::Less,
+                Orientation::Right => Ordering::Greater,
+                Orientation::Collinear => {
+                    let _ = collinear.get_or_insert(((*a).min(*b), (*a).max(*b)));
+                    Ordering::Equal
+                }
+            }
+        });
+        if collinear.is_some() {
+            return collinear;
+        }
+        if lower > 0 {
+            if let Some(rv) = conflict(status[lower - 1], inserted[0]) {
+                return Some(rv);
+            }
+        }
+        if lower < status.len() {
+            if let Some(rv) = conflict(inserted[inserted.len() - 1], status[lower]) {
+                return Some(rv);
+            }
+        }
+        let _ = status.splice(lower..lower, inserted.drain(..));
+    }
+    None
+}
+
+/// Looks for two segments having any point in common other than a shared endpoint, e.g.
+/// crossing segments, an endpoint touching the interior of another segment or overlapping
+/// collinear segments. Such input is not supported by the builder.
+/// Returns the indices of the first intersecting pair found (lowest index first), or `None`.
+/// Zero length segments are ignored.
+/// ```
+/// # use boostvoronoi::builder::{find_intersecting_segments, to_segments, IntersectionCheck};
+/// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0], [10, 0, 10, 10], [5, -5, 5, 5]]);
+/// for method in [IntersectionCheck::BruteForce, IntersectionCheck::SweepLine].iter() {
+///     assert_eq!(find_intersecting_segments(&segments, *method), Some((0, 2)));
+///     assert_eq!(find_intersecting_segments(&segments[0..2], *method), None);
+/// }
+/// ```
+pub fn find_intersecting_segments<I: InputType + Neg<Output = I>>(
+    segments: &[Line<I>],
+    method: IntersectionCheck,
+) -> Option<(usize, usize)> {
+    match method {
+        IntersectionCheck::BruteForce => brute_force(segments),
+        IntersectionCheck::SweepLine => sweep_line(segments),
+    }
+}
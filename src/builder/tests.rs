@@ -1,4 +1,5 @@
 #![allow(unused_imports)]
+#![allow(clippy::get_first, clippy::useless_vec)]
 use super::super::diagram::Diagram;
 use super::super::BvError;
 use super::Builder;
@@ -33,3 +34,100 @@ fn sort_1() {
         }
     }
 }
+
+#[test]
+fn intersection_check_1() {
+    use super::{find_intersecting_segments, IntersectionCheck};
+    // compare the two algorithms on random short segments in a small grid, creating plenty of
+    // shared endpoints, collinear overlaps and vertical segments
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = |m: i32| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % m as u64) as i32
+    };
+    let mut found = 0;
+    for _ in 0..2000 {
+        let n = 2 + random(6);
+        let segments: Vec<Line<i32>> = (0..n)
+            .map(|_| {
+                Line::new(
+                    Point {
+                        x: random(5),
+                        y: random(5),
+                    },
+                    Point {
+                        x: random(5),
+                        y: random(5),
+                    },
+                )
+            })
+            .filter(|l| l.start != l.end)
+            .collect();
+        let brute_force = find_intersecting_segments(&segments, IntersectionCheck::BruteForce);
+        let sweep_line = find_intersecting_segments(&segments, IntersectionCheck::SweepLine);
+        assert_eq!(
+            brute_force.is_some(),
+            sweep_line.is_some(),
+            "{:?} {:?}",
+            segments,
+            sweep_line
+        );
+        if let Some((a, b)) = sweep_line {
+            assert!(a < b);
+            assert!(super::intersection::segments_conflict(
+                &segments[a],
+                &segments[b]
+            ));
+            found += 1;
+        }
+    }
+    // both outcomes must be well represented
+    assert!(found > 200 && found < 1800);
+}
+
+#[test]
+fn ulps_1() -> Result<(), BvError> {
+    type I = i32;
+    type F = f64;
+    let points = super::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [300, 301], [99, 17]]);
+    let segments = super::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+        [147, 103, 96, 170],
+    ]);
+    // returns the diagram and the number of exact recomputations during the build
+    let build = |ulps: Option<u64>| -> Result<(Diagram<I, F>, usize), BvError> {
+        let mut vb = Builder::<I, F>::default();
+        if let Some(ulps) = ulps {
+            let _ = vb.with_ulps(ulps);
+        }
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        let output = vb.build()?;
+        let count = output.stats().exact_circle_events;
+        Ok((output, count))
+    };
+    let (default, default_count) = build(None)?;
+    let (same, same_count) = build(Some(64))?;
+    let (tight, tight_count) = build(Some(0))?;
+    let (loose, loose_count) = build(Some(4096))?;
+    // the default is 64
+    assert_eq!(default_count, same_count);
+    assert_eq!(default.counts(), same.counts());
+    // a lower threshold recomputes more circle events, a higher one less
+    assert!(tight_count > default_count);
+    assert!(loose_count < default_count);
+    for output in [&tight, &loose] {
+        output.check_invariants()?;
+        assert_eq!(output.counts(), default.counts());
+        for (a, b) in output.vertices().iter().zip(default.vertices().iter()) {
+            let (a, b) = (a.get(), b.get());
+            assert!((a.x() - b.x()).abs() < 1e-6 && (a.y() - b.y()).abs() < 1e-6);
+        }
+    }
+    Ok(())
+}
@@ -10,19 +10,22 @@
 // Ported from C++ boost 1.76.0 to Rust in 2020/2021 by Eadf (github.com/eadf)
 
 use super::beach_line as VB;
+use super::diagram as VD;
 use super::extended_exp_fpt as EX;
 
 use super::OutputType;
 #[cfg(feature = "console_debug")]
 use crate::tln;
 use crate::BvError;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::Neg;
 use ordered_float::OrderedFloat;
-use std::cell::Cell;
-use std::cmp::Ordering;
-use std::collections::BTreeSet;
-use std::fmt;
-use std::ops::Neg;
-use std::rc::Rc;
 
 /// Type-checked placeholder for usize
 /// Hopefully rust zero cost abstractions will flatten this out.
@@ -68,6 +71,7 @@ pub struct CircleEvent {
     lower_x_: OrderedFloat<f64>,
     beach_line_index_: Option<VB::BeachLineIndex>, //beach_line_iterator in C++
     is_site_point_: bool,
+    kind_: VD::VertexKind,
 }
 
 impl fmt::Debug for CircleEvent {
@@ -89,6 +93,7 @@ impl Default for CircleEvent {
             lower_x_: OrderedFloat(0_f64),
             beach_line_index_: None,
             is_site_point_: false,
+            kind_: VD::VertexKind::default(),
         }
     }
 }
@@ -211,6 +216,13 @@ impl CircleEventC {
     pub(crate) fn is_site_point(&self) -> bool {
         self.0.get().is_site_point_
     }
+
+    /// sets the kind of sites that formed this circle event.
+    pub(crate) fn set_kind(&self, kind: VD::VertexKind) {
+        let mut selfc = self.0.get();
+        selfc.kind_ = kind;
+        self.0.set(selfc)
+    }
 }
 
 impl PartialOrd for CircleEventC {
@@ -252,6 +264,7 @@ where
             beach_line_index_: Some(bech_line_index),
             index_: None,
             is_site_point_: false,
+            kind_: VD::VertexKind::default(),
         }
     }
 
@@ -324,7 +337,6 @@ where
         self
     }
 
-    #[allow(dead_code)]
     #[inline(always)]
     pub(crate) fn raw_lower_x(&self) -> f64 {
         self.lower_x_.into_inner()
@@ -354,6 +366,11 @@ where
         self.is_site_point_
     }
 
+    #[inline(always)]
+    pub(crate) fn kind(&self) -> VD::VertexKind {
+        self.kind_
+    }
+
     #[cfg(any(feature = "ce_corruption_check", feature = "console_debug"))]
     #[allow(dead_code)]
     pub fn dbg(&self) {
@@ -363,6 +380,35 @@ where
 
 pub type CircleEventType = Rc<CircleEventC>;
 
+/// The circle events by id, the BTreeMap of alloc is used without the `std` feature.
+#[cfg(feature = "std")]
+type CircleEventMap = ahash::AHashMap<usize, CircleEventType>;
+#[cfg(not(feature = "std"))]
+type CircleEventMap = alloc::collections::BTreeMap<usize, CircleEventType>;
+
+/// A growable set of indices, stored as bits.
+#[derive(Default, Debug)]
+struct BitSet {
+    words_: Vec<u64>,
+}
+
+impl BitSet {
+    #[inline(always)]
+    fn contains(&self, index: usize) -> bool {
+        self.words_
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    fn insert(&mut self, index: usize) {
+        let word = index / 64;
+        if word >= self.words_.len() {
+            self.words_.resize(word + 1, 0);
+        }
+        self.words_[word] |= 1 << (index % 64);
+    }
+}
+
 /// Event queue data structure, holds circle events.
 /// During algorithm run, some of the circle events disappear (become
 /// inactive). Priority queue data structure doesn't support
@@ -374,18 +420,18 @@ pub(crate) struct CircleEventQueue {
     // circle events sorted by order
     c_: BTreeSet<CircleEventType>,
     // circle events sorted by id
-    c_list_: ahash::AHashMap<usize, CircleEventType>,
+    c_list_: CircleEventMap,
     c_list_next_free_index_: CircleEventIndex,
-    inactive_circle_ids_: yabf::Yabf, // Circle events turned inactive
+    inactive_circle_ids_: BitSet, // Circle events turned inactive
 }
 
 impl Default for CircleEventQueue {
     fn default() -> CircleEventQueue {
         Self {
             c_: BTreeSet::new(),
-            c_list_: ahash::AHashMap::new(),
+            c_list_: CircleEventMap::default(),
             c_list_next_free_index_: CircleEventIndex(0),
-            inactive_circle_ids_: yabf::Yabf::default(),
+            inactive_circle_ids_: BitSet::default(),
         }
     }
 }
@@ -486,7 +532,7 @@ impl CircleEventQueue {
         if let Some(circle) = self.pop_first() {
             if let Some(circle_id) = circle.0.get().index_ {
                 let _ = self.c_list_.remove(&circle_id.0);
-                let _ = self.inactive_circle_ids_.set_bit(circle_id.0, true);
+                self.inactive_circle_ids_.insert(circle_id.0);
             } else {
                 return Err(BvError::InternalError(format!(
                     "circle event lists corruption, circle event id {:?} not found {}:{}",
@@ -511,7 +557,7 @@ impl CircleEventQueue {
     pub(crate) fn clear(&mut self) {
         self.c_.clear();
         self.c_list_.clear();
-        self.inactive_circle_ids_ = yabf::Yabf::default()
+        self.inactive_circle_ids_ = BitSet::default()
     }
 
     /// Take ownership of the circle event,
@@ -536,23 +582,23 @@ impl CircleEventQueue {
 
     #[inline(always)]
     pub(crate) fn is_active(&self, circle_event_id: CircleEventIndex) -> bool {
-        !self.inactive_circle_ids_.bit(circle_event_id.0)
+        !self.inactive_circle_ids_.contains(circle_event_id.0)
     }
 
     pub(crate) fn deactivate(&mut self, circle_event_id: Option<CircleEventIndex>) {
         #[cfg(not(feature = "console_debug"))]
         if let Some(circle_event_id) = circle_event_id {
-            let _ = self.inactive_circle_ids_.set_bit(circle_event_id.0, true);
+            self.inactive_circle_ids_.insert(circle_event_id.0);
         }
         #[cfg(feature = "console_debug")]
         if let Some(circle_event_id) = circle_event_id {
-            if !self.inactive_circle_ids_.bit(circle_event_id.0) {
+            if !self.inactive_circle_ids_.contains(circle_event_id.0) {
                 if self.c_list_.contains_key(&circle_event_id.0) {
                     tln!("deactivate {:?}", self.c_list_[&circle_event_id.0]);
                 } else {
                     tln!("circle {} not present", circle_event_id);
                 }
-                let _ = self.inactive_circle_ids_.set_bit(circle_event_id.0, true);
+                let _ = self.inactive_circle_ids_.insert(circle_event_id.0);
             }
         }
     }
@@ -583,8 +629,8 @@ impl CircleEventQueue {
     }
 
     /// Returns the number of circle events (both active and inactive)
-    /// Only used by test code.
-    #[cfg(any(feature = "test", feature = "console_debug"))]
+    /// Only used by debug code.
+    #[cfg(feature = "console_debug")]
     pub(crate) fn len(&self) -> usize {
         self.c_.len()
     }
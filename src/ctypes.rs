@@ -9,7 +9,7 @@
 
 // Ported from C++ boost 1.76.0 to Rust in 2020/2021 by Eadf (github.com/eadf)
 
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
 union UlpMemCpy {
     f64: f64,
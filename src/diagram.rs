@@ -14,6 +14,7 @@
 
 use super::circle_event as VC;
 use super::ctypes as CT;
+use super::geometry::{Line, Point};
 use super::site_event as VSE;
 use super::visual_utils as VU;
 use super::TypeConverter2 as TC2;
@@ -22,20 +23,104 @@ use crate::{sync_diagram as SD, BvError};
 pub use super::{InputType, OutputType};
 #[allow(unused_imports)]
 use crate::{t, tln};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell;
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Neg;
 use num::NumCast;
-use std::cell;
-use std::cmp::Ordering;
-use std::fmt;
-use std::marker::PhantomData;
-use std::ops::Neg;
-use std::rc::Rc;
+
+mod dxf;
+mod export;
+mod gis;
+mod obj;
+mod svg;
+pub use dxf::{to_dxf, DxfOptions};
+pub use gis::{to_geojson, to_wkt};
+pub use obj::{to_obj, ObjOptions};
+pub use svg::{to_svg, SvgOptions};
 
 pub type SourceIndex = usize;
 
-///! See <https://www.boost.org/doc/libs/1_76_0/libs/polygon/doc/voronoi_diagram.htm>
+/// A shared vertex buffer and triangles indexing into it, see `Diagram::triangulate_cells()`
+pub type TriangleMesh<F> = (Vec<[F; 2]>, Vec<[u32; 3]>);
+
+/// The number of cells, edges and vertices of a diagram, see `Diagram::counts()`.
+/// `edges` counts half-edges, i.e. both twins of every edge, just like `Diagram::num_edges()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Counts {
+    pub cells: usize,
+    pub edges: usize,
+    pub vertices: usize,
+}
+
+/// Statistics of a built diagram, see `Diagram::stats()`.
+/// Like in `Counts`, the edge counts are half-edges, i.e. both twins of every edge.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct DiagramStats {
+    /// The number of input points consumed by the builder, duplicates are counted once
+    pub points: usize,
+    /// The number of input segments consumed by the builder, duplicates are counted once
+    pub segments: usize,
+    /// The number of cells, including the degenerate ones
+    pub cells: usize,
+    /// The number of vertices
+    pub vertices: usize,
+    /// The number of half-edges
+    pub edges: usize,
+    /// The number of primary half-edges, i.e. not between a segment and its own endpoint
+    pub primary_edges: usize,
+    /// The number of curved (parabolic) half-edges
+    pub curved_edges: usize,
+    /// The number of half-edges missing vertex0 or vertex1
+    pub infinite_edges: usize,
+    /// The number of circle events where the lazy (floating point) evaluation was not accurate
+    /// enough and the exact (big integer) path was taken.
+    pub exact_circle_events: usize,
+}
+
+impl fmt::Display for DiagramStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "input: {} points, {} segments",
+            self.points, self.segments
+        )?;
+        writeln!(
+            f,
+            "output: {} cells, {} vertices, {} edges",
+            self.cells, self.vertices, self.edges
+        )?;
+        writeln!(
+            f,
+            "edges: {} primary, {} curved, {} infinite",
+            self.primary_edges, self.curved_edges, self.infinite_edges
+        )?;
+        write!(f, "exact circle events: {}", self.exact_circle_events)
+    }
+}
+
+/// The area, centroid and perimeter of a finite cell, see `Diagram::analyze_cells()`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CellAnalysis<F> {
+    pub cell_id: CellIndex,
+    pub area: F,
+    pub centroid: [F; 2],
+    pub perimeter: F,
+}
+
+// See <https://www.boost.org/doc/libs/1_76_0/libs/polygon/doc/voronoi_diagram.htm>
 
 /// Typed container for cell indices
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellIndex(pub usize);
 
 impl fmt::Debug for CellIndex {
@@ -46,6 +131,7 @@ impl fmt::Debug for CellIndex {
 
 /// Typed container for edge indices
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EdgeIndex(pub usize);
 
 impl fmt::Debug for EdgeIndex {
@@ -56,6 +142,7 @@ impl fmt::Debug for EdgeIndex {
 
 /// Typed container for vertex indices
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexIndex(pub usize);
 
 impl fmt::Debug for VertexIndex {
@@ -106,6 +193,7 @@ impl ColorBits {
 
 /// Represents the type of input geometry a cell was created from
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SourceCategory {
     SinglePoint,
     SegmentStart,
@@ -113,15 +201,82 @@ pub enum SourceCategory {
     Segment,
 }
 
+/// The input geometry a cell was created from, with the exact input coordinates.
+/// A segment keeps the direction it was given to the builder.
+/// See `Diagram::cell_site_geometry()`, `Diagram::iter_cells_with_sites()` and
+/// `Diagram::edge_bisected_geometry()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SiteGeometry<I: InputType> {
+    Point(Point<I>),
+    Segment(Line<I>),
+}
+
+/// The side of an input segment, seen from the `start` towards the `end` of the segment as it
+/// was given to the builder. See `Diagram::edge_segment_side()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SegmentSide {
+    Left,
+    Right,
+}
+
+/// Represents the kind of the three input sites that generated a Voronoi vertex,
+/// i.e. which of the circle formation formulas that was used to calculate it.
+/// `P` stands for a point site and `S` for a segment site.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VertexKind {
+    /// point, point, point
+    #[default]
+    PPP,
+    /// point, point, segment
+    PPS,
+    /// point, segment, segment
+    PSS,
+    /// segment, segment, segment
+    SSS,
+}
+
+impl VertexKind {
+    /// Returns the vertex kind generated by three sites of which `segments` are segments.
+    pub(crate) fn from_segment_count(segments: usize) -> Self {
+        match segments {
+            0 => VertexKind::PPP,
+            1 => VertexKind::PPS,
+            2 => VertexKind::PSS,
+            _ => VertexKind::SSS,
+        }
+    }
+}
+
+/// Tells which of the two vertices of an edge that are missing, see `Diagram::edge_kind()`.
+/// vertex0 is the start of the half-edge and vertex1 is the end, i.e. the vertex0 of the twin.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeKind {
+    /// Both vertices exist (segment, parabolic arc)
+    Finite,
+    /// The edge comes from infinity and ends at vertex1 (ray)
+    InfiniteAt0,
+    /// The edge starts at vertex0 and goes to infinity (ray)
+    InfiniteAt1,
+    /// Both vertices are missing (line), only exists in diagrams without vertices
+    FullyInfinite,
+}
+
 /// Represents Voronoi cell.
 /// Data members:
 ///   1) index of the source within the initial input set
 ///   2) id of the incident edge
 ///   3) mutable color member
+///
 /// Cell may contain point or segment site inside.
 ///
 /// TODO! fix the name confusion "initial index" & "source index" referring to the same thing.
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cell<I, F>
 where
     I: InputType + Neg<Output = I>,
@@ -213,6 +368,8 @@ where
         self.internal_color().0 == ColorBits::SEGMENT_END_POINT__BIT.0
     }
 
+    /// Returns the id of the cell, i.e. its position in `Diagram::cells()`.
+    /// See `Diagram::cells()` for how the cells are ordered.
     #[inline(always)]
     pub fn id(&self) -> CellIndex {
         self.id_
@@ -231,6 +388,79 @@ where
         (self.source_index_, self.source_category())
     }
 
+    /// Returns the input point of this cell. For a segment cell the end point of the segment
+    /// is returned.
+    /// `point_data` and `segment_data` must be the same input data, in the same order, as given
+    /// to the builder. Panics if the source index is out of bounds of that data.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// # use boostvoronoi::geometry::Point;
+    /// let points = to_points::<i32, i32>(&[[5, 5]]);
+    /// let segments = to_segments::<i32, i32>(&[[0, 0, 0, 10]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// vb.with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// for cell in output.cell_iter() {
+    ///     let cell = cell.get();
+    ///     if cell.contains_segment_startpoint() {
+    ///         assert_eq!(cell.source_point(&points, &segments), Point { x: 0, y: 0 });
+    ///     }
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn source_point(&self, point_data: &[Point<I>], segment_data: &[Line<I>]) -> Point<I> {
+        match self.source_category() {
+            SourceCategory::SinglePoint => point_data[self.source_index_],
+            SourceCategory::SegmentStart => {
+                segment_data[self.source_index_ - point_data.len()].start
+            }
+            SourceCategory::Segment | SourceCategory::SegmentEnd => {
+                segment_data[self.source_index_ - point_data.len()].end
+            }
+        }
+    }
+
+    /// Returns the input segment of this cell, or `None` if the cell contains a point site.
+    /// The cells of the segment endpoints are point cells, so `None` is returned for those too.
+    /// `point_data` and `segment_data` must be the same input data, in the same order, as given
+    /// to the builder.
+    pub fn source_segment(
+        &self,
+        point_data: &[Point<I>],
+        segment_data: &[Line<I>],
+    ) -> Option<Line<I>> {
+        if !self.contains_segment() {
+            return None;
+        }
+        self.source_index_
+            .checked_sub(point_data.len())
+            .and_then(|i| segment_data.get(i))
+            .copied()
+    }
+
+    /// get_color returns the custom cell info. (does not contain the reserved bits)
+    #[inline(always)]
+    pub fn get_color(&self) -> ColorType {
+        self.color_ >> ColorBits::RESERVED_BITS__SHIFT.0
+    }
+
+    /// set_color sets the custom cell info. (does not affect the reserved bits)
+    /// This is a Cell operation, remember to set() the entire cell
+    #[inline(always)]
+    pub fn set_color(&mut self, color: ColorType) -> ColorType {
+        self.color_ &= ColorBits::RESERVED__MASK.0;
+        self.color_ |= color << ColorBits::RESERVED_BITS__SHIFT.0;
+        self.color_
+    }
+
+    /// or_color sets the custom cell info together with the previous value. (does not affect the reserved bits)
+    /// This is a Cell operation, remember to set() the entire cell
+    #[inline(always)]
+    pub fn or_color(&mut self, color: ColorType) -> ColorType {
+        self.set_color(self.get_color() | color)
+    }
+
     /// Degenerate cells don't have any incident edges.
     pub fn is_degenerate(&self) -> bool {
         self.incident_edge_.is_none()
@@ -241,6 +471,52 @@ where
     pub fn get_incident_edge(&self) -> Option<EdgeIndex> {
         self.incident_edge_
     }
+
+    /// Returns an iterator over the cells sharing an edge with this cell, i.e. the cell of the
+    /// twin of every edge of this cell. Infinite edges separate two cells as well, so they are
+    /// included; only edges without a twin cell are skipped.
+    /// The cells are not deduplicated, a neighbor is returned once for every shared edge.
+    /// A degenerate cell has no neighbors.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let center = output.cells().iter().find(|c| c.get().source_index() == 4).unwrap();
+    /// assert_eq!(center.get().neighbors(&output).count(), 4);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn neighbors<'s>(
+        &self,
+        diagram: &'s Diagram<I, F>,
+    ) -> impl Iterator<Item = CellIndex> + 's {
+        EdgeNextIterator::<'s, I, F>::new(diagram, self.incident_edge_).filter_map(move |edge_id| {
+            diagram.edge_get_cell_(diagram.edge_get_twin_(Some(edge_id)))
+        })
+    }
+
+    /// Returns true if the cell is bounded, i.e. none of its edges is infinite.
+    /// The edges are visited until the first infinite edge is found. A cell without edges
+    /// (the only cell of a single point diagram) covers the whole plane and is not bounded.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// for cell in output.cell_iter() {
+    ///     let cell = cell.get();
+    ///     // only the center point is surrounded by other points
+    ///     assert_eq!(cell.is_bounded(&output), cell.source_index() == 4);
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn is_bounded(&self, diagram: &Diagram<I, F>) -> bool {
+        self.incident_edge_.is_some()
+            && EdgeNextIterator::new(diagram, self.incident_edge_)
+                .all(|edge_id| diagram.edge_is_finite_(Some(edge_id)) == Some(true))
+    }
 }
 
 /// Iterator over edges of a Cell
@@ -451,6 +727,7 @@ where
 ///   2) id of the incident edge
 ///   3) mutable color member
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex<I, F>
 where
     I: InputType + Neg<Output = I>,
@@ -461,6 +738,13 @@ where
     pub(crate) y_: F,
     pub(crate) incident_edge_: Option<EdgeIndex>,
     pub(crate) color_: ColorType,
+    pub(crate) kind_: VertexKind,
+    // the order in which the sweep-line created the vertex
+    pub(crate) sequence_: usize,
+    // the radius of the circle event, see clearance()
+    pub(crate) clearance_: F,
+    // the sites of the circle event, see generating_sites()
+    pub(crate) sites_: Option<[(SourceIndex, SourceCategory); 3]>,
     #[doc(hidden)]
     pdi_: PhantomData<I>,
 }
@@ -505,12 +789,20 @@ where
             y_: y,
             incident_edge_: None,
             color_: color,
+            kind_: VertexKind::default(),
+            sequence_: id.0,
+            clearance_: F::zero(),
+            sites_: None,
             pdi_: PhantomData,
         }))
     }
 
+    /// Returns true if the vertices are within 128 ulps of the output type from each other.
+    /// The comparison is done with f64, so the ulps are scaled by the precision of `F`, e.g.
+    /// one f32 ulp is 2^29 f64 ulps.
     fn vertex_equality_predicate_eq(&self, other: &Self) -> bool {
-        let ulp = 128;
+        let ulp_scale: f64 = NumCast::from(F::epsilon()).unwrap();
+        let ulp = (128.0 * ulp_scale / f64::EPSILON) as u64;
         let x1: f64 = NumCast::from(self.x()).unwrap();
         let y1: f64 = NumCast::from(self.y()).unwrap();
         let x2: f64 = NumCast::from(other.x()).unwrap();
@@ -536,6 +828,31 @@ where
         })
     }
 
+    /// Returns an iterator over the half-edges originating at this vertex, rotating around the
+    /// vertex (see `Diagram::edge_rot_next()`) starting with the incident edge. Every outgoing
+    /// half-edge is returned exactly once, so `count()` is the degree of the vertex.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// for vertex in output.vertex_iter() {
+    ///     let vertex = vertex.get();
+    ///     for edge_id in vertex.incident_edges(&output) {
+    ///         assert_eq!(output.edge_get_vertex0(edge_id)?, Some(vertex.get_id()));
+    ///     }
+    ///     assert_eq!(vertex.incident_edges(&output).count(), 3);
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn incident_edges<'s>(
+        &self,
+        diagram: &'s Diagram<I, F>,
+    ) -> impl Iterator<Item = EdgeIndex> + 's {
+        diagram.edge_rot_next_iterator_(self.incident_edge_)
+    }
+
     /// returns the x coordinate of the circle event
     #[inline]
     pub fn x(&self) -> F {
@@ -572,6 +889,72 @@ where
     pub fn is_site_point(&self) -> bool {
         (self.color_ & ColorBits::SITE_VERTEX__BIT.0) != 0
     }
+
+    /// Returns the kind of the three sites that generated this vertex.
+    /// Vertices involving segments are calculated with more complex (and less exact) formulas.
+    /// A vertex merged by `Diagram::prune_edges_shorter_than()` or `Diagram::weld_vertices()`
+    /// gets the least exact kind of the merged vertices.
+    #[inline]
+    pub fn kind(&self) -> VertexKind {
+        self.kind_
+    }
+
+    /// Returns the radius of the largest empty circle centered at the vertex, i.e. the distance
+    /// from the vertex to the (three or more) sites that generated it. Zero for vertices located
+    /// on a site point.
+    /// The value is computed by the sweep-line, it is not updated by `Diagram::map_vertices()`.
+    /// `Diagram::prune_edges_shorter_than()` and `Diagram::weld_vertices()` recompute it for the
+    /// merged vertices, as the distance to the closest site around the merged vertex.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [8, 0], [0, 6]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let vertex = output.vertices()[0].get();
+    /// assert_eq!((vertex.x(), vertex.y()), (4.0, 3.0));
+    /// assert!((vertex.clearance() - 5.0).abs() < 1e-9);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    #[inline]
+    pub fn clearance(&self) -> F {
+        self.clearance_
+    }
+
+    /// Returns the source index and category of the three input sites that generated this
+    /// vertex, i.e. the sites of the circle event, in the order of the beach line arcs.
+    /// For point input these are the three corners of the dual Delaunay triangle.
+    ///
+    /// A vertex where more than three sites meet (e.g. cocircular points) only reports the
+    /// three sites of the circle event that created it. The two sides of a segment are two
+    /// arcs of the beach line, so a segment may be reported twice. Returns `None` for vertices
+    /// not created by the builder, see `new_3()`, and for the vertices merged by
+    /// `Diagram::prune_edges_shorter_than()` or `Diagram::weld_vertices()`.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// # use boostvoronoi::diagram::SourceCategory;
+    /// let points = to_points::<i32, i32>(&[[0, 0], [8, 0], [0, 6]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let mut sites = output.vertices()[0].get().generating_sites().unwrap();
+    /// sites.sort_unstable_by_key(|s| s.0);
+    /// assert_eq!(sites.map(|s| s.0), [0, 1, 2]);
+    /// assert!(sites.iter().all(|s| s.1 == SourceCategory::SinglePoint));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    #[inline]
+    pub fn generating_sites(&self) -> Option<[(SourceIndex, SourceCategory); 3]> {
+        self.sites_
+    }
+
+    /// Returns the sequence number of the circle event that created this vertex, i.e. the
+    /// order in which the sweep-line created the vertices. The numbers are unique but not
+    /// necessarily contiguous, as degenerate vertices are removed after the sweep.
+    #[inline]
+    pub fn creation_sequence(&self) -> usize {
+        self.sequence_
+    }
 }
 
 /// Half-edge data structure. Represents a Voronoi edge.
@@ -584,6 +967,7 @@ where
 ///   5) id of to the CCW prev edge
 ///   6) mutable color member
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Edge<I, F>
 where
     I: InputType + Neg<Output = I>,
@@ -719,6 +1103,8 @@ where
 
     /// Returns true if the edge is linear (segment, ray, line).
     /// Returns false if the edge is curved (parabolic arc).
+    /// Just like in Boost, an edge is curved if it is primary and exactly one of the two sites
+    /// it separates is a segment, i.e. it is the bisector of a point and a segment.
     #[inline]
     pub fn is_linear(&self) -> bool {
         (self.color_ & Self::BIT_IS_LINEAR) != 0
@@ -745,6 +1131,16 @@ where
         !self.is_primary()
     }
 
+    /// Returns the euclidean distance between vertex0 and vertex1 of the edge, or `None` if
+    /// the edge is infinite.
+    /// Curved edges are measured by their chord, i.e. the straight line between the two
+    /// vertices. Sum up the polyline of `Diagram::edge_discretize()` if the length of the
+    /// parabolic arc is needed.
+    pub fn length(&self, diagram: &Diagram<I, F>) -> Option<F> {
+        let [x0, y0, x1, y1] = diagram.edge_as_line_(Some(self.id_))?;
+        Some((x1 - x0).hypot(y1 - y0))
+    }
+
     /// get_color returns the custom edge info. (does not contain the reserved bits)
     #[inline(always)]
     pub fn get_color(&self) -> ColorType {
@@ -782,6 +1178,98 @@ where
     cells_: Vec<CellType<I, F>>,      // indexed by CellIndex
     vertices_: Vec<VertexType<I, F>>, // indexed by VertexIndex
     edges_: Vec<EdgeType<I, F>>,      // indexed by EdgeIndex
+    sites_: Vec<SiteGeometry<I>>,     // indexed by CellIndex
+    // (points, segments, exact circle events) of the build, see stats()
+    build_stats_: (usize, usize, usize),
+    // see exterior_(), reset when edges are removed
+    exterior_: cell::RefCell<Option<Exterior>>,
+}
+
+/// The edges and vertices connected to an infinite edge via primary edges, indexed by
+/// `EdgeIndex` and `VertexIndex`.
+#[derive(Debug, Clone)]
+struct Exterior {
+    edges: Vec<bool>,
+    vertices: Vec<bool>,
+}
+
+/// The serialized form of a `Diagram`, the cells, vertices and edges are stored by value and
+/// refer to each other by index. So `twin()`, `next()` and `prev()` survive a round-trip.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound(
+    serialize = "I: serde::Serialize, F: serde::Serialize",
+    deserialize = "I: serde::Deserialize<'de>, F: serde::Deserialize<'de>"
+))]
+struct DiagramData<I, F>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    cells: Vec<Cell<I, F>>,
+    vertices: Vec<Vertex<I, F>>,
+    edges: Vec<Edge<I, F>>,
+    sites: Vec<SiteGeometry<I>>,
+}
+
+#[cfg(feature = "serde")]
+impl<I, F> serde::Serialize for Diagram<I, F>
+where
+    I: InputType + Neg<Output = I> + serde::Serialize,
+    F: OutputType + Neg<Output = F> + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DiagramData {
+            cells: self.cells_.iter().map(|c| c.get()).collect(),
+            vertices: self.vertices_.iter().map(|v| v.get()).collect(),
+            edges: self.edges_.iter().map(|e| e.get()).collect(),
+            sites: self.sites_.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I, F> serde::Deserialize<'de> for Diagram<I, F>
+where
+    I: InputType + Neg<Output = I> + serde::Deserialize<'de>,
+    F: OutputType + Neg<Output = F> + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = DiagramData::<I, F>::deserialize(deserializer)?;
+        if data.sites.len() != data.cells.len() {
+            return Err(serde::de::Error::custom(format!(
+                "The diagram has {} cells but {} sites",
+                data.cells.len(),
+                data.sites.len()
+            )));
+        }
+        let diagram = Self {
+            cells_: data
+                .cells
+                .into_iter()
+                .map(|c| Rc::new(cell::Cell::new(c)))
+                .collect(),
+            vertices_: data
+                .vertices
+                .into_iter()
+                .map(|v| Rc::new(cell::Cell::new(v)))
+                .collect(),
+            edges_: data
+                .edges
+                .into_iter()
+                .map(|e| Rc::new(cell::Cell::new(e)))
+                .collect(),
+            sites_: data.sites,
+            build_stats_: (0, 0, 0),
+            exterior_: Default::default(),
+        };
+        // the indices of the data are not trusted
+        diagram
+            .validate_topology()
+            .map_err(serde::de::Error::custom)?;
+        Ok(diagram)
+    }
 }
 
 impl<I, F> Diagram<I, F>
@@ -789,11 +1277,17 @@ where
     I: InputType + Neg<Output = I>,
     F: OutputType + Neg<Output = F>,
 {
+    /// Creates an empty diagram with room for the output of `input_size` site events.
+    /// Every site gets one cell and, by Euler's formula for a planar graph, the diagram of
+    /// n sites has at most 2n-5 vertices and 3n-6 edges, i.e. 6n-12 half-edges.
     pub fn new(input_size: usize) -> Self {
         Self {
             cells_: Vec::<CellType<I, F>>::with_capacity(input_size),
-            vertices_: Vec::<VertexType<I, F>>::with_capacity(input_size),
-            edges_: Vec::<EdgeType<I, F>>::with_capacity(input_size * 2),
+            vertices_: Vec::<VertexType<I, F>>::with_capacity(input_size * 2),
+            edges_: Vec::<EdgeType<I, F>>::with_capacity(input_size * 6),
+            sites_: Vec::<SiteGeometry<I>>::with_capacity(input_size),
+            build_stats_: (0, 0, 0),
+            exterior_: Default::default(),
         }
     }
 
@@ -802,14 +1296,303 @@ where
         self.cells_.clear();
         self.vertices_.clear();
         self.edges_.clear();
+        self.sites_.clear();
+        self.build_stats_ = (0, 0, 0);
+        self.exterior_ = Default::default();
     }
 
     #[inline(always)]
-    /// Returns a reference to the list of cells
+    /// Returns a reference to the list of cells.
+    ///
+    /// The cells are ordered like the sites are processed by the sweep-line, just like in Boost:
+    /// by ascending x, then ascending y, with the cell of a segment after the cell of its lowest
+    /// endpoint. Coincident input sites share one cell. The order only depends on the input
+    /// geometry, so the same input always gives the same cell ids, but they do not follow the
+    /// insertion order. `Cell::source_index_2()` maps every cell back to its input site, points
+    /// first in insertion order followed by the segments. Use `cells_in_input_order()` to
+    /// iterate the cells in input order.
     pub fn cells(&self) -> &Vec<CellType<I, F>> {
         &self.cells_
     }
 
+    /// Returns the cell ids ordered by their input site, i.e. by `Cell::source_index()`.
+    /// The cells of a segment are ordered start point, end point and then the segment itself.
+    /// Input sites that coincide with an earlier site have no cell of their own, so they are
+    /// missing from the result.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// # use boostvoronoi::diagram::SourceCategory;
+    /// let points = to_points::<i32, i32>(&[[10, 10], [0, 0]]);
+    /// let segments = to_segments::<i32, i32>(&[[5, 20, 5, -20]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// vb.with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// let sources: Vec<_> = output
+    ///     .cells_in_input_order()
+    ///     .map(|c| output.get_cell(c).unwrap().get().source_index_2())
+    ///     .collect();
+    /// assert_eq!(
+    ///     sources,
+    ///     vec![
+    ///         (0, SourceCategory::SinglePoint),
+    ///         (1, SourceCategory::SinglePoint),
+    ///         (2, SourceCategory::SegmentStart),
+    ///         (2, SourceCategory::SegmentEnd),
+    ///         (2, SourceCategory::Segment),
+    ///     ]
+    /// );
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn cells_in_input_order(&self) -> impl Iterator<Item = CellIndex> {
+        let mut cells: Vec<(SourceIndex, u8, CellIndex)> = self
+            .cells_
+            .iter()
+            .map(|c| {
+                let c = c.get();
+                let rank = match c.source_category() {
+                    SourceCategory::SinglePoint | SourceCategory::SegmentStart => 0,
+                    SourceCategory::SegmentEnd => 1,
+                    SourceCategory::Segment => 2,
+                };
+                (c.source_index(), rank, c.id())
+            })
+            .collect();
+        cells.sort_unstable_by_key(|c| (c.0, c.1));
+        cells.into_iter().map(|c| c.2)
+    }
+
+    /// Returns the input geometry of the cell, with the exact input coordinates.
+    pub fn cell_site_geometry(&self, cell_id: CellIndex) -> Result<SiteGeometry<I>, BvError> {
+        self.sites_.get(cell_id.0).copied().ok_or_else(|| {
+            BvError::IdError(format!(
+                "The cell with id:{} does not have any site geometry",
+                cell_id.0
+            ))
+        })
+    }
+
+    /// Returns the input geometry of the two sites separated by the edge, with the exact input
+    /// coordinates. The first site is the site of the edge's own cell, the second is the site
+    /// of the twin's cell.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// # use boostvoronoi::diagram::{EdgeIndex, SiteGeometry};
+    /// # use boostvoronoi::geometry::Point;
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let (a, b) = output.edge_bisected_geometry(EdgeIndex(0))?;
+    /// assert_eq!(a, SiteGeometry::Point(Point { x: 0, y: 0 }));
+    /// assert_eq!(b, SiteGeometry::Point(Point { x: 10, y: 0 }));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn edge_bisected_geometry(
+        &self,
+        edge_id: EdgeIndex,
+    ) -> Result<(SiteGeometry<I>, SiteGeometry<I>), BvError> {
+        let edge = self.get_edge(edge_id)?.get();
+        let twin = self.get_edge(edge.twin()?)?.get();
+        Ok((
+            self.cell_site_geometry(edge.cell()?)?,
+            self.cell_site_geometry(twin.cell()?)?,
+        ))
+    }
+
+    /// Iterates over every cell together with the input geometry that generated it.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// # use boostvoronoi::diagram::SiteGeometry;
+    /// let points = to_points::<i32, i32>(&[[5, 5]]);
+    /// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter()).unwrap();
+    /// vb.with_segments(segments.iter()).unwrap();
+    /// let output = vb.build().unwrap();
+    /// for (cell_id, site) in output.iter_cells_with_sites() {
+    ///     let cell = output.get_cell(cell_id).unwrap().get();
+    ///     match site {
+    ///         SiteGeometry::Point(_) => assert!(cell.contains_point()),
+    ///         SiteGeometry::Segment(s) => assert_eq!(s, segments[0]),
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_cells_with_sites(&self) -> impl Iterator<Item = (CellIndex, SiteGeometry<I>)> + '_ {
+        self.cells_
+            .iter()
+            .zip(self.sites_.iter())
+            .map(|(c, s)| (c.get().id(), *s))
+    }
+
+    /// Returns the distance from `position` to the input geometry of a site.
+    fn site_distance_(site: &SiteGeometry<I>, position: [F; 2]) -> F {
+        site_distance_sq_::<I, F>(site, position).sqrt()
+    }
+
+    /// Returns the source index and category of the cell containing `position`, i.e. the
+    /// input site closest to it. Returns None if the diagram has no cells.
+    ///
+    /// A segment cell only contains the positions that are closest to the interior of the
+    /// segment, positions closest to an endpoint belong to the cell of that endpoint. On the
+    /// border between two cells either one of them may be returned.
+    ///
+    /// This is currently a linear scan over every site, O(n) per query.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// # use boostvoronoi::diagram::SourceCategory;
+    /// let points = to_points::<i32, i32>(&[[0, 10]]);
+    /// let segments = to_segments::<i32, i32>(&[[-10, 0, 10, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// vb.with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// assert_eq!(output.locate([1.0, 8.0]), Some((0, SourceCategory::SinglePoint)));
+    /// assert_eq!(output.locate([3.0, 1.0]), Some((1, SourceCategory::Segment)));
+    /// assert_eq!(output.locate([12.0, 1.0]), Some((1, SourceCategory::SegmentEnd)));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn locate(&self, position: [F; 2]) -> Option<(SourceIndex, SourceCategory)> {
+        let i_to_f = TC2::<I, F>::i_to_f;
+        self.cells_
+            .iter()
+            .zip(self.sites_.iter())
+            .filter_map(|(cell, site)| {
+                if let SiteGeometry::Segment(s) = site {
+                    // only the interior of the segment belongs to the segment cell
+                    let start = [i_to_f(s.start.x), i_to_f(s.start.y)];
+                    let d = [i_to_f(s.end.x) - start[0], i_to_f(s.end.y) - start[1]];
+                    let t = (position[0] - start[0]) * d[0] + (position[1] - start[1]) * d[1];
+                    if t <= F::zero() || t >= d[0] * d[0] + d[1] * d[1] {
+                        return None;
+                    }
+                }
+                Some((site_distance_sq_::<I, F>(site, position), cell.get()))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+            .map(|(_, cell)| cell.source_index_2())
+    }
+
+    /// Samples the clearance radius along a finite primary edge, i.e. the distance from points
+    /// on the edge to the sites on either side of it. Returns `samples` evenly spaced
+    /// `(position, radius)` pairs, from vertex0 to vertex1 (both included).
+    /// The radius varies linearly along straight edges, curved edges are sampled evenly along
+    /// the projection onto the segment site.
+    ///
+    /// Returns `BvError::ValueError` for infinite and secondary edges, and if `samples < 2`.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 20]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// for edge in output.edges().iter() {
+    ///     let edge = edge.get();
+    ///     if let Ok(profile) = output.edge_radius_profile(edge.id(), 5) {
+    ///         assert_eq!(profile.len(), 5);
+    ///         assert!(profile.iter().all(|(_, radius)| *radius >= 5.0 - 1e-9));
+    ///     }
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn edge_radius_profile(
+        &self,
+        edge_id: EdgeIndex,
+        samples: usize,
+    ) -> Result<Vec<([F; 2], F)>, BvError> {
+        let edge = self.get_edge(edge_id)?.get();
+        if !edge.is_primary() {
+            return Err(BvError::ValueError(format!(
+                "The edge with id:{} is a secondary edge",
+                edge_id.0
+            )));
+        }
+        let (v0, v1) = match self.edge_vertices(edge_id)? {
+            (Some(v0), Some(v1)) => (self.vertex_position(v0)?, self.vertex_position(v1)?),
+            _ => {
+                return Err(BvError::ValueError(format!(
+                    "The edge with id:{} is infinite",
+                    edge_id.0
+                )))
+            }
+        };
+        if samples < 2 {
+            return Err(BvError::ValueError(format!(
+                "At least two samples are needed, got {}",
+                samples
+            )));
+        }
+        let site = self.cell_site_geometry(edge.cell()?)?;
+        let twin_site = self.cell_site_geometry(self.get_edge(edge.twin()?)?.get().cell()?)?;
+        let i_to_f = TC2::<I, F>::i_to_f;
+        let fraction = |i: usize| {
+            TC2::<I, F>::f64_to_f(i as f64) / TC2::<I, F>::f64_to_f((samples - 1) as f64)
+        };
+
+        let curve = match (site, twin_site) {
+            (SiteGeometry::Point(p), SiteGeometry::Segment(s))
+            | (SiteGeometry::Segment(s), SiteGeometry::Point(p))
+                if edge.is_curved() =>
+            {
+                Some((p, s))
+            }
+            _ => None,
+        };
+        let rv = if let Some((p, s)) = curve {
+            // a point X on the parabola is located at the distance h from its projection Q
+            // onto the segment line, along the unit normal n pointing towards the point site P.
+            // |X-P| = h gives h = |Q-P|² / (2 * n·(P-Q))
+            let start = [i_to_f(s.start.x), i_to_f(s.start.y)];
+            let length = (i_to_f(s.end.x) - start[0]).hypot(i_to_f(s.end.y) - start[1]);
+            let u = [
+                (i_to_f(s.end.x) - start[0]) / length,
+                (i_to_f(s.end.y) - start[1]) / length,
+            ];
+            let p = [i_to_f(p.x), i_to_f(p.y)];
+            let side = u[0] * (p[1] - start[1]) - u[1] * (p[0] - start[0]);
+            let (n, d) = if side < F::zero() {
+                ([u[1], -u[0]], -side)
+            } else {
+                ([-u[1], u[0]], side)
+            };
+            let project = |v: [F; 2]| (v[0] - start[0]) * u[0] + (v[1] - start[1]) * u[1];
+            let (t0, t1) = (project(v0), project(v1));
+            (0..samples)
+                .map(|i| {
+                    let t = t0 + (t1 - t0) * fraction(i);
+                    let q = [start[0] + u[0] * t, start[1] + u[1] * t];
+                    let q_p = (q[0] - p[0]).hypot(q[1] - p[1]);
+                    let h = q_p * q_p / (d + d);
+                    ([q[0] + n[0] * h, q[1] + n[1] * h], h)
+                })
+                .collect()
+        } else {
+            (0..samples)
+                .map(|i| {
+                    let f = fraction(i);
+                    let position = [v0[0] + (v1[0] - v0[0]) * f, v0[1] + (v1[1] - v0[1]) * f];
+                    (position, Self::site_distance_(&site, position))
+                })
+                .collect()
+        };
+        Ok(rv)
+    }
+
+    /// Stores the input geometry of the cells, indexed by `CellIndex`.
+    pub(crate) fn set_sites_(&mut self, sites: Vec<SiteGeometry<I>>) {
+        self.sites_ = sites;
+    }
+
+    /// Records the number of input points, input segments and exact circle events of the build.
+    pub(crate) fn set_build_stats_(
+        &mut self,
+        points: usize,
+        segments: usize,
+        exact_circle_events: usize,
+    ) {
+        self.build_stats_ = (points, segments, exact_circle_events);
+    }
+
     #[inline(always)]
     /// Returns a reference to all of the vertices
     pub fn vertices(&self) -> &Vec<VertexType<I, F>> {
@@ -827,12 +1610,72 @@ where
         rv
     }
 
+    /// Returns the `(low, high)` corners of the smallest axis aligned rectangle containing all
+    /// the vertices, or None if the diagram has no vertices.
+    /// Note that vertices can be located far away from the input geometry, see
+    /// `Builder::input_bounding_box()` for the extent of the input.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// assert_eq!(vb.input_bounding_box(), Some(([0.0, 0.0], [10.0, 10.0])));
+    /// let output = vb.build()?;
+    /// // the only vertex is the circumcenter of the triangle
+    /// assert_eq!(output.bounding_box(), Some(([5.0, 5.0], [5.0, 5.0])));
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn bounding_box(&self) -> Option<([F; 2], [F; 2])> {
+        let aabb = self.vertices_get_aabb();
+        Some((aabb.get_low()?, aabb.get_high()?))
+    }
+
+    /// Applies the same transform to the coordinates of every vertex, in place.
+    /// Edges and cells refer to vertices by index, so they will see the transformed vertices.
+    /// Note that the input sites are not transformed, so after a transform the methods combining
+    /// the vertices with the input geometry, e.g. `edge_discretize()`, `locate()` or
+    /// `cell_polygon_discretized()`, return wrong results. Use this only on a diagram that is no
+    /// longer queried, or transform the results of those methods instead.
+    /// ```
+    /// # use boostvoronoi::builder::{Builder, to_points};
+    /// let p = to_points::<i32, i32>(&[[0, 0], [10, 0], [5, 10]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(p.iter()).unwrap();
+    /// let mut diagram = vb.build().unwrap();
+    /// let before = diagram.vertices()[0].get();
+    /// diagram.map_vertices(|[x, y]| [x * 2.0 + 1.0, y * 2.0 - 1.0]);
+    /// let after = diagram.vertices()[0].get();
+    /// assert_eq!(after.x(), before.x() * 2.0 + 1.0);
+    /// assert_eq!(after.y(), before.y() * 2.0 - 1.0);
+    /// ```
+    pub fn map_vertices<FN>(&mut self, transform: FN)
+    where
+        FN: Fn([F; 2]) -> [F; 2],
+    {
+        for vertex in self.vertices_.iter() {
+            let mut v = vertex.get();
+            let [x, y] = transform([v.x_, v.y_]);
+            v.x_ = x;
+            v.y_ = y;
+            vertex.set(v);
+        }
+    }
+
     #[inline(always)]
     /// Returns a reference to the list of edges
     pub fn edges(&self) -> &Vec<EdgeType<I, F>> {
         &self.edges_
     }
 
+    /// Returns the ids of the primary edges, i.e. all edges except the secondary edges that
+    /// connect a segment endpoint to the segment interior. Both twins are included.
+    pub fn primary_edges(&self) -> impl Iterator<Item = EdgeIndex> + '_ {
+        self.edges_.iter().filter_map(|e| {
+            let e = e.get();
+            e.is_primary().then_some(e.id())
+        })
+    }
+
     #[inline(always)]
     /// Returns a Rc<cell::Cell<>> belonging to the cell_id
     pub fn get_cell(&self, cell_id: CellIndex) -> Result<Rc<cell::Cell<Cell<I, F>>>, BvError> {
@@ -845,7 +1688,7 @@ where
     /// Returns the edge associated with the edge id
     pub(crate) fn get_edge_(&self, edge_id: Option<EdgeIndex>) -> Option<EdgeType<I, F>> {
         let edge_id = edge_id?;
-        self.edges_.get(edge_id.0).map(|x| Rc::clone(x))
+        self.edges_.get(edge_id.0).map(Rc::clone)
     }
 
     /// Returns the edge associated with the edge id
@@ -860,6 +1703,23 @@ where
         }
     }
 
+    /// Returns the `[x, y]` coordinates of the vertex with the given id.
+    /// ```
+    /// # use boostvoronoi::builder::Builder;
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::diagram::VertexIndex;
+    /// let mut b = Builder::<i32, f64>::default();
+    /// b.with_vertices([Point { x: 0, y: 0 }, Point { x: 10, y: 0 }, Point { x: 0, y: 10 }].iter())?;
+    /// let diagram = b.build()?;
+    /// assert_eq!(diagram.vertex_position(VertexIndex(0))?, [5.0, 5.0]);
+    /// assert!(diagram.vertex_position(VertexIndex(1)).is_err());
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn vertex_position(&self, vertex_id: VertexIndex) -> Result<[F; 2], BvError> {
+        let vertex = self.vertex_get(vertex_id)?.get();
+        Ok([vertex.x(), vertex.y()])
+    }
+
     /// Return the edge represented as an straight line
     /// if the edge does not exists or if it lacks v0 or v1; None will be returned.
     /// TODO: this looks like an into() candidate
@@ -891,47 +1751,276 @@ where
     /// Iterates over all edges, colors each edge as exterior if it has an unbroken primary edge
     /// link connection to an infinite edge.
     pub fn color_exterior_edges(&self, external_color: ColorType) {
-        for it in self.edges().iter() {
-            let edge_id = Some(it.get().id());
-            if !self.edge_is_finite_(edge_id).unwrap() {
-                self.recurse_color_exterior(edge_id, external_color);
+        let exterior = self.exterior_();
+        for (edge_id, _) in exterior.edges.iter().enumerate().filter(|(_, e)| **e) {
+            self.edge_or_color_(Some(EdgeIndex(edge_id)), external_color);
+        }
+        for (vertex_id, _) in exterior.vertices.iter().enumerate().filter(|(_, v)| **v) {
+            self.vertex_set_color_(Some(VertexIndex(vertex_id)), external_color);
+        }
+    }
+
+    /// Returns the edges and vertices connected to an infinite edge via primary edges, see
+    /// `color_exterior_edges()`. The result is computed on the first call and kept until the
+    /// edges of the diagram are modified.
+    fn exterior_(&self) -> cell::Ref<'_, Exterior> {
+        if self.exterior_.borrow().is_none() {
+            let mut exterior = Exterior {
+                edges: vec![false; self.edges_.len()],
+                vertices: vec![false; self.vertices_.len()],
+            };
+            for edge in self.edges_.iter() {
+                let edge_id = Some(edge.get().id());
+                if !self.edge_is_finite_(edge_id).unwrap() {
+                    self.recurse_exterior_(edge_id, &mut exterior);
+                }
             }
+            *self.exterior_.borrow_mut() = Some(exterior);
         }
+        cell::Ref::map(self.exterior_.borrow(), |e| e.as_ref().unwrap())
     }
 
     /// Mark all edges connected to an 'infinite' edge via primary edges as 'external'
     /// You should only call this on edges that you know are infinite. i.e. lacks one or two vertexes
-    fn recurse_color_exterior(&self, edge_id: Option<EdgeIndex>, external_color: ColorType) {
-        if edge_id.is_none() || (self.edge_get_color_(edge_id).unwrap() & external_color) != 0 {
-            // This edge has already been colored, break recursion
-            return;
-        }
-        // Color edge as EXTERNAL
-        self.edge_or_color_(edge_id, external_color);
+    fn recurse_exterior_(&self, edge_id: Option<EdgeIndex>, exterior: &mut Exterior) {
+        let edge = match edge_id {
+            Some(edge) if !exterior.edges[edge.0] => edge,
+            // This edge has already been marked, break recursion
+            _ => return,
+        };
+        // Mark edge as EXTERNAL
+        exterior.edges[edge.0] = true;
 
         let v1 = self._edge_get_vertex1(edge_id);
         if self.edge_get_vertex0_(edge_id).is_some() && v1.is_none() {
             // this edge leads to nowhere, break recursion
             return;
         }
-        // Color twin edge as EXTERNAL
-        self.edge_or_color_(self.edge_get_twin_(edge_id), external_color);
-        if v1.is_none()
-            || self.vertex_is_site_point_(v1).unwrap_or(true)
-            || !self
-                .get_edge_(edge_id)
-                .map_or(false, |x| x.get().is_primary())
-        {
+        // Mark twin edge as EXTERNAL
+        if let Some(twin) = self.edge_get_twin_(edge_id) {
+            exterior.edges[twin.0] = true;
+        }
+        let v1 = match v1 {
+            Some(v1)
+                if !self.vertex_is_site_point_(Some(v1)).unwrap_or(true)
+                    && self
+                        .get_edge_(edge_id)
+                        .is_some_and(|x| x.get().is_primary()) =>
+            {
+                v1
+            }
             // stop recursion if this edge does not have a vertex1 (e.g is infinite)
             // or if this edge isn't a primary edge.
-            return;
-        }
-        self.vertex_set_color_(v1, external_color);
-        let incident_edge = self.vertex_get_incident_edge(v1);
+            _ => return,
+        };
+        exterior.vertices[v1.0] = true;
+        let incident_edge = self.vertex_get_incident_edge(Some(v1));
         for e in self.edge_rot_next_iterator_(incident_edge) {
             // mark all surrounding edges as EXTERNAL, but only recurse on primary edges
-            self.recurse_color_exterior(Some(e), external_color);
+            self.recurse_exterior_(Some(e), exterior);
+        }
+    }
+
+    /// Returns the interior medial edges of an input segment, i.e. the finite primary edges
+    /// of the segment cell that are not connected to an infinite edge through primary edges
+    /// (the same rule as `color_exterior_edges()` uses). The secondary edges, separating the
+    /// segment from its own endpoints, are excluded.
+    ///
+    /// `segment_index` is the source index of the segment, i.e. the number of input points
+    /// plus the index into the input segments. Only segments of a closed polygon have
+    /// interior edges, the edges of an open polyline are all exterior.
+    pub fn segment_medial_edges(
+        &self,
+        segment_index: SourceIndex,
+    ) -> Result<Vec<EdgeIndex>, BvError> {
+        let cell_id = self
+            .cells_
+            .iter()
+            .map(|c| c.get())
+            .find(|c| c.source_index() == segment_index && c.contains_segment())
+            .map(|c| c.id())
+            .ok_or_else(|| {
+                BvError::IdError(format!(
+                    "The segment with source index:{} does not exist",
+                    segment_index
+                ))
+            })?;
+
+        let exterior = self.exterior_();
+        Ok(self
+            .cell_edge_iterator_(Some(cell_id))
+            .filter(|e| {
+                !exterior.edges[e.0]
+                    && self.edge_is_finite_(Some(*e)).unwrap_or(false)
+                    && self
+                        .get_edge_(Some(*e))
+                        .is_some_and(|x| x.get().is_primary())
+            })
+            .collect())
+    }
+
+    /// Returns the medial axis of the closed input polygons, i.e. the finite primary edges
+    /// (linear and curved) located inside a polygon. Edges connected to the unbounded region
+    /// and secondary edges are discarded. Only one edge of each twin pair is returned, the one
+    /// with the lowest id.
+    ///
+    /// The input segments must form closed, non self-intersecting loops, the interior is
+    /// decided by the even-odd rule, so a loop inside another loop is a hole. Open chains of
+    /// segments are ignored when deciding the interior. Input points inside a polygon are
+    /// sites like any other, their edges become part of the medial axis.
+    pub fn medial_axis(&self) -> Result<Vec<EdgeIndex>, BvError> {
+        let exterior = self.exterior_();
+        // Only the segments of closed loops bound the interior, prune the open chains by
+        // repeatedly removing segments with a dangling endpoint.
+        let mut loop_segments: Vec<&Line<I>> = self
+            .sites_
+            .iter()
+            .filter_map(|s| match s {
+                SiteGeometry::Segment(segment) => Some(segment),
+                SiteGeometry::Point(_) => None,
+            })
+            .collect();
+        loop {
+            let mut degree = BTreeMap::<(I, I), usize>::new();
+            for s in loop_segments.iter() {
+                *degree.entry((s.start.x, s.start.y)).or_insert(0) += 1;
+                *degree.entry((s.end.x, s.end.y)).or_insert(0) += 1;
+            }
+            let len = loop_segments.len();
+            let degree_of = |p: &Point<I>| degree[&(p.x, p.y)];
+            loop_segments.retain(|s| degree_of(&s.start) > 1 && degree_of(&s.end) > 1);
+            if loop_segments.len() == len {
+                break;
+            }
+        }
+        let i2f = TC2::<I, F>::i_to_f;
+        let half = TC2::<I, F>::f64_to_f(0.5);
+        // even-odd ray casting towards +x
+        let is_inside = |p: [F; 2]| {
+            loop_segments
+                .iter()
+                .filter(|s| {
+                    let (x0, y0, x1, y1) =
+                        (i2f(s.start.x), i2f(s.start.y), i2f(s.end.x), i2f(s.end.y));
+                    (y0 > p[1]) != (y1 > p[1]) && p[0] < x0 + (p[1] - y0) * (x1 - x0) / (y1 - y0)
+                })
+                .count()
+                % 2
+                == 1
+        };
+
+        let mut rv = Vec::new();
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            let edge_id = edge.id();
+            if exterior.edges[edge_id.0]
+                || edge.is_secondary()
+                || edge_id.0 > edge.twin()?.0
+                || !self.edge_is_finite(edge_id)?
+            {
+                continue;
+            }
+            // test a point on the edge that is not a vertex, the vertices may be located on
+            // the polygon boundary
+            let (v0, v1) = match self.edge_vertices(edge_id)? {
+                (Some(v0), Some(v1)) => (self.vertex_position(v0)?, self.vertex_position(v1)?),
+                _ => continue,
+            };
+            let mut sample = [(v0[0] + v1[0]) * half, (v0[1] + v1[1]) * half];
+            if edge.is_curved() {
+                // the middle of the chord may be on the wrong side of the focus of the arc
+                let chord =
+                    ((v1[0] - v0[0]) * (v1[0] - v0[0]) + (v1[1] - v0[1]) * (v1[1] - v0[1])).sqrt();
+                let polyline =
+                    self.edge_discretize(edge_id, chord * TC2::<I, F>::f64_to_f(0.01))?;
+                if polyline.len() > 2 {
+                    sample = polyline[polyline.len() / 2];
+                }
+            }
+            if is_inside(sample) {
+                rv.push(edge_id);
+            }
+        }
+        Ok(rv)
+    }
+
+    /// Returns the vertex indices ordered by `Vertex::creation_sequence()`, i.e. in the order
+    /// the sweep-line created the vertices.
+    pub fn vertices_in_creation_order(&self) -> impl Iterator<Item = VertexIndex> {
+        let mut vertices: Vec<(usize, VertexIndex)> = self
+            .vertices_
+            .iter()
+            .map(|v| {
+                let v = v.get();
+                (v.sequence_, v.id_)
+            })
+            .collect();
+        vertices.sort_unstable_by_key(|v| v.0);
+        vertices.into_iter().map(|v| v.1)
+    }
+
+    /// Converts the diagram into an undirected petgraph graph. Every vertex becomes a node,
+    /// with its coordinates as weight, and every finite edge (one per twin pair) becomes an
+    /// edge weighted by its length. Infinite edges are skipped.
+    /// The node indices are the same as the vertex indices. Curved edges are weighted by their
+    /// chord, see `edges_by_length()`.
+    #[cfg(feature = "petgraph")]
+    pub fn to_petgraph(&self) -> petgraph::graph::UnGraph<[F; 2], F> {
+        self.to_petgraph_with_maps().0
+    }
+
+    /// Same as `to_petgraph()` but also returns the maps from the graph indices back to the
+    /// diagram: the `VertexIndex` of every node and the `EdgeIndex` of every graph edge,
+    /// indexed by `NodeIndex::index()` and `petgraph::graph::EdgeIndex::index()`.
+    /// The mapped edge is the half-edge with the lowest id of the twin pair, it runs from the
+    /// source node to the target node of the graph edge.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let (graph, nodes, edges) = output.to_petgraph_with_maps();
+    /// for e in graph.edge_indices() {
+    ///     let (a, _) = graph.edge_endpoints(e).unwrap();
+    ///     assert_eq!(output.edge_get_vertex0(edges[e.index()])?, Some(nodes[a.index()]));
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    #[cfg(feature = "petgraph")]
+    #[allow(clippy::type_complexity)]
+    pub fn to_petgraph_with_maps(
+        &self,
+    ) -> (
+        petgraph::graph::UnGraph<[F; 2], F>,
+        Vec<VertexIndex>,
+        Vec<EdgeIndex>,
+    ) {
+        let mut graph = petgraph::graph::UnGraph::<[F; 2], F>::with_capacity(
+            self.vertices_.len(),
+            self.edges_.len() / 2,
+        );
+        let mut nodes = Vec::<VertexIndex>::with_capacity(self.vertices_.len());
+        let mut edges = Vec::<EdgeIndex>::with_capacity(self.edges_.len() / 2);
+        for vertex in self.vertices_.iter() {
+            let vertex = vertex.get();
+            let _ = graph.add_node([vertex.x(), vertex.y()]);
+            nodes.push(vertex.get_id());
+        }
+        for (edge_id, length) in self.edges_by_length(F::zero()) {
+            if let (Some(v0), Some(v1)) = (
+                self.edge_get_vertex0_(Some(edge_id)),
+                self._edge_get_vertex1(Some(edge_id)),
+            ) {
+                let _ = graph.add_edge(
+                    petgraph::graph::NodeIndex::new(v0.0),
+                    petgraph::graph::NodeIndex::new(v1.0),
+                    length,
+                );
+                edges.push(edge_id);
+            }
         }
+        (graph, nodes, edges)
     }
 
     /// Returns an iterator over all cells
@@ -945,7 +2034,7 @@ where
     }
 
     /// Returns an iterator over all edges
-    pub fn edge_iter(&self) -> std::slice::Iter<'_, EdgeType<I, F>> {
+    pub fn edge_iter(&self) -> core::slice::Iter<'_, EdgeType<I, F>> {
         self.edges_.iter()
     }
 
@@ -1003,6 +2092,93 @@ where
         self.vertices_.len()
     }
 
+    /// returns the number of cells, edges and vertices in the diagram
+    pub fn counts(&self) -> Counts {
+        Counts {
+            cells: self.cells_.len(),
+            edges: self.edges_.len(),
+            vertices: self.vertices_.len(),
+        }
+    }
+
+    /// Returns the degree, i.e. the number of incident edges, of every vertex indexed by
+    /// `VertexIndex`. See `Vertex::incident_edges()`.
+    /// Point input in general position gives degree 3 everywhere, four or more cocircular
+    /// points and segment input can give larger degrees.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// assert_eq!(vb.build()?.vertex_degrees(), vec![3, 3, 3, 3]);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn vertex_degrees(&self) -> Vec<usize> {
+        self.vertices_
+            .iter()
+            .map(|v| v.get().incident_edges(self).count())
+            .collect()
+    }
+
+    /// Returns statistics of the diagram: the input consumed by the builder, the number of
+    /// cells, vertices and (primary, curved, infinite) edges, and how many circle events needed
+    /// the exact arithmetic fallback.
+    /// The input and circle event counts are only known for diagrams returned by the builder,
+    /// they are zero for deserialized diagrams.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [5, 10]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let stats = vb.build()?.stats();
+    /// assert_eq!(stats.points, 3);
+    /// assert_eq!(stats.vertices, 1);
+    /// assert_eq!(stats.edges, 6);
+    /// assert_eq!(stats.infinite_edges, 6);
+    /// assert_eq!(stats.curved_edges, 0);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn stats(&self) -> DiagramStats {
+        let mut rv = DiagramStats {
+            points: self.build_stats_.0,
+            segments: self.build_stats_.1,
+            cells: self.cells_.len(),
+            vertices: self.vertices_.len(),
+            edges: self.edges_.len(),
+            exact_circle_events: self.build_stats_.2,
+            ..Default::default()
+        };
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            if edge.is_primary() {
+                rv.primary_edges += 1;
+            }
+            if edge.is_curved() {
+                rv.curved_edges += 1;
+            }
+            if self._edge_is_infinite(Some(edge.id())) == Some(true) {
+                rv.infinite_edges += 1;
+            }
+        }
+        rv
+    }
+
+    /// Returns upper bounds of the counts of a diagram built from `n_points` points and
+    /// `n_segments` segments. Useful for pre-allocating buffers before the diagram is built.
+    ///
+    /// Every segment contributes (at most) three sites: the two endpoints and the segment
+    /// itself. With `n` sites Euler's formula limits the number of vertices to `2n-5` and the
+    /// number of edges to `3n-6` (`6n-12` half-edges). The estimate rounds these up to `2n` and
+    /// `6n` so it is valid for any input size.
+    pub fn estimate_counts(n_points: usize, n_segments: usize) -> Counts {
+        let n_sites = n_points + 3 * n_segments;
+        Counts {
+            cells: n_sites,
+            edges: 6 * n_sites,
+            vertices: 2 * n_sites,
+        }
+    }
+
     /// reserves space for an number of additional sites
     pub fn reserve_(&mut self, additional_sites: usize) {
         self.cells_.reserve(additional_sites);
@@ -1019,6 +2195,13 @@ where
         );
     }
 
+    /// Creates the cells of all the sites up front, the sites must be sorted and indexed.
+    pub(crate) fn process_all_sites_(&mut self, sites: &[VSE::SiteEvent<I, F>]) {
+        for site in sites.iter() {
+            self.process_single_site_(site);
+        }
+    }
+
     #[inline]
     fn cell_get_(&self, cell_id: Option<CellIndex>) -> Option<&CellType<I, F>> {
         let _ = cell_id?;
@@ -1044,6 +2227,32 @@ where
         None
     }
 
+    #[inline]
+    /// Returns the color field of the cell.
+    pub fn cell_get_color(&self, cell_id: CellIndex) -> Result<ColorType, BvError> {
+        Ok(self.get_cell(cell_id)?.get().get_color())
+    }
+
+    #[inline]
+    /// Sets the color field of the cell with new value
+    pub fn cell_set_color(&self, cell_id: CellIndex, color: ColorType) -> Result<(), BvError> {
+        let cell = self.get_cell(cell_id)?;
+        let mut c = cell.get();
+        let _ = c.set_color(color);
+        cell.set(c);
+        Ok(())
+    }
+
+    #[inline]
+    /// OR the previous color field value of the cell with this new color value
+    pub fn cell_or_color(&self, cell_id: CellIndex, color: ColorType) -> Result<(), BvError> {
+        let cell = self.get_cell(cell_id)?;
+        let mut c = cell.get();
+        let _ = c.or_color(color);
+        cell.set(c);
+        Ok(())
+    }
+
     fn cell_is_degenerate_(&self, cell_id: Option<CellIndex>) -> bool {
         if cell_id.is_none() {
             return false;
@@ -1055,11 +2264,35 @@ where
     }
 
     /// Returns an edge iterator. This iterates over the edges belonging to this cell starting with
-    /// the incident edge.
+    /// the incident edge, following the `next` links, i.e. counter-clockwise around the cell.
+    /// Every edge of the cell is returned exactly once. The two infinite edges of an unbounded
+    /// cell are linked to each other, so the iteration terminates for those cells as well.
+    /// Degenerate cells yield no edges.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// # use boostvoronoi::diagram::CellIndex;
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let center = output.cells().iter().find(|c| c.get().source_index() == 4).unwrap();
+    /// for edge_id in output.cell_edge_iterator(center.get().id()) {
+    ///     assert_eq!(output.edge_get_cell(edge_id)?, center.get().id());
+    /// }
+    /// assert_eq!(output.cell_edge_iterator(center.get().id()).count(), 4);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
     pub fn cell_edge_iterator(&self, cell_id: CellIndex) -> EdgeNextIterator<'_, I, F> {
         self.cell_edge_iterator_(Some(cell_id))
     }
 
+    /// Returns an edge iterator. This iterates over the edges belonging to the same cell as
+    /// `start_edge`, following the `next` links, starting with `start_edge` itself.
+    /// The iteration stops when `start_edge` is reached again.
+    pub fn cell_edges_from(&self, start_edge: EdgeIndex) -> EdgeNextIterator<'_, I, F> {
+        EdgeNextIterator::<'_, I, F>::new(self, Some(start_edge))
+    }
+
     /// Returns an edge iterator. This iterates over the edges belonging to this cell starting with
     /// the incident edge.
     fn cell_edge_iterator_(&self, cell_id: Option<CellIndex>) -> EdgeNextIterator<'_, I, F> {
@@ -1174,6 +2407,23 @@ where
         })
     }
 
+    /// returns the number of edges originating at this vertex
+    pub(crate) fn vertex_degree_(&self, vertex_id: Option<VertexIndex>) -> Option<usize> {
+        let incident_edge = self.vertex_get_(vertex_id)?.get().incident_edge_;
+        Some(self.edge_rot_next_iterator_(incident_edge).count())
+    }
+
+    /// Returns the number of edges originating at this vertex. A regular Voronoi vertex has
+    /// degree 3, a higher degree means that four or more sites are cocircular.
+    pub fn vertex_degree(&self, vertex_id: VertexIndex) -> Result<usize, BvError> {
+        self.vertex_degree_(Some(vertex_id)).ok_or_else(|| {
+            BvError::IdError(format!(
+                "Vertex id {} (probably) does not exists",
+                vertex_id.0
+            ))
+        })
+    }
+
     /// Create and insert a new edge
     fn create_and_insert_edge(
         &mut self,
@@ -1183,21 +2433,14 @@ where
     ) -> EdgeIndex {
         let new_edge_id = EdgeIndex(self.edges_.len());
         let new_edge = Edge::new_(new_edge_id, cell_id, is_linear, is_primary);
-        let _ = self.edges_.push(new_edge);
+        self.edges_.push(new_edge);
         tln!("Created and inserted new edge : e={}", new_edge_id.0);
         new_edge_id
     }
 
     #[inline]
     pub(crate) fn _edge_get(&self, edge_id: Option<EdgeIndex>) -> Option<&EdgeType<I, F>> {
-        let _ = edge_id?;
-        let rv = self.edges_.get(edge_id.unwrap().0);
-        if rv.is_none() {
-            dbg!(edge_id.unwrap().0);
-            // todo: remove this panic and raise error?
-            panic!();
-        }
-        rv
+        self.edges_.get(edge_id?.0)
     }
 
     /// Overwrites the content of dest with the content of source.
@@ -1342,15 +2585,10 @@ where
     }
 
     #[inline]
-    fn edge_set_cell_(&self, edge_id: Option<EdgeIndex>, cell_id: Option<CellIndex>) {
-        if edge_id.is_none() {
-            return;
-        }
-        if let Some(edgecell) = self._edge_get(edge_id) {
-            let mut edge = edgecell.get();
-            edge.cell_ = cell_id;
-            edgecell.set(edge);
-        }
+    pub fn edge_get_prev(&self, edge_id: EdgeIndex) -> Result<EdgeIndex, BvError> {
+        self._edge_get_prev(Some(edge_id)).ok_or_else(|| {
+            BvError::IdError(format!("Edge {} did not have any prev edge", edge_id.0))
+        })
     }
 
     #[inline]
@@ -1400,6 +2638,915 @@ where
             .ok_or_else(|| BvError::IdError(format!("Edge id {} doesn't exists", edge_id.0)))
     }
 
+    /// Returns all the finite edges paired with their length, sorted by ascending length.
+    /// Only one half-edge of each twin pair is reported (the one with the lowest id).
+    /// The length is measured as the straight line between vertex0 and vertex1, i.e. curved
+    /// edges are measured by their chord.
+    /// If `tolerance` is larger than zero, lengths are grouped into buckets of that size and
+    /// edges within the same bucket are ordered by their `EdgeIndex`. This gives a
+    /// deterministic order even when lengths differ only by rounding errors.
+    pub fn edges_by_length(&self, tolerance: F) -> Vec<(EdgeIndex, F)> {
+        let mut rv: Vec<(EdgeIndex, F)> = self
+            .edges_
+            .iter()
+            .filter_map(|edge| {
+                let edge = edge.get();
+                let edge_id = edge.id();
+                if edge.twin_?.0 < edge_id.0 {
+                    return None;
+                }
+                Some((edge_id, edge.length(self)?))
+            })
+            .collect();
+
+        if tolerance > F::zero() {
+            rv.sort_by(|a, b| {
+                let bucket_a = (a.1 / tolerance).floor();
+                let bucket_b = (b.1 / tolerance).floor();
+                bucket_a
+                    .partial_cmp(&bucket_b)
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.0 .0.cmp(&b.0 .0))
+            });
+        } else {
+            rv.sort_by(|a, b| {
+                a.1.partial_cmp(&b.1)
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.0 .0.cmp(&b.0 .0))
+            });
+        }
+        rv
+    }
+
+    /// Returns the bridges of the graph formed by the vertices and the finite edges, i.e. the
+    /// edges whose removal would disconnect the graph. Infinite edges are not part of the graph.
+    /// Only one half-edge of each twin pair is reported (the one with the lowest id), in
+    /// ascending id order.
+    pub fn bridges(&self) -> Vec<EdgeIndex> {
+        let n = self.vertices_.len();
+        let mut adjacency = vec![Vec::<(usize, EdgeIndex)>::new(); n];
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            let edge_id = edge.id();
+            if let (Some(v0), Some(v1), Some(twin)) = (
+                edge.vertex0(),
+                self._edge_get_vertex1(Some(edge_id)),
+                edge.twin_,
+            ) {
+                let id = if twin.0 < edge_id.0 { twin } else { edge_id };
+                adjacency[v0.0].push((v1.0, id));
+            }
+        }
+        // Tarjan's bridge finding, with an explicit depth first search stack
+        let mut discovery = vec![usize::MAX; n];
+        let mut low = vec![0_usize; n];
+        let mut time = 0_usize;
+        let mut rv = Vec::<EdgeIndex>::new();
+        for root in 0..n {
+            if discovery[root] != usize::MAX {
+                continue;
+            }
+            discovery[root] = time;
+            low[root] = time;
+            time += 1;
+            // (vertex, the edge it was reached by, next adjacency index)
+            let mut stack = vec![(root, None, 0_usize)];
+            while let Some(top) = stack.last_mut() {
+                let (v, parent_edge) = (top.0, top.1);
+                if let Some(&(w, e)) = adjacency[v].get(top.2) {
+                    top.2 += 1;
+                    if Some(e) == parent_edge {
+                        continue;
+                    }
+                    if discovery[w] == usize::MAX {
+                        discovery[w] = time;
+                        low[w] = time;
+                        time += 1;
+                        stack.push((w, Some(e), 0));
+                    } else {
+                        low[v] = low[v].min(discovery[w]);
+                    }
+                } else {
+                    let _ = stack.pop();
+                    if let (Some(e), Some(&(u, _, _))) = (parent_edge, stack.last()) {
+                        low[u] = low[u].min(low[v]);
+                        if low[v] > discovery[u] {
+                            rv.push(e);
+                        }
+                    }
+                }
+            }
+        }
+        rv.sort_unstable_by_key(|e| e.0);
+        rv
+    }
+
+    /// Returns the edges of the Delaunay triangulation, the dual of the Voronoi diagram, as
+    /// pairs of input point source indices `(lower, higher)` in ascending order.
+    /// Two input points are connected if their cells share a primary edge.
+    ///
+    /// Only cells of input points (`SourceCategory::SinglePoint`) are considered, the cells
+    /// of segments and segment endpoints are skipped. Collinear input points are connected
+    /// to their neighbours only, and four or more cocircular points do not get the diagonals
+    /// of their polygon, so the result is not always a full triangulation.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [5, 8], [5, 20]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// assert_eq!(
+    ///     output.delaunay_edges(),
+    ///     vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+    /// );
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn delaunay_edges(&self) -> Vec<(SourceIndex, SourceIndex)> {
+        let point_source = |cell_id: Option<CellIndex>| {
+            let cell = self.cells_.get(cell_id?.0)?.get();
+            (cell.source_category() == SourceCategory::SinglePoint).then(|| cell.source_index())
+        };
+        let mut rv: Vec<(SourceIndex, SourceIndex)> = self
+            .edges_
+            .iter()
+            .map(|edge| edge.get())
+            .filter(|edge| edge.is_primary())
+            .filter_map(|edge| {
+                let a = point_source(edge.cell_)?;
+                let b = point_source(self.edges_.get(edge.twin_?.0)?.get().cell_)?;
+                Some((a.min(b), a.max(b)))
+            })
+            .collect();
+        rv.sort_unstable();
+        rv.dedup();
+        rv
+    }
+
+    /// Verifies the internal consistency of the diagram, returns `BvError::InvariantError` on
+    /// the first violation found.
+    /// Checked invariants:
+    ///   * every cell id matches its position in the cell list.
+    ///   * every non-degenerate cell has an incident edge that belongs to the cell, and the
+    ///     `next` links starting at that edge returns to it while staying inside the cell.
+    ///   * no edge refers to a cell flagged as degenerate (i.e. a cell without incident edge).
+    pub fn check_invariants(&self) -> Result<(), BvError> {
+        for (index, cell) in self.cells_.iter().enumerate() {
+            let cell = cell.get();
+            if cell.id().0 != index {
+                return Err(BvError::InvariantError(format!(
+                    "Cell at position {} has id {}",
+                    index,
+                    cell.id().0
+                )));
+            }
+            let incident_edge = match cell.get_incident_edge() {
+                Some(incident_edge) => incident_edge,
+                None => continue,
+            };
+            let mut edge_id = incident_edge;
+            let mut steps = 0_usize;
+            loop {
+                if self.edge_get_cell_(Some(edge_id)) != Some(cell.id()) {
+                    return Err(BvError::InvariantError(format!(
+                        "Edge {} is linked into cell {} but belongs to another cell",
+                        edge_id.0, index
+                    )));
+                }
+                edge_id = self._edge_get_next(Some(edge_id)).ok_or_else(|| {
+                    BvError::InvariantError(format!(
+                        "Edge {} of cell {} has no next edge",
+                        edge_id.0, index
+                    ))
+                })?;
+                steps += 1;
+                if edge_id == incident_edge {
+                    break;
+                }
+                if steps > self.edges_.len() {
+                    return Err(BvError::InvariantError(format!(
+                        "The edges of cell {} does not form a loop",
+                        index
+                    )));
+                }
+            }
+        }
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            let cell_id = edge.cell_.ok_or_else(|| {
+                BvError::InvariantError(format!("Edge {} has no cell", edge.id().0))
+            })?;
+            if cell_id.0 >= self.cells_.len() {
+                return Err(BvError::InvariantError(format!(
+                    "Edge {} refers to the non-existing cell {}",
+                    edge.id().0,
+                    cell_id.0
+                )));
+            }
+            if self.cell_is_degenerate_(Some(cell_id)) {
+                return Err(BvError::InvariantError(format!(
+                    "Edge {} refers to cell {} that is flagged as degenerate",
+                    edge.id().0,
+                    cell_id.0
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies the half-edge structure of the diagram, returns `BvError::InvariantError` on
+    /// the first violation found.
+    /// Checked invariants, in addition to those of `check_invariants()`:
+    ///   * every edge and vertex id matches its position in the list, and every vertex referred
+    ///     to by an edge exists.
+    ///   * every edge has a twin, `twin(twin(e)) == e` and `twin(e)` belongs to another cell.
+    ///   * `prev(next(e)) == e` and `next(prev(e)) == e`.
+    ///   * `vertex0(next(e)) == vertex1(e)`, where `None` (infinity) must match `None`. This is
+    ///     not checked for diagrams built with `Builder::drop_secondary_edges()`, removing a
+    ///     secondary edge leaves a gap in the loop of its cell.
+    ///   * the incident edge of every vertex starts at that vertex.
+    /// ```
+    /// # use boostvoronoi::builder::{to_segments, Builder};
+    /// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0], [10, 0, 10, 10], [5, 5, 7, 9]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// output.validate_topology()?;
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn validate_topology(&self) -> Result<(), BvError> {
+        self.check_invariants()?;
+        let edge_error = |edge_id: EdgeIndex, what: &str| {
+            BvError::InvariantError(format!("Edge {} {}", edge_id.0, what))
+        };
+        // every segment cell has secondary edges, unless Builder::drop_secondary_edges() was
+        // used. Then next(e) may start at another vertex, where the secondary edge was.
+        let secondary_edges_dropped = self.cells_.iter().any(|c| c.get().contains_segment())
+            && self.edges_.iter().all(|e| e.get().is_primary());
+        for (index, edge) in self.edges_.iter().enumerate() {
+            let edge = edge.get();
+            let edge_id = edge.id();
+            if edge_id.0 != index {
+                return Err(BvError::InvariantError(format!(
+                    "Edge at position {} has id {}",
+                    index, edge_id.0
+                )));
+            }
+            if let Some(vertex_id) = edge.vertex_ {
+                if vertex_id.0 >= self.vertices_.len() {
+                    return Err(BvError::InvariantError(format!(
+                        "Edge {} refers to the non-existing vertex {}",
+                        edge_id.0, vertex_id.0
+                    )));
+                }
+            }
+            let twin_id = self
+                .edge_get_twin_(Some(edge_id))
+                .ok_or_else(|| edge_error(edge_id, "has no twin"))?;
+            if self.edge_get_twin_(Some(twin_id)) != Some(edge_id) {
+                return Err(edge_error(edge_id, "is not the twin of its twin"));
+            }
+            if self.edge_get_cell_(Some(twin_id)) == edge.cell_() {
+                return Err(edge_error(edge_id, "belongs to the same cell as its twin"));
+            }
+            let next_id = self
+                ._edge_get_next(Some(edge_id))
+                .ok_or_else(|| edge_error(edge_id, "has no next edge"))?;
+            let prev_id = self
+                ._edge_get_prev(Some(edge_id))
+                .ok_or_else(|| edge_error(edge_id, "has no prev edge"))?;
+            if self._edge_get_prev(Some(next_id)) != Some(edge_id)
+                || self._edge_get_next(Some(prev_id)) != Some(edge_id)
+            {
+                return Err(edge_error(edge_id, "has inconsistent next and prev links"));
+            }
+            if !secondary_edges_dropped
+                && self.edge_get_vertex0_(Some(next_id)) != self.edge_get_vertex0_(Some(twin_id))
+            {
+                return Err(edge_error(
+                    edge_id,
+                    "does not end where its next edge starts",
+                ));
+            }
+        }
+        for (index, vertex) in self.vertices_.iter().enumerate() {
+            let vertex = vertex.get();
+            if vertex.get_id().0 != index {
+                return Err(BvError::InvariantError(format!(
+                    "Vertex at position {} has id {}",
+                    index,
+                    vertex.get_id().0
+                )));
+            }
+            let incident_edge = vertex._get_incident_edge().ok_or_else(|| {
+                BvError::InvariantError(format!(
+                    "Vertex {} has no incident edge",
+                    vertex.get_id().0
+                ))
+            })?;
+            if self.edge_get_vertex0_(Some(incident_edge)) != Some(vertex.get_id()) {
+                return Err(BvError::InvariantError(format!(
+                    "The incident edge {} of vertex {} does not start at the vertex",
+                    incident_edge.0,
+                    vertex.get_id().0
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the input point of a point cell (a single point or a segment endpoint).
+    pub(crate) fn retrieve_point_(&self, cell_id: CellIndex) -> Result<Point<I>, BvError> {
+        match self.cell_site_geometry(cell_id)? {
+            SiteGeometry::Point(point) => Ok(point),
+            SiteGeometry::Segment(_) => Err(BvError::IdError(format!(
+                "The cell with id:{} is not a point cell",
+                cell_id.0
+            ))),
+        }
+    }
+
+    /// Returns the input segment of a segment cell.
+    pub(crate) fn retrieve_segment_(&self, cell_id: CellIndex) -> Result<Line<I>, BvError> {
+        match self.cell_site_geometry(cell_id)? {
+            SiteGeometry::Segment(segment) => Ok(segment),
+            SiteGeometry::Point(_) => Err(BvError::IdError(format!(
+                "The cell with id:{} is not a segment cell",
+                cell_id.0
+            ))),
+        }
+    }
+
+    /// Discretizes a finite curved edge into `discretization`, which must initially contain the
+    /// two endpoints of the edge. `max_dist` is the maximum allowed distance between the
+    /// parabolic arc and the discretized line segments.
+    pub(crate) fn discretize_curved_edge_(
+        &self,
+        edge_id: EdgeIndex,
+        max_dist: F,
+        discretization: &mut Vec<[F; 2]>,
+    ) -> Result<(), BvError> {
+        let cell_id = self.edge_get_cell(edge_id)?;
+        let twin_cell_id = self.edge_get_cell(self.edge_get_twin(edge_id)?)?;
+        let (point_cell_id, segment_cell_id) = if self.get_cell(cell_id)?.get().contains_point() {
+            (cell_id, twin_cell_id)
+        } else {
+            (twin_cell_id, cell_id)
+        };
+        let point = self.retrieve_point_(point_cell_id)?;
+        let segment = self.retrieve_segment_(segment_cell_id)?;
+        VU::VoronoiVisualUtils::<I, F>::discretize(
+            &point,
+            &segment,
+            max_dist,
+            &VU::SimpleAffine::default(),
+            discretization,
+        );
+        Ok(())
+    }
+
+    /// Samples a finite edge into a polyline going from vertex0 to vertex1. Curved (parabolic)
+    /// edges are discretized so that no point of the arc is further away than `max_dist` from
+    /// the polyline, linear edges are returned as their two vertices.
+    /// Returns `BvError::ValueError` for infinite edges.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// let points = to_points::<i32, i32>(&[[5, 5]]);
+    /// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0], [10, 0, 10, 10]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// vb.with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// for edge in output.edges().iter() {
+    ///     let edge = edge.get();
+    ///     if !output.edge_is_finite(edge.id())? {
+    ///         continue;
+    ///     }
+    ///     let polyline = output.edge_discretize(edge.id(), 0.01)?;
+    ///     assert_eq!(polyline.len() > 2, edge.is_curved());
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn edge_discretize(&self, edge_id: EdgeIndex, max_dist: F) -> Result<Vec<[F; 2]>, BvError> {
+        let (v0, v1) = match self.edge_vertices(edge_id)? {
+            (Some(v0), Some(v1)) => (self.vertex_position(v0)?, self.vertex_position(v1)?),
+            _ => {
+                return Err(BvError::ValueError(format!(
+                    "The edge with id:{} is infinite",
+                    edge_id.0
+                )))
+            }
+        };
+        let mut discretization = vec![v0, v1];
+        if self.get_edge(edge_id)?.get().is_curved() {
+            self.discretize_curved_edge_(edge_id, max_dist, &mut discretization)?;
+        }
+        Ok(discretization)
+    }
+
+    /// Returns true if the edge lies to the left of the input segment of its cell, seen from
+    /// the `start` towards the `end` of the segment as it was given to the builder. If the
+    /// edge belongs to a point cell, the twin edge (in the segment cell) is examined instead.
+    /// The internal (sorted) segment direction is never exposed, so reversing an input
+    /// segment flips the result.
+    ///
+    /// Returns `Ok(None)` if neither the edge nor its twin belongs to a segment cell, if all
+    /// the vertices of the edge are located on the line of the segment (e.g. edges without
+    /// vertices), or if the vertices are on opposite sides of the line (a secondary edge
+    /// crossing it at the segment endpoint).
+    pub fn edge_is_left_of_segment(&self, edge_id: EdgeIndex) -> Result<Option<bool>, BvError> {
+        let cell_id = {
+            let cell_id = self.edge_get_cell(edge_id)?;
+            if self.get_cell(cell_id)?.get().contains_segment() {
+                cell_id
+            } else {
+                let twin_cell_id = self.edge_get_cell(self.edge_get_twin(edge_id)?)?;
+                if !self.get_cell(twin_cell_id)?.get().contains_segment() {
+                    return Ok(None);
+                }
+                twin_cell_id
+            }
+        };
+        let segment = self.retrieve_segment_(cell_id)?;
+        let [sx, sy] = [
+            TC2::<I, F>::i_to_f(segment.start.x),
+            TC2::<I, F>::i_to_f(segment.start.y),
+        ];
+        let [dx, dy] = [
+            TC2::<I, F>::i_to_f(segment.end.x) - sx,
+            TC2::<I, F>::i_to_f(segment.end.y) - sy,
+        ];
+        let crosses = [
+            self.edge_get_vertex0(edge_id)?,
+            self.edge_get_vertex1(edge_id)?,
+        ]
+        .iter()
+        .flatten()
+        .map(|v| {
+            let v = self.vertex_get(*v)?.get();
+            Ok(dx * (v.y() - sy) - dy * (v.x() - sx))
+        })
+        .collect::<Result<Vec<F>, BvError>>()?;
+        let left = crosses.iter().any(|c| *c > F::zero());
+        let right = crosses.iter().any(|c| *c < F::zero());
+        Ok(if left != right { Some(left) } else { None })
+    }
+
+    /// Returns the side of the input segment an edge of a segment cell (or the twin of such an
+    /// edge) lies on, see `edge_is_left_of_segment()`.
+    ///
+    /// Just like in Boost, every input segment has a single cell, covering both sides of the
+    /// segment. So the side is a property of the edges bounding the cell, not of the cell:
+    /// the primary edges of a segment cell are always on one side of the segment, while a
+    /// secondary edge (on the perpendicular line through an endpoint) may cross it, and then
+    /// returns `Ok(None)`.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+    /// # use boostvoronoi::diagram::SegmentSide;
+    /// let points = to_points::<i32, i32>(&[[5, 5]]);
+    /// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// vb.with_segments(segments.iter())?;
+    /// let output = vb.build()?;
+    /// let sides: Vec<_> = output
+    ///     .edges()
+    ///     .iter()
+    ///     .filter(|e| e.get().is_curved())
+    ///     .map(|e| output.edge_segment_side(e.get().id()))
+    ///     .collect::<Result<_, _>>()?;
+    /// // the parabolic arc between the point and the segment, and its twin
+    /// assert_eq!(sides, vec![Some(SegmentSide::Left); 2]);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn edge_segment_side(&self, edge_id: EdgeIndex) -> Result<Option<SegmentSide>, BvError> {
+        Ok(self.edge_is_left_of_segment(edge_id)?.map(|is_left| {
+            if is_left {
+                SegmentSide::Left
+            } else {
+                SegmentSide::Right
+            }
+        }))
+    }
+
+    /// Returns the vertices of a finite cell as a CCW polygon, in the order of
+    /// `cell_edge_iterator()`. Curved edges are represented by their straight chord, use
+    /// `cell_polygon_discretized()` to sample them.
+    /// Returns `Ok(None)` for degenerate cells and cells with infinite edges.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// for cell in output.cells().iter() {
+    ///     let cell = cell.get();
+    ///     let polygon = output.cell_polygon(cell.id())?;
+    ///     // only the center point has a finite cell
+    ///     assert_eq!(polygon.is_some(), cell.source_index() == 4);
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn cell_polygon(&self, cell_id: CellIndex) -> Result<Option<Vec<[F; 2]>>, BvError> {
+        if self.get_cell(cell_id)?.get().is_degenerate() {
+            return Ok(None);
+        }
+        let mut polygon = Vec::<[F; 2]>::new();
+        for edge_id in self.cell_edge_iterator(cell_id) {
+            match self.edge_vertices(edge_id)? {
+                (Some(v0), Some(_)) => polygon.push(self.vertex_position(v0)?),
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(polygon))
+    }
+
+    /// Returns the boundary of a finite cell as a CCW polygon, curved edges are discretized with
+    /// `tolerance` as the maximum allowed distance to the arc.
+    /// Returns `Ok(None)` for degenerate cells and cells with infinite edges.
+    pub fn cell_polygon_discretized(
+        &self,
+        cell_id: CellIndex,
+        tolerance: F,
+    ) -> Result<Option<Vec<[F; 2]>>, BvError> {
+        if self.get_cell(cell_id)?.get().is_degenerate() {
+            return Ok(None);
+        }
+        let mut polygon = Vec::<[F; 2]>::new();
+        for edge_id in self.cell_edge_iterator(cell_id) {
+            let (v0, v1) = match (
+                self.edge_get_vertex0_(Some(edge_id)),
+                self._edge_get_vertex1(Some(edge_id)),
+            ) {
+                (Some(v0), Some(v1)) => (self.vertices_[v0.0].get(), self.vertices_[v1.0].get()),
+                _ => return Ok(None),
+            };
+            polygon.push([v0.x(), v0.y()]);
+            if self.get_edge(edge_id)?.get().is_curved() {
+                let mut discretization = vec![[v0.x(), v0.y()], [v1.x(), v1.y()]];
+                self.discretize_curved_edge_(edge_id, tolerance, &mut discretization)?;
+                // the first and the last points are the edge vertices
+                polygon.extend(
+                    discretization
+                        .iter()
+                        .skip(1)
+                        .take(discretization.len().saturating_sub(2)),
+                );
+            }
+        }
+        Ok(Some(polygon))
+    }
+
+    /// Lazily computes the area, centroid and perimeter of every finite cell, one cell at a
+    /// time, so only the boundary of a single cell is kept in memory.
+    /// Curved edges are discretized with `tolerance` as the maximum allowed distance to the arc.
+    /// Cells with infinite edges and degenerate cells are skipped.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// let analysis: Vec<_> = output
+    ///     .analyze_cells(0.1)
+    ///     .collect::<Result<_, _>>()?;
+    /// // only the center point has a finite cell, a square with the side 10/√2
+    /// assert_eq!(analysis.len(), 1);
+    /// assert!((analysis[0].area - 50.0).abs() < 1e-9);
+    /// assert!((analysis[0].perimeter - 4.0 * 50_f64.sqrt()).abs() < 1e-9);
+    /// assert!((analysis[0].centroid[0] - 5.0).abs() < 1e-9);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn analyze_cells<'a>(
+        &'a self,
+        tolerance: F,
+    ) -> impl Iterator<Item = Result<CellAnalysis<F>, BvError>> + 'a {
+        self.cells_.iter().filter_map(move |cell| {
+            let cell_id = cell.get().id();
+            let polygon = match self.cell_polygon_discretized(cell_id, tolerance) {
+                Ok(Some(polygon)) => polygon,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+            let (area, centroid, perimeter) = Self::polygon_metrics_(&polygon);
+            Some(Ok(CellAnalysis {
+                cell_id,
+                area,
+                centroid,
+                perimeter,
+            }))
+        })
+    }
+
+    /// Returns the (area, centroid, perimeter) of a CCW polygon.
+    fn polygon_metrics_(polygon: &[[F; 2]]) -> (F, [F; 2], F) {
+        let mut area = F::zero();
+        let mut perimeter = F::zero();
+        let mut centroid = [F::zero(), F::zero()];
+        for (i, a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            let cross = a[0] * b[1] - b[0] * a[1];
+            area = area + cross;
+            centroid[0] = centroid[0] + (a[0] + b[0]) * cross;
+            centroid[1] = centroid[1] + (a[1] + b[1]) * cross;
+            perimeter = perimeter + (b[0] - a[0]).hypot(b[1] - a[1]);
+        }
+        let area = area * TC2::<I, F>::f64_to_f(0.5);
+        if area != F::zero() {
+            let scale = TC2::<I, F>::f64_to_f(6.0) * area;
+            centroid = [centroid[0] / scale, centroid[1] / scale];
+        }
+        (area, centroid, perimeter)
+    }
+
+    /// Returns the area of a finite cell, computed with the shoelace formula over
+    /// `cell_polygon()`. Curved edges contribute their straight chord, use `analyze_cells()`
+    /// for the area of the discretized arcs.
+    /// Returns `Ok(None)` for degenerate cells and cells with infinite edges.
+    pub fn cell_area(&self, cell_id: CellIndex) -> Result<Option<F>, BvError> {
+        Ok(self
+            .cell_polygon(cell_id)?
+            .map(|polygon| Self::polygon_metrics_(&polygon).0))
+    }
+
+    /// Returns the perimeter of a finite cell, the length of `cell_polygon()`. Curved edges
+    /// contribute their straight chord, use `analyze_cells()` for the length of the
+    /// discretized arcs.
+    /// Returns `Ok(None)` for degenerate cells and cells with infinite edges.
+    pub fn cell_perimeter(&self, cell_id: CellIndex) -> Result<Option<F>, BvError> {
+        Ok(self
+            .cell_polygon(cell_id)?
+            .map(|polygon| Self::polygon_metrics_(&polygon).2))
+    }
+
+    /// Returns the approximate diameter of a finite cell, i.e. the largest distance between
+    /// two points on the cell boundary. Curved edges are discretized with `tolerance` as the
+    /// maximum allowed distance to the arc, so the result may be up to `2*tolerance` too small.
+    /// The diameter is found with rotating calipers over the convex hull of the boundary.
+    /// Returns `Ok(None)` for degenerate cells and cells with infinite edges.
+    pub fn cell_diameter(&self, cell_id: CellIndex, tolerance: F) -> Result<Option<F>, BvError> {
+        let polygon = match self.cell_polygon_discretized(cell_id, tolerance)? {
+            Some(polygon) => polygon,
+            None => return Ok(None),
+        };
+        let hull = convex_hull_(polygon);
+        let distance = |a: [F; 2], b: [F; 2]| (b[0] - a[0]).hypot(b[1] - a[1]);
+        // twice the area of the triangle a,b,c
+        let area = |a: [F; 2], b: [F; 2], c: [F; 2]| {
+            ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs()
+        };
+        let n = hull.len();
+        if n < 3 {
+            return Ok(Some(if n == 2 {
+                distance(hull[0], hull[1])
+            } else {
+                F::zero()
+            }));
+        }
+        let mut diameter = F::zero();
+        let mut j = 1;
+        for i in 0..n {
+            let next_i = (i + 1) % n;
+            // advance j while it moves further away from the edge i -> i+1
+            while area(hull[i], hull[next_i], hull[(j + 1) % n])
+                > area(hull[i], hull[next_i], hull[j])
+            {
+                j = (j + 1) % n;
+            }
+            diameter = diameter
+                .max(distance(hull[i], hull[j]))
+                .max(distance(hull[next_i], hull[j]));
+        }
+        Ok(Some(diameter))
+    }
+
+    /// Tessellates every finite cell into a triangle fan around the average of its polygon
+    /// vertices. Curved edges are discretized so that no point of the arc is further away than
+    /// `tolerance` from the discretized line segments.
+    /// Cells with infinite edges and degenerate cells are skipped.
+    /// Returns a shared vertex buffer and the CCW oriented triangles as indices into that buffer.
+    pub fn triangulate_cells(&self, tolerance: F) -> Result<TriangleMesh<F>, BvError> {
+        let mut vertices = Vec::<[F; 2]>::with_capacity(self.vertices_.len() * 2);
+        let mut triangles = Vec::<[u32; 3]>::new();
+        // maps diagram VertexIndex to an index in the vertex buffer
+        let mut vertex_map = vec![None; self.vertices_.len()];
+        let mut polygon = Vec::<u32>::new();
+
+        'cells: for cell in self.cells_.iter() {
+            let cell = cell.get();
+            if cell.is_degenerate() {
+                continue;
+            }
+            polygon.clear();
+            for edge_id in self.cell_edge_iterator(cell.id()) {
+                let (v0, v1) = match (
+                    self.edge_get_vertex0_(Some(edge_id)),
+                    self._edge_get_vertex1(Some(edge_id)),
+                ) {
+                    (Some(v0), Some(v1)) => (v0, v1),
+                    _ => continue 'cells,
+                };
+                let index = *vertex_map[v0.0].get_or_insert_with(|| {
+                    let v = self.vertices_[v0.0].get();
+                    vertices.push([v.x(), v.y()]);
+                    (vertices.len() - 1) as u32
+                });
+                polygon.push(index);
+                if self.get_edge(edge_id)?.get().is_curved() {
+                    let v0 = self.vertices_[v0.0].get();
+                    let v1 = self.vertices_[v1.0].get();
+                    let mut discretization = vec![[v0.x(), v0.y()], [v1.x(), v1.y()]];
+                    self.discretize_curved_edge_(edge_id, tolerance, &mut discretization)?;
+                    // the first and the last points are the edge vertices
+                    for p in discretization
+                        .iter()
+                        .skip(1)
+                        .take(discretization.len().saturating_sub(2))
+                    {
+                        vertices.push(*p);
+                        polygon.push((vertices.len() - 1) as u32);
+                    }
+                }
+            }
+            if polygon.len() < 3 {
+                continue;
+            }
+            let mut centroid = [F::zero(), F::zero()];
+            for i in polygon.iter() {
+                centroid[0] = centroid[0] + vertices[*i as usize][0];
+                centroid[1] = centroid[1] + vertices[*i as usize][1];
+            }
+            let n = TC2::<I, F>::f64_to_f(polygon.len() as f64);
+            vertices.push([centroid[0] / n, centroid[1] / n]);
+            let centroid = (vertices.len() - 1) as u32;
+            for i in 0..polygon.len() {
+                triangles.push([centroid, polygon[i], polygon[(i + 1) % polygon.len()]]);
+            }
+        }
+        Ok((vertices, triangles))
+    }
+
+    /// Returns a copy of the diagram where the finite edges shorter than `length` have been
+    /// removed, e.g. to get rid of the tiny spurs of a noisy medial axis.
+    ///
+    /// The edges are removed in ascending length order (measured by their chord, see
+    /// `edges_by_length()`). Each removed edge merges its two vertices into one, positioned at
+    /// the average of the merged vertices, and the `next`/`prev` links of the surrounding edges
+    /// are repaired. An edge is kept if removing it would collapse a cycle of edges into a
+    /// single vertex, or if it would leave either of its cells with less than two edges.
+    ///
+    /// The merged vertices get a new clearance, see `Vertex::clearance()`, the least exact
+    /// `Vertex::kind()` of the merged vertices, and no `Vertex::generating_sites()`.
+    ///
+    /// Note that the source diagram is unchanged, and that the remaining edges and vertices are
+    /// renumbered. The cells keep their ids.
+    pub fn prune_edges_shorter_than(&self, length: F) -> Diagram<I, F> {
+        let mut rv = Diagram {
+            cells_: self
+                .cells_
+                .iter()
+                .map(|c| Rc::new(cell::Cell::new(c.get())))
+                .collect(),
+            vertices_: self
+                .vertices_
+                .iter()
+                .map(|v| Rc::new(cell::Cell::new(v.get())))
+                .collect(),
+            edges_: self
+                .edges_
+                .iter()
+                .map(|e| Rc::new(cell::Cell::new(e.get())))
+                .collect(),
+            sites_: self.sites_.clone(),
+            build_stats_: self.build_stats_,
+            exterior_: Default::default(),
+        };
+        rv.merge_short_edges_(|edge_length| edge_length < length);
+        rv
+    }
+
+    /// Welds the vertices that are within `epsilon` of each other, e.g. the almost coincident
+    /// vertices emitted for near-degenerate input, so that they are shared exactly.
+    ///
+    /// Vertices are welded if they are connected by a finite edge no longer than `epsilon`
+    /// (measured by its chord), clusters of such vertices are welded transitively. The connecting
+    /// edges are removed, the `next`/`prev` links of the surrounding edges are repaired and the
+    /// edges of the welded vertices are pointed to the remaining vertex, positioned at the
+    /// average of the cluster. The same safeguards as in `prune_edges_shorter_than()` apply,
+    /// edges are kept if removing them would collapse a cycle or leave a cell with less than
+    /// two edges. The welded vertices get their clearance, kind and generating sites updated
+    /// just like the merged vertices of `prune_edges_shorter_than()`.
+    ///
+    /// Note that the remaining edges and vertices are renumbered.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// // almost cocircular points, the two vertices near (50,50) are 0.71 apart
+    /// let points = to_points::<i32, i32>(&[[0, 0], [100, 0], [0, 100], [100, 101]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let mut output = vb.build()?;
+    /// assert_eq!(output.num_vertices(), 2);
+    /// output.weld_vertices(1.0);
+    /// output.check_invariants()?;
+    /// assert_eq!(output.num_vertices(), 1);
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn weld_vertices(&mut self, epsilon: F) {
+        self.merge_short_edges_(|edge_length| edge_length <= epsilon);
+    }
+
+    /// Removes the finite edges accepted by `is_short`, in ascending length order, and merges
+    /// the two vertices of every removed edge. See `prune_edges_shorter_than()`.
+    fn merge_short_edges_<P>(&mut self, is_short: P)
+    where
+        P: Fn(F) -> bool,
+    {
+        self.exterior_ = Default::default();
+        // union-find of the merged vertices
+        fn find(parent: &mut [usize], mut v: usize) -> usize {
+            while parent[v] != v {
+                parent[v] = parent[parent[v]];
+                v = parent[v];
+            }
+            v
+        }
+        let mut parent: Vec<usize> = (0..self.vertices_.len()).collect();
+        let mut cell_edges: Vec<usize> = (0..self.cells_.len())
+            .map(|c| self.cell_edge_iterator_(Some(CellIndex(c))).count())
+            .collect();
+        let mut remove = vec![false; self.edges_.len()];
+
+        for (edge, edge_length) in self.edges_by_length(F::zero()) {
+            if !is_short(edge_length) {
+                break;
+            }
+            let edge_id = Some(edge);
+            let twin_id = self.edge_get_twin_(edge_id);
+            let (v0, v1) = match (
+                self.edge_get_vertex0_(edge_id),
+                self._edge_get_vertex1(edge_id),
+            ) {
+                (Some(v0), Some(v1)) => (find(&mut parent, v0.0), find(&mut parent, v1.0)),
+                _ => continue,
+            };
+            let (c0, c1) = match (self.edge_get_cell_(edge_id), self.edge_get_cell_(twin_id)) {
+                (Some(c0), Some(c1)) => (c0.0, c1.0),
+                _ => continue,
+            };
+            if v0 == v1 || cell_edges[c0] <= 2 || cell_edges[c1] <= 2 {
+                continue;
+            }
+            parent[v1] = v0;
+            cell_edges[c0] -= 1;
+            cell_edges[c1] -= 1;
+            remove[edge.0] = true;
+            if let Some(twin_id) = twin_id {
+                remove[twin_id.0] = true;
+            }
+        }
+
+        // move every merged vertex to the average position of its group
+        let mut sums = vec![(F::zero(), F::zero(), F::zero()); self.vertices_.len()];
+        let mut kinds = vec![VertexKind::default(); self.vertices_.len()];
+        for v in 0..self.vertices_.len() {
+            let root = find(&mut parent, v);
+            let vertex = self.vertices_[v].get();
+            let sum = &mut sums[root];
+            *sum = (sum.0 + vertex.x_, sum.1 + vertex.y_, sum.2 + F::one());
+            if vertex.kind_ as u8 > kinds[root] as u8 {
+                kinds[root] = vertex.kind_;
+            }
+        }
+        let position = |sum: (F, F, F)| [sum.0 / sum.2, sum.1 / sum.2];
+        // the clearance of a merged vertex is the distance to the closest of the sites around it
+        let mut clearances: Vec<Option<F>> = vec![None; self.vertices_.len()];
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            let site = edge.cell_.and_then(|c| self.sites_.get(c.0));
+            if let (Some(v), Some(site)) = (edge.vertex_, site) {
+                let root = find(&mut parent, v.0);
+                if sums[root].2 > F::one() {
+                    let distance = Self::site_distance_(site, position(sums[root]));
+                    clearances[root] = Some(clearances[root].map_or(distance, |c| c.min(distance)));
+                }
+            }
+        }
+        for v in 0..self.vertices_.len() {
+            let root = find(&mut parent, v);
+            if sums[root].2 > F::one() {
+                let mut vertex = self.vertices_[v].get();
+                [vertex.x_, vertex.y_] = position(sums[root]);
+                vertex.kind_ = kinds[root];
+                vertex.sites_ = None;
+                if let Some(clearance) = clearances[root] {
+                    vertex.clearance_ = clearance;
+                }
+                self.vertices_[v].set(vertex);
+            }
+        }
+
+        self.remove_edges_(|_, edge_id| remove[edge_id.0]);
+    }
+
     /// Remove degenerate edge.
     fn _remove_edge(&mut self, edge: Option<EdgeIndex>) {
         #[cfg(feature = "console_debug")]
@@ -1445,10 +3592,25 @@ where
         );
     }
 
-    fn _vertex_new_2(&mut self, x: F, y: F, is_site_vertex: bool) -> VertexIndex {
+    /// Adds the vertex of a circle event, `cells` are the cells of the three sites of the event.
+    fn _vertex_new_2(&mut self, circle: &VC::CircleEvent, cells: [CellIndex; 3]) -> VertexIndex {
+        let sites = cells.map(|c| self.cells_[c.0].get().source_index_2());
         let new_vertex_id = VertexIndex(self.vertices_.len());
-        let new_edge = Vertex::new_3(new_vertex_id, x, y, is_site_vertex);
-        let _ = self.vertices_.push(new_edge);
+        let new_edge = Vertex::new_3(
+            new_vertex_id,
+            TC2::<I, F>::f64_to_f(circle.raw_x()),
+            TC2::<I, F>::f64_to_f(circle.raw_y()),
+            circle.is_site_point(),
+        );
+        {
+            let mut v = new_edge.get();
+            v.kind_ = circle.kind();
+            // lower_x is the rightmost point of the circle
+            v.clearance_ = TC2::<I, F>::f64_to_f(circle.raw_lower_x() - circle.raw_x());
+            v.sites_ = Some(sites);
+            new_edge.set(v);
+        }
+        self.vertices_.push(new_edge);
         #[cfg(feature = "console_debug")]
         assert_eq!(self.vertices_.len() - 1, new_vertex_id.0);
         new_vertex_id
@@ -1471,10 +3633,11 @@ where
         self._edge_get(edge_id).and_then(|x| x.get().vertex0())
     }
 
+    /// Returns the start vertex of the edge, i.e. the vertex stored in the half-edge itself.
+    /// `None` means that the edge starts at infinity.
     #[inline]
-    // todo: add error when edge is not found
     pub fn edge_get_vertex0(&self, edge_id: EdgeIndex) -> Result<Option<VertexIndex>, BvError> {
-        Ok(self.edge_get_vertex0_(Some(edge_id)))
+        Ok(self.get_edge(edge_id)?.get().vertex0())
     }
 
     #[inline]
@@ -1484,10 +3647,54 @@ where
         self.edge_get_vertex0_(twin)
     }
 
+    /// Returns the end vertex of the edge, i.e. the start vertex of the twin half-edge.
+    /// `None` means that the edge ends at infinity.
     #[inline]
-    // todo: add error when edge is not found
     pub fn edge_get_vertex1(&self, edge_id: EdgeIndex) -> Result<Option<VertexIndex>, BvError> {
-        Ok(self._edge_get_vertex1(Some(edge_id)))
+        let twin = self.get_edge(edge_id)?.get().twin()?;
+        Ok(self.get_edge(twin)?.get().vertex0())
+    }
+
+    /// Returns the `(start, end)` vertices of the edge, `None` means infinity.
+    ///
+    /// The start vertex is the vertex of the half-edge itself and the end vertex is the
+    /// vertex of its twin, so the twin edge returns the same vertices swapped. Walking the
+    /// edges of a cell with `next` (counter clockwise), the end vertex of an edge is the start
+    /// vertex of the next edge.
+    /// A finite edge has two vertices, a ray has one (`(Some, None)` or `(None, Some)`) and a
+    /// line, which only exists in diagrams without vertices, has none.
+    pub fn edge_vertices(
+        &self,
+        edge_id: EdgeIndex,
+    ) -> Result<(Option<VertexIndex>, Option<VertexIndex>), BvError> {
+        Ok((
+            self.edge_get_vertex0(edge_id)?,
+            self.edge_get_vertex1(edge_id)?,
+        ))
+    }
+
+    /// Returns which of the vertices of the edge that are missing. The twin edge has the
+    /// `InfiniteAt0` and `InfiniteAt1` kinds swapped.
+    /// ```
+    /// # use boostvoronoi::builder::{to_points, Builder};
+    /// # use boostvoronoi::diagram::EdgeKind;
+    /// let points = to_points::<i32, i32>(&[[0, 0], [10, 0]]);
+    /// let mut vb = Builder::<i32, f64>::default();
+    /// vb.with_vertices(points.iter())?;
+    /// let output = vb.build()?;
+    /// // two points are separated by a single line
+    /// for edge in output.edges().iter() {
+    ///     assert_eq!(output.edge_kind(edge.get().id())?, EdgeKind::FullyInfinite);
+    /// }
+    /// # Ok::<(), boostvoronoi::BvError>(())
+    /// ```
+    pub fn edge_kind(&self, edge_id: EdgeIndex) -> Result<EdgeKind, BvError> {
+        Ok(match self.edge_vertices(edge_id)? {
+            (Some(_), Some(_)) => EdgeKind::Finite,
+            (None, Some(_)) => EdgeKind::InfiniteAt0,
+            (Some(_), None) => EdgeKind::InfiniteAt1,
+            (None, None) => EdgeKind::FullyInfinite,
+        })
     }
 
     #[inline]
@@ -1572,14 +3779,7 @@ where
         let site1_index = site1.sorted_index();
         let site2_index = site2.sorted_index();
 
-        let is_linear = VSE::SiteEvent::is_linear_edge(&site1, &site2);
-        let is_primary = VSE::SiteEvent::is_primary_edge(&site1, &site2);
-
-        // Create a new half-edge that belongs to the first site.
-        let edge1_id = self.create_and_insert_edge(CellIndex(site1_index), is_linear, is_primary);
-
-        // Create a new half-edge that belongs to the second site.
-        let edge2_id = self.create_and_insert_edge(CellIndex(site2_index), is_linear, is_primary);
+        let (edge1_id, edge2_id) = self._insert_bisector(&site1, &site2);
 
         // Add the initial cell during the first edge insertion.
         if self.cells_.is_empty() {
@@ -1598,20 +3798,68 @@ where
             site2.source_category(),
         );
 
-        // Set up pointers to cells. Todo! is this needed? Didn't we do this already?
-        self.edge_set_cell_(Some(edge1_id), Some(CellIndex(site1_index)));
-        self.edge_set_cell_(Some(edge2_id), Some(CellIndex(site2_index)));
+        //tln!("edge1: {:?}", self.get_edge_(edge1_id).get());
+        //tln!("edge2: {:?}", self.get_edge_(edge2_id).get());
+        //tln!("edges.len():{}", self.edges_.len());
+        (edge1_id, edge2_id)
+    }
+
+    /// Insert a pair of twin half-edges, without vertices, forming the bisector of the two
+    /// sites. The first half-edge belongs to the cell of site1, the second to the cell of site2.
+    /// The cells are not created.
+    pub(crate) fn _insert_bisector(
+        &mut self,
+        site1: &VSE::SiteEvent<I, F>,
+        site2: &VSE::SiteEvent<I, F>,
+    ) -> (EdgeIndex, EdgeIndex) {
+        let is_linear = VSE::SiteEvent::is_linear_edge(site1, site2);
+        let is_primary = VSE::SiteEvent::is_primary_edge(site1, site2);
+
+        // Create a new half-edge that belongs to the first site.
+        let edge1_id =
+            self.create_and_insert_edge(CellIndex(site1.sorted_index()), is_linear, is_primary);
+
+        // Create a new half-edge that belongs to the second site.
+        let edge2_id =
+            self.create_and_insert_edge(CellIndex(site2.sorted_index()), is_linear, is_primary);
 
         // Set up twin pointers.
         self.edge_set_twin_(Some(edge1_id), Some(edge2_id));
         self.edge_set_twin_(Some(edge2_id), Some(edge1_id));
-
-        //tln!("edge1: {:?}", self.get_edge_(edge1_id).get());
-        //tln!("edge2: {:?}", self.get_edge_(edge2_id).get());
-        //tln!("edges.len():{}", self.edges_.len());
         (edge1_id, edge2_id)
     }
 
+    /// Inserts a Voronoi vertex at the intersection point of three previously added
+    /// half-edges, closing a cycle of three sites instead of creating a new bisector.
+    /// edge12 belongs to site1, edge23 to site2 and edge31 to site3; the three half-edges
+    /// are directed out of the new vertex. Only used by the farthest-point diagram, where
+    /// the last three sites surround the final vertex.
+    pub(crate) fn _insert_last_vertex(
+        &mut self,
+        circle: VC::CircleEvent,
+        edge12_id: EdgeIndex,
+        edge23_id: EdgeIndex,
+        edge31_id: EdgeIndex,
+    ) -> Result<(), BvError> {
+        let cells = [
+            self.edge_get_cell(edge12_id)?,
+            self.edge_get_cell(edge23_id)?,
+            self.edge_get_cell(edge31_id)?,
+        ];
+        let new_vertex_id = self._vertex_new_2(&circle, cells);
+        for (edge_id, prev_id) in [
+            (edge12_id, edge31_id),
+            (edge23_id, edge12_id),
+            (edge31_id, edge23_id),
+        ] {
+            self._edge_set_vertex0(Some(edge_id), Some(new_vertex_id));
+            let prev_twin_id = self.edge_get_twin_(Some(prev_id));
+            self._edge_set_prev(Some(edge_id), prev_twin_id);
+            self._edge_set_next(prev_twin_id, Some(edge_id));
+        }
+        Ok(())
+    }
+
     /// Insert a new half-edge into the output data structure with the
     /// start at the point where two previously added half-edges intersect.
     /// Takes as input two sites that create a new bisector, circle event
@@ -1625,7 +3873,7 @@ where
         circle: VC::CircleEvent,
         edge12_id: EdgeIndex,
         edge23_id: EdgeIndex,
-    ) -> (EdgeIndex, EdgeIndex) {
+    ) -> Result<(EdgeIndex, EdgeIndex), BvError> {
         /*tln!("-> insert_new_edge_5()");
         tln!(
             "site1:{:?}\nsite3:{:?}\ncircle:{:?}\nedge12_id:{:?}\nedge23_id{:?}\n",
@@ -1648,11 +3896,19 @@ where
         let new_edge2_id =
             self.create_and_insert_edge(CellIndex(site3.sorted_index()), is_linear, is_primary);
 
-        // Add a new Voronoi vertex.
+        // Add a new Voronoi vertex. The cell of the middle site is the one of the (A, B)
+        // bisector pair that does not belong to site1.
+        let site2_cell = match self.edge_get_cell_(Some(edge12_id)) {
+            Some(cell) if cell.0 != site1.sorted_index() => cell,
+            _ => self.edge_get_cell(self.edge_get_twin(edge12_id)?)?,
+        };
         let new_vertex_id = self._vertex_new_2(
-            TC2::<I, F>::f64_to_f(circle.raw_x()),
-            TC2::<I, F>::f64_to_f(circle.raw_y()),
-            circle.is_site_point(),
+            &circle,
+            [
+                CellIndex(site1.sorted_index()),
+                site2_cell,
+                CellIndex(site3.sorted_index()),
+            ],
         );
 
         // Update vertex pointers of the old edges.
@@ -1692,13 +3948,19 @@ where
         //tln!("edge23: {:?}", self.get_edge_(edge23_id).get());
         //tln!("edges.len():{}", self.edges_.len());
         // Return a pointer to the new half-edge.
-        (new_edge1_id, new_edge2_id)
+        Ok((new_edge1_id, new_edge2_id))
     }
 
     /// Make sure the diagram is consistent. Removes degenerate edges, connects incident
     /// edges etc. etc
-    pub(crate) fn _build(&mut self) {
-        // Remove degenerate edges.
+    /// Removes the edge pairs selected by `is_degenerate` (called with the first edge of each
+    /// twin pair), merging vertex1 of the edge into vertex0. Then the incident edge pointers
+    /// are set up again and vertices without edges are removed.
+    fn remove_edges_<P>(&mut self, is_degenerate: P)
+    where
+        P: Fn(&Self, EdgeIndex) -> bool,
+    {
+        self.exterior_ = Default::default();
         #[cfg(feature = "console_debug")]
         self.debug_print_edges("b4 degenerate");
         if !self.edges_.is_empty() {
@@ -1708,21 +3970,7 @@ where
 
             //let mut edges_to_erase: Vec<usize> = Vec::new();
             while it < edges_end {
-                let is_equal = {
-                    let v1 = self.edge_get_vertex0_(Some(EdgeIndex(it)));
-                    let v1 = self.vertex_get_(v1);
-                    let v2 = self._edge_get_vertex1(Some(EdgeIndex(it)));
-                    let v2 = self.vertex_get_(v2);
-                    //tln!("looking at edge:{}, v1={:?}, v2={:?}", it, v1, v2);
-                    v1.is_some()
-                        && v2.is_some()
-                        && v1
-                            .unwrap()
-                            .get()
-                            .vertex_equality_predicate_eq(&v2.unwrap().get())
-                };
-
-                if is_equal {
+                if is_degenerate(self, EdgeIndex(it)) {
                     self._remove_edge(Some(EdgeIndex(it)));
                 } else {
                     if it != last_edge {
@@ -1767,6 +4015,16 @@ where
         tln!();
 
         // Set up incident edge pointers for cells and vertices.
+        for cell in self.cells_.iter() {
+            let mut c = cell.get();
+            c.incident_edge_ = None;
+            cell.set(c);
+        }
+        for vertex in self.vertices_.iter() {
+            let mut v = vertex.get();
+            v.incident_edge_ = None;
+            vertex.set(v);
+        }
         for edge_it in self.edge_iter().enumerate().map(|x| EdgeIndex(x.0)) {
             let cell = self.edge_get_cell_(Some(edge_it));
             if self.cell_get_incident_edge_(cell).is_none() {
@@ -1795,10 +4053,9 @@ where
             let mut last_vertex_iterator = (0..self.vertices_.len()).map(VertexIndex);
             let mut last_vertex = last_vertex_iterator.next();
             for it in (0..self.vertices_.len()).map(VertexIndex) {
-                let it = Some(it);
-                if self.vertex_get_incident_edge(it).is_some() {
-                    if it != last_vertex {
-                        self.vertex_copy_(last_vertex.unwrap().0, it.unwrap().0);
+                if self.vertex_get_incident_edge(Some(it)).is_some() {
+                    if Some(it) != last_vertex {
+                        self.vertex_copy_(last_vertex.unwrap().0, it.0);
                         let v = last_vertex;
                         let mut e = self.vertex_get_incident_edge(last_vertex);
                         loop {
@@ -1821,6 +4078,23 @@ where
             }
         }
         tln!("vertices after degenerate {}", self.vertices_.len());
+    }
+
+    pub(crate) fn _build(&mut self) {
+        // Remove degenerate edges.
+        self.remove_edges_(|diagram, edge_id| {
+            let v1 = diagram.edge_get_vertex0_(Some(edge_id));
+            let v1 = diagram.vertex_get_(v1);
+            let v2 = diagram._edge_get_vertex1(Some(edge_id));
+            let v2 = diagram.vertex_get_(v2);
+            //tln!("looking at edge:{}, v1={:?}, v2={:?}", it, v1, v2);
+            v1.is_some()
+                && v2.is_some()
+                && v1
+                    .unwrap()
+                    .get()
+                    .vertex_equality_predicate_eq(&v2.unwrap().get())
+        });
 
         // Set up next/prev pointers for infinite edges.
         if self.vertices_.is_empty() {
@@ -1884,6 +4158,88 @@ where
         }
     }
 
+    /// Removes all the secondary edges, see `Builder::drop_secondary_edges()`.
+    /// Every removed edge is unlinked from the edge loop of its own cell, i.e. the prev and
+    /// next edges are linked to each other. The remaining edges and vertices are renumbered,
+    /// and vertices without any remaining edges are removed.
+    pub(crate) fn remove_secondary_edges_(&mut self) {
+        self.exterior_ = Default::default();
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            if edge.is_primary() {
+                continue;
+            }
+            let prev = edge.prev_ccw_;
+            let next = edge.next_ccw_;
+            if prev != Some(edge.id_) {
+                self._edge_set_next(prev, next);
+                self._edge_set_prev(next, prev);
+            }
+        }
+
+        let mut new_edge_ids = vec![None; self.edges_.len()];
+        let mut num_edges = 0;
+        for edge in self.edges_.iter() {
+            if edge.get().is_primary() {
+                new_edge_ids[edge.get().id_.0] = Some(EdgeIndex(num_edges));
+                num_edges += 1;
+            }
+        }
+        let map_edge = |e: Option<EdgeIndex>| e.and_then(|e| new_edge_ids[e.0]);
+
+        let mut is_referenced = vec![false; self.vertices_.len()];
+        for edge in self.edges_.iter() {
+            let edge = edge.get();
+            if let (true, Some(v)) = (edge.is_primary(), edge.vertex_) {
+                is_referenced[v.0] = true;
+            }
+        }
+        let mut new_vertex_ids = vec![None; self.vertices_.len()];
+        let mut vertices = Vec::<VertexType<I, F>>::new();
+        for vertex in self.vertices_.iter() {
+            let mut v = vertex.get();
+            if is_referenced[v.id_.0] {
+                new_vertex_ids[v.id_.0] = Some(VertexIndex(vertices.len()));
+                v.id_ = VertexIndex(vertices.len());
+                v.incident_edge_ = None;
+                vertices.push(Rc::new(cell::Cell::new(v)));
+            }
+        }
+        let map_vertex = |v: Option<VertexIndex>| v.and_then(|v| new_vertex_ids[v.0]);
+
+        let mut edges = Vec::<EdgeType<I, F>>::with_capacity(num_edges);
+        for edge in self.edges_.iter() {
+            let mut e = edge.get();
+            if let Some(new_id) = new_edge_ids[e.id_.0] {
+                e.id_ = new_id;
+                e.twin_ = map_edge(e.twin_);
+                e.next_ccw_ = map_edge(e.next_ccw_);
+                e.prev_ccw_ = map_edge(e.prev_ccw_);
+                e.vertex_ = map_vertex(e.vertex_);
+                edges.push(Rc::new(cell::Cell::new(e)));
+            }
+        }
+        self.edges_ = edges;
+        self.vertices_ = vertices;
+
+        // Set up incident edge pointers for cells and vertices.
+        for cell in self.cells_.iter() {
+            let mut c = cell.get();
+            c.incident_edge_ = None;
+            cell.set(c);
+        }
+        for edge_it in (0..self.edges_.len()).map(EdgeIndex) {
+            let cell = self.edge_get_cell_(Some(edge_it));
+            if self.cell_get_incident_edge_(cell).is_none() {
+                self.cell_set_incident_edge_(cell, Some(edge_it));
+            }
+            let vertex = self.edge_get_vertex0_(Some(edge_it));
+            if self.vertex_get_incident_edge(vertex).is_none() {
+                self.vertex_set_incident_edge_(vertex, Some(edge_it));
+            }
+        }
+    }
+
     /// prints cells and vertices to the console
     /// edges will be printed if the 'edge_filter' returns true for that edge id.
     #[cfg(feature = "console_debug")]
@@ -1955,3 +4311,110 @@ where
         )
     }
 }
+
+/// Returns the squared euclidean distance from `position` to an input site, identified by
+/// its source index and category just like `Cell::source_index_2()` returns them.
+/// The distance to a segment site is the perpendicular distance if `position` projects onto
+/// the segment, and the distance to the nearest endpoint otherwise.
+/// `point_data` and `segment_data` must be the same input data, in the same order, as given
+/// to the builder. Panics if the source index is out of bounds.
+/// ```
+/// # use boostvoronoi::builder::{to_points, to_segments};
+/// # use boostvoronoi::diagram::{point_to_site_distance_sq, SourceCategory};
+/// let points = to_points::<i32, i32>(&[[0, 0]]);
+/// let segments = to_segments::<i32, i32>(&[[10, 0, 10, 10]]);
+/// let d = |p: [f64; 2], index, category| {
+///     point_to_site_distance_sq(p, index, category, &points, &segments)
+/// };
+/// assert_eq!(d([3.0, 4.0], 0, SourceCategory::SinglePoint), 25.0);
+/// assert_eq!(d([7.0, 5.0], 1, SourceCategory::Segment), 9.0);
+/// assert_eq!(d([7.0, 14.0], 1, SourceCategory::Segment), 25.0);
+/// assert_eq!(d([7.0, 14.0], 1, SourceCategory::SegmentStart), 205.0);
+/// ```
+pub fn point_to_site_distance_sq<I, F>(
+    position: [F; 2],
+    source_index: SourceIndex,
+    category: SourceCategory,
+    point_data: &[Point<I>],
+    segment_data: &[Line<I>],
+) -> F
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let site = match category {
+        SourceCategory::SinglePoint => SiteGeometry::Point(point_data[source_index]),
+        SourceCategory::SegmentStart => {
+            SiteGeometry::Point(segment_data[source_index - point_data.len()].start)
+        }
+        SourceCategory::SegmentEnd => {
+            SiteGeometry::Point(segment_data[source_index - point_data.len()].end)
+        }
+        SourceCategory::Segment => {
+            SiteGeometry::Segment(segment_data[source_index - point_data.len()])
+        }
+    };
+    site_distance_sq_::<I, F>(&site, position)
+}
+
+/// Returns the squared distance from `position` to the input geometry of a site.
+fn site_distance_sq_<I, F>(site: &SiteGeometry<I>, position: [F; 2]) -> F
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let i_to_f = TC2::<I, F>::i_to_f;
+    let (dx, dy) = match site {
+        SiteGeometry::Point(p) => (position[0] - i_to_f(p.x), position[1] - i_to_f(p.y)),
+        SiteGeometry::Segment(s) => {
+            let start = [i_to_f(s.start.x), i_to_f(s.start.y)];
+            let d = [i_to_f(s.end.x) - start[0], i_to_f(s.end.y) - start[1]];
+            let length_sq = d[0] * d[0] + d[1] * d[1];
+            let t = ((position[0] - start[0]) * d[0] + (position[1] - start[1]) * d[1]) / length_sq;
+            let t = t.max(F::zero()).min(F::one());
+            (
+                position[0] - start[0] - t * d[0],
+                position[1] - start[1] - t * d[1],
+            )
+        }
+    };
+    dx * dx + dy * dy
+}
+
+/// Returns the convex hull of the points in CCW order (Andrew's monotone chain).
+/// Collinear points are removed.
+fn convex_hull_<F: OutputType>(mut points: Vec<[F; 2]>) -> Vec<[F; 2]> {
+    points.sort_by(|a, b| {
+        a[0].partial_cmp(&b[0])
+            .unwrap_or(Ordering::Equal)
+            .then(a[1].partial_cmp(&b[1]).unwrap_or(Ordering::Equal))
+    });
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let cross = |o: [F; 2], a: [F; 2], b: [F; 2]| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+    let mut hull = Vec::<[F; 2]>::with_capacity(points.len() + 1);
+    // lower hull, then upper hull
+    for pass in 0..2 {
+        let start = hull.len();
+        let iter: Box<dyn Iterator<Item = &[F; 2]>> = if pass == 0 {
+            Box::new(points.iter())
+        } else {
+            Box::new(points.iter().rev())
+        };
+        for p in iter {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], *p) <= F::zero()
+            {
+                let _ = hull.pop();
+            }
+            hull.push(*p);
+        }
+        // the last point is the first point of the next chain
+        let _ = hull.pop();
+    }
+    hull
+}
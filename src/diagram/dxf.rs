@@ -0,0 +1,98 @@
+//! ASCII DXF export of the Voronoi skeleton, see `to_dxf()`.
+
+use super::export::{default_max_dist, edge_polyline};
+use super::Diagram;
+use crate::{BvError, InputType, OutputType};
+use alloc::string::String;
+use core::fmt::Write;
+use core::ops::Neg;
+
+/// Options for `to_dxf()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DxfOptions<F: OutputType> {
+    /// If set, the infinite edges are clipped to this rectangle, given as `(low, high)`
+    /// corners. If `None`, the infinite edges are skipped.
+    pub bounding_box: Option<([F; 2], [F; 2])>,
+    /// Export the secondary edges, i.e. the edges between a segment and its endpoints.
+    pub secondary_edges: bool,
+    /// The maximum allowed distance between a curved edge and its discretization.
+    /// If `None`, 0.1% of the largest side of the bounding box (or of the vertices) is used.
+    pub max_dist: Option<F>,
+    /// Put the primary edges on the layer `PRIMARY` and the secondary edges on the layer
+    /// `SECONDARY`. Otherwise every edge is put on the default layer `0`.
+    pub layers: bool,
+}
+
+impl<F: OutputType> Default for DxfOptions<F> {
+    fn default() -> Self {
+        Self {
+            bounding_box: None,
+            secondary_edges: true,
+            max_dist: None,
+            layers: false,
+        }
+    }
+}
+
+/// Returns the Voronoi skeleton as a minimal ASCII DXF document, i.e. only an `ENTITIES`
+/// section, which CAD programs like AutoCAD and LibreCAD import.
+///
+/// Every edge (one per twin pair) is written as a `LINE` entity if it is straight, and as a
+/// `POLYLINE` entity if it is curved. Curved edges are discretized, see
+/// `Diagram::edge_discretize()`. Infinite edges are clipped to `options.bounding_box`.
+/// ```
+/// # use boostvoronoi::builder::{to_points, Builder};
+/// # use boostvoronoi::diagram::{to_dxf, DxfOptions};
+/// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+/// let mut vb = Builder::<i32, f64>::default();
+/// vb.with_vertices(points.iter())?;
+/// let output = vb.build()?;
+/// let dxf = to_dxf(&output, &DxfOptions::default())?;
+/// // the four edges of the center cell
+/// assert_eq!(dxf.lines().filter(|l| *l == "LINE").count(), 4);
+/// assert!(dxf.ends_with("0\nEOF\n"));
+/// # Ok::<(), boostvoronoi::BvError>(())
+/// ```
+pub fn to_dxf<I, F>(diagram: &Diagram<I, F>, options: &DxfOptions<F>) -> Result<String, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let max_dist = options
+        .max_dist
+        .unwrap_or_else(|| default_max_dist(diagram, options.bounding_box));
+
+    let mut dxf = String::from("0\nSECTION\n2\nENTITIES\n");
+    for edge in diagram.edges().iter() {
+        let edge = edge.get();
+        let edge_id = edge.id();
+        if edge_id.0 > edge.twin()?.0 || (!options.secondary_edges && edge.is_secondary()) {
+            continue;
+        }
+        let polyline = match edge_polyline(diagram, edge_id, options.bounding_box, max_dist)? {
+            Some(polyline) => polyline,
+            None => continue,
+        };
+        let layer = match (options.layers, edge.is_primary()) {
+            (false, _) => "0",
+            (true, true) => "PRIMARY",
+            (true, false) => "SECONDARY",
+        };
+        if let [start, end] = polyline[..] {
+            let _ = write!(
+                dxf,
+                "0\nLINE\n8\n{}\n10\n{}\n20\n{}\n11\n{}\n21\n{}\n",
+                layer, start[0], start[1], end[0], end[1]
+            );
+        } else {
+            // group 66 tells that VERTEX entities follow, terminated by SEQEND
+            let _ = write!(dxf, "0\nPOLYLINE\n8\n{}\n66\n1\n10\n0\n20\n0\n", layer);
+            for p in polyline.iter() {
+                let _ = write!(dxf, "0\nVERTEX\n8\n{}\n10\n{}\n20\n{}\n", layer, p[0], p[1]);
+            }
+            let _ = write!(dxf, "0\nSEQEND\n8\n{}\n", layer);
+        }
+    }
+    dxf.push_str("0\nENDSEC\n0\nEOF\n");
+    Ok(dxf)
+}
@@ -0,0 +1,163 @@
+//! Helpers shared by the exporters: the default discretization distance, and the clipping
+//! of the infinite edges.
+
+use super::{Diagram, EdgeIndex, SiteGeometry};
+use crate::TypeConverter2 as TC2;
+use crate::{BvError, InputType, OutputType};
+use alloc::format;
+use alloc::vec::Vec;
+use core::ops::Neg;
+
+/// Returns 0.1% of the largest side of `bounding_box`, or of the vertices if `None`.
+/// Used as the default discretization distance of the exporters.
+pub(super) fn default_max_dist<I, F>(
+    diagram: &Diagram<I, F>,
+    bounding_box: Option<([F; 2], [F; 2])>,
+) -> F
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    match bounding_box.or_else(|| diagram.bounding_box()) {
+        Some((low, high)) => {
+            let size = (high[0] - low[0]).max(high[1] - low[1]);
+            if size > F::zero() {
+                size * TC2::<I, F>::f64_to_f(0.001)
+            } else {
+                F::one()
+            }
+        }
+        None => F::one(),
+    }
+}
+
+/// Returns the polyline of an edge: the discretization of a finite edge, or an infinite
+/// edge clipped to `bounding_box`. Returns None for infinite edges without a bounding box,
+/// or if the clipped edge is outside of it.
+pub(super) fn edge_polyline<I, F>(
+    diagram: &Diagram<I, F>,
+    edge_id: EdgeIndex,
+    bounding_box: Option<([F; 2], [F; 2])>,
+    max_dist: F,
+) -> Result<Option<Vec<[F; 2]>>, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    if diagram.edge_is_finite(edge_id)? {
+        Ok(Some(diagram.edge_discretize(edge_id, max_dist)?))
+    } else if let Some((low, high)) = bounding_box {
+        Ok(clip_infinite_edge(diagram, edge_id, low, high)?.map(|ray| ray.to_vec()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the part of an infinite edge that is located inside the `low`, `high` rectangle,
+/// or None if the edge does not cross the rectangle. An end that is not clipped is the exact
+/// position of its Voronoi vertex, so the exporters can match it with the vertex.
+pub(super) fn clip_infinite_edge<I, F>(
+    diagram: &Diagram<I, F>,
+    edge_id: EdgeIndex,
+    low: [F; 2],
+    high: [F; 2],
+) -> Result<Option<[[F; 2]; 2]>, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let (origin, direction) = infinite_edge_ray(diagram, edge_id)?;
+    // The edge is parameterized as origin + t * direction, t is limited by the vertices.
+    let (mut t0, mut t1) = (F::neg_infinity(), F::infinity());
+    // the vertex positions, as long as the ends are not clipped
+    let (mut start, mut end) = (None, None);
+    let (v0, v1) = diagram.edge_vertices(edge_id)?;
+    let length_sqr = direction[0] * direction[0] + direction[1] * direction[1];
+    let parameter = |p: [F; 2]| {
+        ((p[0] - origin[0]) * direction[0] + (p[1] - origin[1]) * direction[1]) / length_sqr
+    };
+    if let Some(v0) = v0 {
+        let position = diagram.vertex_position(v0)?;
+        t0 = parameter(position);
+        start = Some(position);
+    }
+    if let Some(v1) = v1 {
+        let position = diagram.vertex_position(v1)?;
+        t1 = parameter(position);
+        end = Some(position);
+    }
+    // Liang-Barsky clipping against the rectangle
+    for axis in 0..2 {
+        if direction[axis] == F::zero() {
+            if origin[axis] < low[axis] || origin[axis] > high[axis] {
+                return Ok(None);
+            }
+            continue;
+        }
+        let a = (low[axis] - origin[axis]) / direction[axis];
+        let b = (high[axis] - origin[axis]) / direction[axis];
+        if a.min(b) > t0 {
+            t0 = a.min(b);
+            start = None;
+        }
+        if a.max(b) < t1 {
+            t1 = a.max(b);
+            end = None;
+        }
+    }
+    if t0 > t1 {
+        return Ok(None);
+    }
+    let at = |t: F| [origin[0] + t * direction[0], origin[1] + t * direction[1]];
+    Ok(Some([
+        start.unwrap_or_else(|| at(t0)),
+        end.unwrap_or_else(|| at(t1)),
+    ]))
+}
+
+/// Returns a point on the line of an infinite edge and the direction of the edge, pointing
+/// from vertex0 towards vertex1. The direction is not normalized.
+pub(super) fn infinite_edge_ray<I, F>(
+    diagram: &Diagram<I, F>,
+    edge_id: EdgeIndex,
+) -> Result<([F; 2], [F; 2]), BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let i2f = TC2::<I, F>::i_to_f;
+    let half = TC2::<I, F>::f64_to_f(0.5);
+    // The edge has the site of its own cell to the left.
+    // Infinite edges are never formed by two segment sites.
+    Ok(match diagram.edge_bisected_geometry(edge_id)? {
+        (SiteGeometry::Point(p1), SiteGeometry::Point(p2)) => (
+            [
+                (i2f(p1.x) + i2f(p2.x)) * half,
+                (i2f(p1.y) + i2f(p2.y)) * half,
+            ],
+            [i2f(p1.y) - i2f(p2.y), i2f(p2.x) - i2f(p1.x)],
+        ),
+        (SiteGeometry::Point(p), SiteGeometry::Segment(s))
+        | (SiteGeometry::Segment(s), SiteGeometry::Point(p)) => {
+            let point_is_left = diagram
+                .get_cell(diagram.edge_get_cell(edge_id)?)?
+                .get()
+                .contains_point();
+            let (dx, dy) = (i2f(s.end.x) - i2f(s.start.x), i2f(s.end.y) - i2f(s.start.y));
+            let direction = if (p == s.start) ^ point_is_left {
+                [dy, -dx]
+            } else {
+                [-dy, dx]
+            };
+            ([i2f(p.x), i2f(p.y)], direction)
+        }
+        (SiteGeometry::Segment(_), SiteGeometry::Segment(_)) => {
+            return Err(BvError::InternalError(format!(
+                "The infinite edge with id:{} separates two segments. {}:{}",
+                edge_id.0,
+                file!(),
+                line!()
+            )))
+        }
+    })
+}
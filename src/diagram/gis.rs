@@ -0,0 +1,276 @@
+//! GeoJSON and WKT export of the diagram cells as polygons, see `to_geojson()` and `to_wkt()`.
+
+use super::export::infinite_edge_ray;
+use super::{CellIndex, Diagram, EdgeIndex, SourceCategory, SourceIndex};
+use crate::TypeConverter2 as TC2;
+use crate::{BvError, InputType, OutputType};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::ops::Neg;
+
+/// Returns the cells of the diagram as a GeoJSON `FeatureCollection`, together with the number
+/// of skipped cells.
+///
+/// Every cell becomes a `Feature` with a `Polygon` geometry and the `source_index` and
+/// `source_category` of the cell as properties. Curved edges are discretized, see
+/// `Diagram::edge_discretize()`. Infinite cells are clipped to `bounding_box`, given as
+/// `(low, high)` corners. If `bounding_box` is `None` the infinite cells are skipped, and
+/// counted in the returned number. Degenerate cells are never exported.
+/// ```
+/// # use boostvoronoi::builder::{to_points, Builder};
+/// # use boostvoronoi::diagram::to_geojson;
+/// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+/// let mut vb = Builder::<i32, f64>::default();
+/// vb.with_vertices(points.iter())?;
+/// let output = vb.build()?;
+/// let (geojson, skipped) = to_geojson(&output, None)?;
+/// // only the center point has a finite cell
+/// assert_eq!(skipped, 4);
+/// assert!(geojson.contains("\"source_index\":4"));
+/// let (_, skipped) = to_geojson(&output, Some(([-5.0, -5.0], [15.0, 15.0])))?;
+/// assert_eq!(skipped, 0);
+/// # Ok::<(), boostvoronoi::BvError>(())
+/// ```
+pub fn to_geojson<I, F>(
+    diagram: &Diagram<I, F>,
+    bounding_box: Option<([F; 2], [F; 2])>,
+) -> Result<(String, usize), BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let (polygons, skipped) = cell_polygons(diagram, bounding_box)?;
+    let mut geojson = String::from("{\"type\":\"FeatureCollection\",\"features\":[");
+    for (i, (source_index, source_category, polygon)) in polygons.iter().enumerate() {
+        if i > 0 {
+            geojson.push(',');
+        }
+        let _ = write!(
+            geojson,
+            "\n{{\"type\":\"Feature\",\"properties\":{{\"source_index\":{},\"source_category\":\"{:?}\"}},\
+             \"geometry\":{{\"type\":\"Polygon\",\"coordinates\":[[",
+            source_index, source_category
+        );
+        // the ring is closed by repeating the first point
+        for (j, p) in polygon.iter().chain(polygon.first()).enumerate() {
+            let _ = write!(
+                geojson,
+                "{}[{},{}]",
+                if j == 0 { "" } else { "," },
+                p[0],
+                p[1]
+            );
+        }
+        geojson.push_str("]]}}");
+    }
+    geojson.push_str("\n]}\n");
+    Ok((geojson, skipped))
+}
+
+/// Returns the cells of the diagram as WKT, one `POLYGON` per line in the order of the cells,
+/// together with the number of skipped cells. WKT has no properties, use `to_geojson()` if
+/// the source of each polygon is needed.
+/// See `to_geojson()` for how the cells are converted to polygons.
+pub fn to_wkt<I, F>(
+    diagram: &Diagram<I, F>,
+    bounding_box: Option<([F; 2], [F; 2])>,
+) -> Result<(String, usize), BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let (polygons, skipped) = cell_polygons(diagram, bounding_box)?;
+    let mut wkt = String::new();
+    for (_, _, polygon) in polygons.iter() {
+        wkt.push_str("POLYGON ((");
+        for (j, p) in polygon.iter().chain(polygon.first()).enumerate() {
+            let _ = write!(wkt, "{}{} {}", if j == 0 { "" } else { ", " }, p[0], p[1]);
+        }
+        wkt.push_str("))\n");
+    }
+    Ok((wkt, skipped))
+}
+
+/// Returns the CCW boundary of every non-degenerate cell, infinite cells are clipped to the
+/// bounding box. Also returns the number of infinite cells that were skipped because there
+/// is no bounding box.
+#[allow(clippy::type_complexity)]
+fn cell_polygons<I, F>(
+    diagram: &Diagram<I, F>,
+    bounding_box: Option<([F; 2], [F; 2])>,
+) -> Result<(Vec<(SourceIndex, SourceCategory, Vec<[F; 2]>)>, usize), BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let size = match bounding_box.or_else(|| diagram.bounding_box()) {
+        Some((low, high)) => (high[0] - low[0]).max(high[1] - low[1]),
+        None => F::zero(),
+    };
+    let tolerance = if size > F::zero() {
+        size * TC2::<I, F>::f64_to_f(0.001)
+    } else {
+        F::one()
+    };
+    // Every vertex and every cut off point of an infinite edge must be far away from the
+    // bounding box, or the straight lines connecting them could cross it.
+    let far_circle = bounding_box.map(|(low, high)| {
+        let half = TC2::<I, F>::f64_to_f(0.5);
+        let center = [(low[0] + high[0]) * half, (low[1] + high[1]) * half];
+        let mut radius = (high[0] - low[0]).hypot(high[1] - low[1]);
+        if let Some((v_low, v_high)) = diagram.bounding_box() {
+            for corner in [v_low, v_high, [v_low[0], v_high[1]], [v_high[0], v_low[1]]] {
+                radius = radius.max((corner[0] - center[0]).hypot(corner[1] - center[1]));
+            }
+        }
+        (center, radius * TC2::<I, F>::f64_to_f(4.0))
+    });
+    let mut polygons = Vec::new();
+    let mut skipped = 0;
+    for cell in diagram.cells().iter() {
+        let cell = cell.get();
+        if cell.is_degenerate() {
+            continue;
+        }
+        let polygon = match diagram.cell_polygon_discretized(cell.id(), tolerance)? {
+            Some(polygon) => polygon,
+            None => match (bounding_box, far_circle) {
+                (Some((low, high)), Some((center, radius))) => {
+                    let polygon =
+                        infinite_cell_polygon(diagram, cell.id(), tolerance, center, radius)?;
+                    let polygon = clip_polygon(polygon, low, high);
+                    if polygon.len() < 3 {
+                        // the cell is located outside the bounding box
+                        continue;
+                    }
+                    polygon
+                }
+                _ => {
+                    skipped += 1;
+                    continue;
+                }
+            },
+        };
+        polygons.push((cell.source_index(), cell.source_category(), polygon));
+    }
+    Ok((polygons, skipped))
+}
+
+/// Returns the boundary of an infinite cell as a CCW polygon. The infinite edges are cut off
+/// at least `radius` away from `center` and connected by an arc of that radius.
+/// Curved edges are discretized with `tolerance` as the maximum allowed distance to the arc.
+fn infinite_cell_polygon<I, F>(
+    diagram: &Diagram<I, F>,
+    cell_id: CellIndex,
+    tolerance: F,
+    center: [F; 2],
+    radius: F,
+) -> Result<Vec<[F; 2]>, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let f = TC2::<I, F>::f64_to_f;
+    let distance = |p: [F; 2]| (p[0] - center[0]).hypot(p[1] - center[1]);
+    let unit_ray = |edge_id: EdgeIndex| -> Result<([F; 2], [F; 2]), BvError> {
+        let (origin, direction) = infinite_edge_ray(diagram, edge_id)?;
+        let length = direction[0].hypot(direction[1]);
+        Ok((origin, [direction[0] / length, direction[1] / length]))
+    };
+    // a point on the ray from `anchor`, at least `radius` away from the center
+    let far_point = |anchor: [F; 2], direction: [F; 2]| {
+        let t = distance(anchor) + radius;
+        [anchor[0] + t * direction[0], anchor[1] + t * direction[1]]
+    };
+
+    let edges: Vec<EdgeIndex> = diagram.cell_edge_iterator(cell_id).collect();
+    let mut polygon = Vec::<[F; 2]>::new();
+    for (i, edge_id) in edges.iter().enumerate() {
+        let (v0, v1) = diagram.edge_vertices(*edge_id)?;
+        let v0 = v0.map(|v| diagram.vertex_position(v)).transpose()?;
+        let v1 = v1.map(|v| diagram.vertex_position(v)).transpose()?;
+        if let Some(v0) = v0 {
+            if v1.is_some() && diagram.get_edge(*edge_id)?.get().is_curved() {
+                let polyline = diagram.edge_discretize(*edge_id, tolerance)?;
+                // the last point is the first point of the next edge
+                polygon.extend(polyline.iter().take(polyline.len() - 1));
+            } else {
+                polygon.push(v0);
+            }
+        }
+        if v0.is_some() && v1.is_some() {
+            continue;
+        }
+        let (origin, out_direction) = unit_ray(*edge_id)?;
+        if v0.is_none() {
+            let anchor = v1.unwrap_or(origin);
+            polygon.push(far_point(anchor, [-out_direction[0], -out_direction[1]]));
+        }
+        if v1.is_some() {
+            continue;
+        }
+        polygon.push(far_point(v0.unwrap_or(origin), out_direction));
+        // The edge leads to infinity, walk CCW around the center to where the next edge
+        // comes from.
+        let (_, in_direction) = unit_ray(edges[(i + 1) % edges.len()])?;
+        let angle_out = out_direction[1].atan2(out_direction[0]);
+        let angle_in = (-in_direction[1]).atan2(-in_direction[0]);
+        let two_pi = f(core::f64::consts::TAU);
+        let delta = {
+            let delta = (angle_in - angle_out) % two_pi;
+            if delta < F::zero() {
+                delta + two_pi
+            } else {
+                delta
+            }
+        };
+        let steps = (delta / f(core::f64::consts::FRAC_PI_4))
+            .ceil()
+            .max(F::one());
+        let mut step = F::zero();
+        while step <= steps {
+            let angle = angle_out + delta * step / steps;
+            polygon.push([
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]);
+            step = step + F::one();
+        }
+    }
+    Ok(polygon)
+}
+
+/// Sutherland-Hodgman clipping of a polygon against the `low`, `high` rectangle.
+fn clip_polygon<F: OutputType>(polygon: Vec<[F; 2]>, low: [F; 2], high: [F; 2]) -> Vec<[F; 2]> {
+    let mut polygon = polygon;
+    for axis in 0..2 {
+        for (limit, is_low) in [(low[axis], true), (high[axis], false)] {
+            let inside = |p: &[F; 2]| {
+                if is_low {
+                    p[axis] >= limit
+                } else {
+                    p[axis] <= limit
+                }
+            };
+            let mut clipped = Vec::with_capacity(polygon.len() + 4);
+            for (i, current) in polygon.iter().enumerate() {
+                let previous = &polygon[(i + polygon.len() - 1) % polygon.len()];
+                if inside(current) != inside(previous) {
+                    let t = (limit - previous[axis]) / (current[axis] - previous[axis]);
+                    let mut p = [
+                        previous[0] + t * (current[0] - previous[0]),
+                        previous[1] + t * (current[1] - previous[1]),
+                    ];
+                    p[axis] = limit;
+                    clipped.push(p);
+                }
+                if inside(current) {
+                    clipped.push(*current);
+                }
+            }
+            polygon = clipped;
+        }
+    }
+    polygon
+}
@@ -0,0 +1,107 @@
+//! Wavefront OBJ export of the Voronoi skeleton, see `to_obj()`.
+
+use super::export::{default_max_dist, edge_polyline};
+use super::Diagram;
+use crate::{BvError, InputType, OutputType};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::ops::Neg;
+
+/// Options for `to_obj()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjOptions<F: OutputType> {
+    /// If set, the infinite edges are clipped to this rectangle, given as `(low, high)`
+    /// corners. If `None`, the infinite edges are skipped.
+    pub bounding_box: Option<([F; 2], [F; 2])>,
+    /// Export the secondary edges, i.e. the edges between a segment and its endpoints.
+    pub secondary_edges: bool,
+    /// The maximum allowed distance between a curved edge and its discretization.
+    /// If `None`, 0.1% of the largest side of the bounding box (or of the vertices) is used.
+    pub max_dist: Option<F>,
+}
+
+impl<F: OutputType> Default for ObjOptions<F> {
+    fn default() -> Self {
+        Self {
+            bounding_box: None,
+            secondary_edges: true,
+            max_dist: None,
+        }
+    }
+}
+
+/// Returns the Voronoi skeleton as a Wavefront OBJ document, in the z=0 plane.
+///
+/// Every Voronoi vertex is written as a `v` line, the OBJ index of vertex `i` is `i + 1`.
+/// Every edge (one per twin pair) is written as an `l` polyline. Curved edges are discretized,
+/// see `Diagram::edge_discretize()`, and the interior points of the polyline get `v` lines of
+/// their own, written just before the `l` line. The same goes for the points where the
+/// infinite edges are clipped to `options.bounding_box`.
+/// ```
+/// # use boostvoronoi::builder::{to_points, Builder};
+/// # use boostvoronoi::diagram::{to_obj, ObjOptions};
+/// let points = to_points::<i32, i32>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+/// let mut vb = Builder::<i32, f64>::default();
+/// vb.with_vertices(points.iter())?;
+/// let output = vb.build()?;
+/// let obj = to_obj(&output, &ObjOptions::default())?;
+/// // the four vertices of the center cell, connected by four edges
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 4);
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("l ")).count(), 4);
+/// # Ok::<(), boostvoronoi::BvError>(())
+/// ```
+pub fn to_obj<I, F>(diagram: &Diagram<I, F>, options: &ObjOptions<F>) -> Result<String, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    let max_dist = options
+        .max_dist
+        .unwrap_or_else(|| default_max_dist(diagram, options.bounding_box));
+
+    let mut obj = String::from("# Voronoi skeleton\n");
+    for vertex in diagram.vertices().iter() {
+        let vertex = vertex.get();
+        let _ = writeln!(obj, "v {} {} 0", vertex.x(), vertex.y());
+    }
+    let mut num_obj_vertices = diagram.num_vertices();
+
+    for edge in diagram.edges().iter() {
+        let edge = edge.get();
+        let edge_id = edge.id();
+        if edge_id.0 > edge.twin()?.0 || (!options.secondary_edges && edge.is_secondary()) {
+            continue;
+        }
+        let (v0, v1) = diagram.edge_vertices(edge_id)?;
+        let polyline = match edge_polyline(diagram, edge_id, options.bounding_box, max_dist)? {
+            Some(polyline) => polyline,
+            None => continue,
+        };
+        // the polyline points located at a Voronoi vertex reuse the `v` line of the vertex
+        let mut indices = Vec::with_capacity(polyline.len());
+        for p in polyline.iter() {
+            let mut index = None;
+            for v in [v0, v1].iter().flatten() {
+                if diagram.vertex_position(*v)? == *p {
+                    index = Some(v.0 + 1);
+                }
+            }
+            let index = match index {
+                Some(index) => index,
+                None => {
+                    let _ = writeln!(obj, "v {} {} 0", p[0], p[1]);
+                    num_obj_vertices += 1;
+                    num_obj_vertices
+                }
+            };
+            indices.push(index);
+        }
+        obj.push('l');
+        for index in indices {
+            let _ = write!(obj, " {}", index);
+        }
+        obj.push('\n');
+    }
+    Ok(obj)
+}
@@ -0,0 +1,187 @@
+//! SVG export of a diagram, see `to_svg()`.
+
+use super::export::clip_infinite_edge;
+use super::{Diagram, SiteGeometry, SourceCategory};
+use crate::geometry::{Line, Point};
+use crate::visual_utils as VU;
+use crate::TypeConverter2 as TC2;
+use crate::{BvError, InputType, OutputType};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::ops::Neg;
+
+/// Drawing options for `to_svg()`. Colors can be any SVG color, e.g. `"red"` or `"#ff0000"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions<F: OutputType> {
+    /// The visible rectangle, as `(low, high)` corners in diagram coordinates. If `None`, the
+    /// bounding box of the input geometry and the Voronoi vertices grown by 10% is used.
+    pub bounding_box: Option<([F; 2], [F; 2])>,
+    /// If true the y axis points upwards, as it does in the diagram coordinate system.
+    pub flip_y: bool,
+    /// Draw the secondary edges, i.e. the edges between a segment and its endpoints.
+    pub secondary_edges: bool,
+    /// The radius of the input points and the Voronoi vertices, in diagram coordinates.
+    /// If `None`, 0.5% of the largest side of the bounding box is used.
+    pub point_radius: Option<F>,
+    /// The fill color of the input points, `"red"` by default.
+    pub point_color: String,
+    /// The stroke color of the input segments, `"red"` by default.
+    pub segment_color: String,
+    /// The fill color of the Voronoi vertices, `"green"` by default.
+    pub vertex_color: String,
+    /// The stroke color of the Voronoi edges, `"blue"` by default.
+    pub edge_color: String,
+}
+
+impl<F: OutputType> Default for SvgOptions<F> {
+    fn default() -> Self {
+        Self {
+            bounding_box: None,
+            flip_y: true,
+            secondary_edges: true,
+            point_radius: None,
+            point_color: "red".to_string(),
+            segment_color: "red".to_string(),
+            vertex_color: "green".to_string(),
+            edge_color: "blue".to_string(),
+        }
+    }
+}
+
+/// Returns the diagram as an SVG document.
+///
+/// Input points are drawn as circles, input segments as lines, Voronoi vertices as dots and
+/// Voronoi edges as paths. Curved edges are discretized, see `Diagram::edge_discretize()`.
+/// Infinite edges are clipped to the bounding box, so that they show up as rays.
+/// ```
+/// # use boostvoronoi::builder::{to_points, to_segments, Builder};
+/// # use boostvoronoi::diagram::{to_svg, SvgOptions};
+/// let points = to_points::<i32, i32>(&[[5, 5]]);
+/// let segments = to_segments::<i32, i32>(&[[0, 0, 10, 0]]);
+/// let mut vb = Builder::<i32, f64>::default();
+/// vb.with_vertices(points.iter())?;
+/// vb.with_segments(segments.iter())?;
+/// let output = vb.build()?;
+/// let svg = to_svg(&output, &SvgOptions::default())?;
+/// assert!(svg.starts_with("<svg"));
+/// # Ok::<(), boostvoronoi::BvError>(())
+/// ```
+pub fn to_svg<I, F>(diagram: &Diagram<I, F>, options: &SvgOptions<F>) -> Result<String, BvError>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    // the input geometry, the endpoints of the segments are not drawn as points
+    let mut points = Vec::<Point<I>>::new();
+    let mut segments = Vec::<Line<I>>::new();
+    for (cell_id, site) in diagram.iter_cells_with_sites() {
+        match site {
+            SiteGeometry::Segment(segment) => segments.push(segment),
+            SiteGeometry::Point(point)
+                if diagram.get_cell(cell_id)?.get().source_category()
+                    == SourceCategory::SinglePoint =>
+            {
+                points.push(point)
+            }
+            SiteGeometry::Point(_) => (),
+        }
+    }
+    let (low, high) = match options.bounding_box {
+        Some(bounding_box) => bounding_box,
+        None => {
+            let mut aabb = VU::Aabb2::<I, F>::default();
+            points.iter().for_each(|p| aabb.update_point(p));
+            segments.iter().for_each(|s| aabb.update_line(s));
+            for vertex in diagram.vertices().iter() {
+                let vertex = vertex.get();
+                aabb.update_vertex(vertex.x(), vertex.y());
+            }
+            aabb.grow_percent(10);
+            match (aabb.get_low(), aabb.get_high()) {
+                (Some(low), Some(high)) => (low, high),
+                _ => ([F::zero(), F::zero()], [F::one(), F::one()]),
+            }
+        }
+    };
+    let size = (high[0] - low[0]).max(high[1] - low[1]);
+    let radius = options
+        .point_radius
+        .unwrap_or_else(|| size * TC2::<I, F>::f64_to_f(0.005));
+    let tolerance = size * TC2::<I, F>::f64_to_f(0.001);
+    let i2f = TC2::<I, F>::i_to_f;
+    let y = |y: F| if options.flip_y { -y } else { y };
+    let path = |polyline: &[[F; 2]]| {
+        let mut d = String::new();
+        for (i, p) in polyline.iter().enumerate() {
+            let _ = write!(d, "{}{} {} ", if i == 0 { 'M' } else { 'L' }, p[0], y(p[1]));
+        }
+        d.trim_end().to_string()
+    };
+
+    let view_y = if options.flip_y { -high[1] } else { low[1] };
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        low[0],
+        view_y,
+        high[0] - low[0],
+        high[1] - low[1]
+    );
+    let style = "fill=\"none\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"";
+
+    let _ = writeln!(svg, "<g stroke=\"{}\" {}>", options.edge_color, style);
+    for edge in diagram.edges().iter() {
+        let edge = edge.get();
+        let edge_id = edge.id();
+        if edge_id.0 > edge.twin()?.0 || (!options.secondary_edges && edge.is_secondary()) {
+            continue;
+        }
+        let polyline = if diagram.edge_is_finite(edge_id)? {
+            diagram.edge_discretize(edge_id, tolerance)?
+        } else if let Some(ray) = clip_infinite_edge(diagram, edge_id, low, high)? {
+            ray.to_vec()
+        } else {
+            continue;
+        };
+        let _ = writeln!(svg, "<path d=\"{}\"/>", path(&polyline));
+    }
+    let _ = writeln!(svg, "</g>");
+
+    let _ = writeln!(svg, "<g stroke=\"{}\" {}>", options.segment_color, style);
+    for s in segments.iter() {
+        let polyline = [
+            [i2f(s.start.x), i2f(s.start.y)],
+            [i2f(s.end.x), i2f(s.end.y)],
+        ];
+        let _ = writeln!(svg, "<path d=\"{}\"/>", path(&polyline));
+    }
+    let _ = writeln!(svg, "</g>");
+
+    let _ = writeln!(svg, "<g fill=\"{}\">", options.vertex_color);
+    for vertex in diagram.vertices().iter() {
+        let vertex = vertex.get();
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>",
+            vertex.x(),
+            y(vertex.y()),
+            radius
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+
+    let _ = writeln!(svg, "<g fill=\"{}\">", options.point_color);
+    for p in points.iter() {
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>",
+            i2f(p.x),
+            y(i2f(p.y)),
+            radius
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
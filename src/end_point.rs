@@ -13,8 +13,8 @@ use super::beach_line as VB;
 use super::geometry::Point;
 use super::predicate as VP;
 use super::InputType;
-use std::cmp::Ordering;
-use std::ops::Neg;
+use core::cmp::Ordering;
+use core::ops::Neg;
 
 ///
 /// This was declared as "typedef std::pair<point_type, beach_line_iterator> end_point_type" in C++
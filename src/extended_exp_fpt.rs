@@ -11,9 +11,9 @@
 
 //! Utilities for extended float. Supports 63 bit mantissa with 32 bit exponent.
 use super::extended_int as EI;
+use core::fmt;
+use core::ops;
 use num::{Float, NumCast};
-use std::fmt;
-use std::ops;
 
 /// Floating point type wrapper. Allows to extend exponent boundaries to the
 /// integer type range. This class does not handle division by zero, subnormal
@@ -10,21 +10,39 @@
 // Ported from C++ boost 1.76.0 to Rust in 2020/2021 by Eadf (github.com/eadf)
 
 //! Utilities for big integers. Supports next set of arithmetic operations: +, -, *.
+//!
+//! This is the only big integer type used by the exact (fallback) predicates, there is no
+//! dependency on a general purpose bignum such as `num::BigInt`. The exact formulas only need
+//! a few hundred bits, so the chunks are kept in a `SmallVec` and values up to 256 bits never
+//! allocate.
+//!
+//! The exact predicates (`ExactCircleFormationFunctor` and `robust_sqrt_expr`) use nothing but
+//! the following, a replacement must provide all of it without loss of precision:
+//! * construction from `i32` and `i64`, and `zero()`.
+//! * `+`, `-` and `*` between values (and references), `* i32` and unary `-`.
+//! * the sign tests `is_pos()`, `is_neg()` and `is_zero()`.
+//! * the conversion to `f64` (`d()`) and to `ExtendedExponentFpt<f64>` (`p()`, `e()`), which
+//!   may only round the result once. The exponent of the latter does not overflow like `f64`.
 
 use super::extended_exp_fpt as EX;
 #[allow(unused_imports)]
 use crate::{t, tln};
-use std::cmp;
-use std::fmt;
-use std::num::Wrapping;
-use std::ops;
+use core::cmp;
+use core::fmt;
+use core::num::Wrapping;
+use core::ops;
+
+/// The 32 bit chunks of an `ExtendedInt`, least significant first.
+/// Eight inline chunks cover nearly all the intermediate values of the exact circle formation
+/// formulas for 32 bit input, four chunks (128 bits) made the segment cases spill to the heap.
+type Chunks = smallvec::SmallVec<[Wrapping<u32>; 8]>;
 
 /// Stack allocated big integer class.
 /// Supports next set of arithmetic operations: +, -, *.
 /// Ported from voronoi_ctypes.hpp
 #[derive(Clone)]
 pub struct ExtendedInt {
-    chunks_: smallvec::SmallVec<[Wrapping<u32>; 4]>,
+    chunks_: Chunks,
     count_: i32,
 }
 
@@ -99,7 +117,7 @@ impl ExtendedInt {
     #[inline(always)]
     pub fn zero() -> Self {
         Self {
-            chunks_: smallvec::SmallVec::<[Wrapping<u32>; 4]>::default(),
+            chunks_: Chunks::default(),
             count_: 0,
         }
     }
@@ -112,11 +130,11 @@ impl ExtendedInt {
         match self.size() {
             0 => return rv,
             1 => {
-                rv.0 = num::cast::<u32, f64>(self.chunks_.get(0).unwrap().0).unwrap();
+                rv.0 = num::cast::<u32, f64>(self.chunks_.first().unwrap().0).unwrap();
             }
             2 => {
                 rv.0 = num::cast::<u32, f64>(self.chunks_.get(1).unwrap().0).unwrap() * sep
-                    + num::cast::<u32, f64>(self.chunks_.get(0).unwrap().0).unwrap();
+                    + num::cast::<u32, f64>(self.chunks_.first().unwrap().0).unwrap();
             }
             _ => {
                 for v in self.chunks_.iter().rev().take(3) {
@@ -418,7 +436,7 @@ impl ops::Add for ExtendedInt {
     }
 }
 
-impl<'a, 'b> ops::Add<&'b ExtendedInt> for &'a ExtendedInt {
+impl<'b> ops::Add<&'b ExtendedInt> for &ExtendedInt {
     type Output = ExtendedInt;
     /// Adds `self` to `that` returning a new object containing the result
     /// ```
@@ -478,7 +496,7 @@ impl ops::Sub for ExtendedInt {
     }
 }
 
-impl<'a, 'b> ops::Sub<&'b ExtendedInt> for &'a ExtendedInt {
+impl<'b> ops::Sub<&'b ExtendedInt> for &ExtendedInt {
     type Output = ExtendedInt;
     /// Subtracts `that` from `self` returning a new object containing the result
     /// ```
@@ -538,7 +556,7 @@ impl ops::Mul for ExtendedInt {
     }
 }
 
-impl<'a, 'b> ops::Mul<&'b ExtendedInt> for &'a ExtendedInt {
+impl<'b> ops::Mul<&'b ExtendedInt> for &ExtendedInt {
     type Output = ExtendedInt;
     /// Multiplies `self` with `that` returning a new object containing the result
     /// ```
@@ -578,7 +596,7 @@ impl<'b> ops::Mul<&'b ExtendedInt> for ExtendedInt {
     }
 }
 
-impl<'b> ops::Mul<i32> for ExtendedInt {
+impl ops::Mul<i32> for ExtendedInt {
     type Output = ExtendedInt;
     /// Multiplies `self` with `that` returning a new object containing the result
     /// ```
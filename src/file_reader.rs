@@ -13,9 +13,9 @@
 
 use crate::geometry;
 use crate::BvError;
+use core::ops::Neg;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::ops::Neg;
 use std::path::Path;
 
 #[derive(Debug)]
@@ -8,13 +8,14 @@
 use super::diagram::Vertex;
 use super::InputType;
 use crate::OutputType;
-use std::cmp;
-use std::fmt;
-use std::hash;
-use std::ops::Neg;
+use core::cmp;
+use core::fmt;
+use core::hash;
+use core::ops::Neg;
 
 /// A really simple 2d coordinate container type - integer only
 #[derive(Copy, Clone, cmp::PartialEq, cmp::Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point<T: InputType> {
     pub x: T,
     pub y: T,
@@ -282,6 +283,7 @@ where
 
 /// A really simple 2d line type - integer only
 #[derive(Copy, Clone, cmp::PartialEq, cmp::Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Line<T: InputType> {
     pub start: Point<T>,
     pub end: Point<T>,
@@ -27,19 +27,26 @@
     elided_lifetimes_in_paths
 )]
 #![cfg_attr(feature = "map_first_last", feature(map_first_last))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
 use core::fmt::Debug;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::ops::Neg;
 use extended_exp_fpt as EX;
 use extended_int as EI;
 use num::{Float, NumCast, PrimInt, Zero};
-use std::fmt;
-use std::hash::Hash;
-use std::marker::PhantomData;
-use std::ops::Neg;
 
 mod beach_line;
 pub mod builder;
 mod circle_event;
+mod cpp_map;
 mod ctypes;
 pub mod diagram;
 mod end_point;
@@ -47,12 +54,15 @@ mod end_point;
 pub mod extended_exp_fpt;
 // I'd prefer if this module could be pub (crate), but then the documentation examples would not work.
 pub mod extended_int;
+#[cfg(feature = "std")]
 pub mod file_reader;
 pub(crate) mod predicate;
+pub mod predicates;
 // I'd prefer if this module could be pub (crate), but then the documentation examples would not work.
 pub mod geometry;
+pub mod robust;
 pub mod robust_fpt;
-mod site_event;
+pub mod site_event;
 pub mod sync_diagram;
 pub mod visual_utils;
 
@@ -83,33 +93,90 @@ macro_rules! tln {
     });
 }
 
+/// The error type of the crate. The variants carrying a `String` describe the failing
+/// object, and it is included in the `Display` output.
 #[derive(thiserror::Error, Debug)]
 pub enum BvError {
-    #[error("error: Some error from cpp_map")]
+    #[error("error: the beach-line map failed: {source:?}")]
     ListError {
         #[from]
         source: cpp_map::MapError,
     },
-    #[error("error: Some error with object id")]
+    /// An index did not refer to an object of the diagram
+    #[error("error: invalid object id: {0}")]
     IdError(String),
-    #[error("error: Some error with a value")]
+    #[error("error: invalid value: {0}")]
     ValueError(String),
-    #[error("error: Some error with the beach-line")]
+    #[error("error: the beach-line is inconsistent: {0}")]
     BeachLineError(String),
     #[error("error: given value for the radius is less than 0.0.")]
     RadiusLessThanZero,
+    /// No longer returned, `Builder::with_vertices()` and `Builder::with_segments()` can be
+    /// called in any order.
     #[error("error: vertices should be added before segments")]
     VerticesGoesFirst(String),
-    #[error("error: Some error")]
+    /// A bug, or input the algorithm could not handle. Please report it together with the
+    /// input, see `Builder::dump_input()`.
+    #[error("error: internal error, please report it together with the input: {0}")]
     InternalError(String),
-    #[error("Suspected self-intersecting input data")]
-    SelfIntersecting(String),
-    #[error("Could not cast number")]
+    /// Input segments may only touch at their endpoints, split them at the intersection
+    /// point before adding them.
+    #[error(
+        "error: the input segments {seg_a} and {seg_b} intersect at {point:?}, \
+         split them at the intersection point"
+    )]
+    SelfIntersecting {
+        seg_a: usize,
+        seg_b: usize,
+        point: [f64; 2],
+    },
+    #[error("error: could not cast number: {0}")]
     NumberConversion(String),
+    /// Reported by `Diagram::check_invariants()`
+    #[error("error: the diagram is inconsistent: {0}")]
+    InvariantError(String),
+    /// See `Builder::max_safe_coordinate()`
+    #[error("error: {0}, scale the input down to fit")]
+    CoordinateOutOfRange(String),
+    #[error("error: degenerate input {kind:?} found at input indices {indices:?}")]
+    DegenerateInput {
+        kind: DegenerateKind,
+        indices: Vec<usize>,
+    },
+    #[cfg(feature = "std")]
     #[error(transparent)]
     BvError(#[from] std::io::Error),
 }
 
+/// The kind of degenerate input detected by a builder in strict mode, or reported as a
+/// warning. See `Builder::strict()` and `Builder::build_with_warnings()`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DegenerateKind {
+    /// A segment with identical endpoints, always rejected by `Builder::with_segments()`.
+    ZeroLengthSegment,
+    /// Two or more input points coincide, or an input point coincides with a segment endpoint.
+    CoincidentPoints,
+    /// Three point sites are collinear.
+    CollinearPoints,
+    /// Four or more sites are on the same circle, i.e. several circle events at one position.
+    CocircularSites,
+    /// The sweep-line started with several sites on the same vertical line.
+    VerticalCollinearSites,
+    /// A circle event was rejected because it fell outside of a vertical segment.
+    VerticalSegment,
+    /// An input point, or the endpoint of a segment, lies on the interior of a segment.
+    PointOnSegment,
+}
+
+/// A degenerate case the builder encountered and handled, see `Builder::build_with_warnings()`.
+/// `indices` are the source indices of the involved input sites, just like in
+/// `BvError::DegenerateInput`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DegeneracyWarning {
+    pub kind: DegenerateKind,
+    pub indices: Vec<usize>,
+}
+
 /// This is the integer input type of the algorithm. Typically i32 or i64.
 pub trait InputType:
     fmt::Display
@@ -148,6 +215,9 @@ impl<I> InputType for I where
 }
 
 /// This is the floating point output type of the algorithm. Typically f32 or f64.
+/// The calculations are always done with f64 (and extended precision when needed), the output
+/// type only affects the stored vertices. E.g. with f32 each vertex is the f64 result rounded
+/// to the nearest f32, which halves the memory of the vertices.
 pub trait OutputType:
     Float
     + PartialOrd
@@ -159,7 +229,7 @@ pub trait OutputType:
     + Default
     + Debug
     + Zero
-    + std::ops::MulAssign
+    + core::ops::MulAssign
     + Unpin
 {
 }
@@ -175,7 +245,7 @@ impl<F> OutputType for F where
         + Default
         + Debug
         + Zero
-        + std::ops::MulAssign
+        + core::ops::MulAssign
         + Unpin
         + Neg<Output = F>
 {
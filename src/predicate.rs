@@ -17,6 +17,7 @@ mod tests;
 use super::beach_line as VB;
 use super::circle_event as VC;
 use super::ctypes::UlpComparison;
+use super::diagram as VD;
 use super::extended_exp_fpt as EX;
 use super::extended_int as EI;
 use super::geometry::Point;
@@ -26,15 +27,15 @@ use super::TypeConverter1 as TC1;
 use super::TypeConverter2 as TC2;
 use super::{InputType, OutputType};
 use crate::{t, tln};
-use num::{Float, NumCast, PrimInt, Zero};
-use std::cmp;
-use std::fmt::{Debug, Display};
-use std::marker::PhantomData;
-use std::ops::Neg;
+use core::cmp;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Neg;
+use core::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use num::Zero;
 
-// TODO: how to make these generic?
-const ULPS: u64 = 64;
-const ULPSX2: u64 = 64; // Todo: This is what c++ boost uses. Find a fix for this
+/// The default robustness threshold, see `Builder::with_ulps()`. This is what C++ Boost uses.
+pub(crate) const DEFAULT_ULPS: u64 = 64;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum SiteIndex {
@@ -77,11 +78,16 @@ where
     I: InputType + Neg<Output = I>,
     F: OutputType + Neg<Output = F>,
 {
+    /// Returns true if the site is a vertical segment (or a point).
+    /// The result does not depend on the order of point0 and point1, so an inverse()'ed site
+    /// is classified just like the original site.
     #[inline(always)]
     pub(crate) fn is_vertical_1(site: &VSE::SiteEvent<I, F>) -> bool {
         Self::is_vertical_2(site.point0(), site.point1())
     }
 
+    /// Returns true if the two points share the same x coordinate.
+    /// The comparison is symmetric, the order of the arguments does not matter.
     #[inline(always)]
     pub(crate) fn is_vertical_2(point1: &Point<I>, point2: &Point<I>) -> bool {
         point1.x == point2.x
@@ -90,19 +96,15 @@ where
     /// Compute robust cross_product: a1 * b2 - b1 * a2.
     /// It was mathematically proven that the result is correct
     /// with epsilon relative error equal to 1EPS.
-    /// TODO: this is supposed to use u32 if I==i32
+    /// If I is 32 bits (or less) the arguments are differences of two input coordinates, and
+    /// their magnitudes will fit in u32. The u32 path can't overflow for such input.
+    /// Wider input types, e.g. i64, are calculated with i128.
     #[inline(always)]
     pub(crate) fn robust_cross_product(a1: i64, b1: i64, a2: i64, b2: i64) -> f64 {
-        robust_cross_product_f::<i64, f64>(a1, b1, a2, b2)
-    }
-
-    #[inline(always)]
-    pub(crate) fn ulps() -> u64 {
-        // todo figure out how to cache this
-        if std::mem::size_of::<f64>() > 4 {
-            ULPSX2
+        if size_of::<I>() <= size_of::<u32>() {
+            robust_cross_product_u32(a1, b1, a2, b2)
         } else {
-            ULPS
+            robust_cross_product_i128(a1, b1, a2, b2)
         }
     }
 }
@@ -110,65 +112,68 @@ where
 /// Compute robust cross_product: a1 * b2 - b1 * a2.
 /// It was mathematically proven that the result is correct
 /// with epsilon relative error equal to 1EPS.
+/// The magnitude of every argument must fit in an u32, e.g. the difference of two i32 values.
+/// The products are calculated as u64 and the sums as u128, so nothing can overflow.
 #[inline]
-fn robust_cross_product_f<T, U>(s_a1: T, s_b1: T, s_a2: T, s_b2: T) -> U
-where
-    T: PrimInt
-        + PartialOrd
-        + PartialEq
-        + NumCast
-        + Copy
-        + Clone
-        + Display
-        + Default
-        + Debug
-        + Zero
-        + Neg<Output = T>,
-    U: Float
-        + PartialOrd
-        + PartialEq
-        + NumCast
-        + Copy
-        + Clone
-        + Display
-        + Default
-        + Debug
-        + Zero
-        + Neg<Output = U>,
-{
-    // Why can't *all* integers implement is_negative()? E.g u64 would just always return false.
-    // It would make it easier to implement generic code
-    let u_a1 = if s_a1 < T::zero() { -s_a1 } else { s_a1 };
-    let u_b1 = if s_b1 < T::zero() { -s_b1 } else { s_b1 };
-    let u_a2 = if s_a2 < T::zero() { -s_a2 } else { s_a2 };
-    let u_b2 = if s_b2 < T::zero() { -s_b2 } else { s_b2 };
+fn robust_cross_product_u32(s_a1: i64, s_b1: i64, s_a2: i64, s_b2: i64) -> f64 {
+    #[cfg(feature = "console_debug")]
+    {
+        assert!(s_a1.unsigned_abs() <= u32::MAX as u64);
+        assert!(s_b1.unsigned_abs() <= u32::MAX as u64);
+        assert!(s_a2.unsigned_abs() <= u32::MAX as u64);
+        assert!(s_b2.unsigned_abs() <= u32::MAX as u64);
+    }
+    let u_a1 = s_a1.unsigned_abs() as u32 as u64;
+    let u_b1 = s_b1.unsigned_abs() as u32 as u64;
+    let u_a2 = s_a2.unsigned_abs() as u32 as u64;
+    let u_b2 = s_b2.unsigned_abs() as u32 as u64;
 
     let l = u_a1 * u_b2;
     let r = u_b1 * u_a2;
 
-    if (s_a1 < T::zero()) ^ (s_b2 < T::zero()) {
-        return if (s_a2 < T::zero()) ^ (s_b1 < T::zero()) {
+    if (s_a1 < 0) ^ (s_b2 < 0) {
+        return if (s_a2 < 0) ^ (s_b1 < 0) {
             if l > r {
-                -num::cast::<T, U>(l - r).unwrap()
+                -((l - r) as f64)
             } else {
-                num::cast::<T, U>(r - l).unwrap()
+                (r - l) as f64
             }
         } else {
-            -num::cast::<T, U>(l + r).unwrap()
+            -((l as u128 + r as u128) as f64)
         };
     }
-    if (s_a2 < T::zero()) ^ (s_b1 < T::zero()) {
-        return num::cast::<T, U>(l + r).unwrap();
+    if (s_a2 < 0) ^ (s_b1 < 0) {
+        return (l as u128 + r as u128) as f64;
     }
     if l < r {
-        -num::cast::<T, U>(r - l).unwrap()
+        -((r - l) as f64)
     } else {
-        num::cast::<T, U>(l - r).unwrap()
+        (l - r) as f64
     }
 }
 
+/// Compute robust cross_product: a1 * b2 - b1 * a2.
+/// The arguments are differences of two input coordinates within
+/// ±`Builder::max_safe_coordinate()`, so their magnitudes fit in 54 bits. The products and
+/// their difference are calculated exactly as i128, and only the result is rounded.
+#[inline]
+fn robust_cross_product_i128(a1: i64, b1: i64, a2: i64, b2: i64) -> f64 {
+    (a1 as i128 * b2 as i128 - b1 as i128 * a2 as i128) as f64
+}
+
+/// The outcome of CircleFormationFunctor::circle_formation_predicate()
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum CircleFormation {
+    Formed,
+    NotFormed,
+    /// Three collinear point sites
+    Collinear,
+    /// The circle event lies outside of a vertical segment site
+    OutsideVerticalSegment,
+}
+
 #[derive(Debug, PartialEq, Eq)]
-enum Orientation {
+pub(crate) enum Orientation {
     Right,     // = -1,
     Collinear, // = 0,
     Left,      // = 1
@@ -205,7 +210,7 @@ where
     }
 
     #[inline(always)]
-    fn eval_p(point1: &Point<I>, point2: &Point<I>, point3: &Point<I>) -> Orientation {
+    pub(crate) fn eval_p(point1: &Point<I>, point2: &Point<I>, point3: &Point<I>) -> Orientation {
         let dx1: i64 = TC1::<I>::i_to_i64(point1.x) - TC1::<I>::i_to_i64(point2.x);
         let dx2: i64 = TC1::<I>::i_to_i64(point2.x) - TC1::<I>::i_to_i64(point3.x);
         let dy1: i64 = TC1::<I>::i_to_i64(point1.y) - TC1::<I>::i_to_i64(point2.y);
@@ -292,8 +297,8 @@ where
             if lhs.y0() != rhs.y0() {
                 return lhs.y0() < rhs.y0();
             }
-            return OrientationTest::<I, F>::eval_p(lhs.point1(), lhs.point0(), rhs.point1())
-                == Orientation::Left;
+            OrientationTest::<I, F>::eval_p(lhs.point1(), lhs.point0(), rhs.point1())
+                == Orientation::Left
         }
     }
 
@@ -313,20 +318,24 @@ where
         }
         if Self::event_comparison_predicate_bii(lhs, rhs) {
             cmp::Ordering::Less
-        } else {
+        } else if Self::event_comparison_predicate_bii(rhs, lhs) {
             cmp::Ordering::Greater
+        } else {
+            // e.g. identical point sites, `sort_by()` requires a consistent total order
+            cmp::Ordering::Equal
         }
     }
 
     /// boolean predicate between site and circle (integer<->float)
+    /// `ulps` is the robustness threshold, see `Builder::with_ulps()`.
     #[allow(clippy::let_and_return)]
     pub(crate) fn event_comparison_predicate_bif(
         lhs: &VSE::SiteEvent<I, F>,
         rhs: &VC::CircleEvent,
+        ulps: u64,
     ) -> bool {
         let lhs = TC1::<I>::i_to_f64(lhs.x0());
         let rhs = rhs.lower_x().into_inner();
-        let ulps = Predicates::<I, F>::ulps();
         let rv = UlpComparison::ulp_comparison(lhs, rhs, ulps) == cmp::Ordering::Less;
         tln!(
             "event_comparison_predicate_bif lhs:{:.12} rhs:{:.12} -> {}",
@@ -342,8 +351,9 @@ where
     pub(crate) fn event_comparison_predicate_if(
         lhs: &VSE::SiteEvent<I, F>,
         rhs: &VC::CircleEvent,
+        ulps: u64,
     ) -> cmp::Ordering {
-        if Self::event_comparison_predicate_bif(lhs, rhs) {
+        if Self::event_comparison_predicate_bif(lhs, rhs, ulps) {
             cmp::Ordering::Less
         } else {
             cmp::Ordering::Greater
@@ -514,7 +524,7 @@ where
             let segment1: &Point<I> = site.point1();
             let a1: f64 = i_to_f64(segment1.x) - i_to_f64(segment0.x);
             let b1: f64 = i_to_f64(segment1.y) - i_to_f64(segment0.y);
-            let mut k: f64 = (a1 * a1 + b1 * b1).sqrt();
+            let mut k: f64 = libm::sqrt(a1 * a1 + b1 * b1);
             // Avoid subtraction while computing k.
             #[allow(clippy::suspicious_operation_groupings)]
             if !b1.is_sign_negative() {
@@ -743,7 +753,7 @@ where
             }
             return (node.left_site().y1(), 1);
         }
-        return (node.right_site().y0(), -1);
+        (node.right_site().y0(), -1)
     }
 }
 
@@ -763,16 +773,6 @@ where
     I: InputType + Neg<Output = I>,
     F: OutputType + Neg<Output = F>,
 {
-    #[inline(always)]
-    pub(crate) fn ppp(
-        site1: &VSE::SiteEvent<I, F>,
-        site2: &VSE::SiteEvent<I, F>,
-        site3: &VSE::SiteEvent<I, F>,
-    ) -> bool {
-        OrientationTest::<I, F>::eval_p(site1.point0(), site2.point0(), site3.point0())
-            == Orientation::Right
-    }
-
     #[cfg(all(feature = "geo", feature = "ce_corruption_check"))]
     #[inline(always)]
     pub(crate) fn validate_circle_formation_predicate(
@@ -880,6 +880,44 @@ where
     }
 }
 
+/// The settings of the circle event predicates during one build, see `Builder::with_ulps()`
+/// and `Builder::exactness()`. Also counts the circle events recomputed with exact arithmetic.
+/// The counter is atomic, so that the `Builder` holding the settings stays `Sync`.
+#[derive(Debug)]
+pub(crate) struct PredicateSettings {
+    // the maximum error, in units in the last place, of the lazy evaluation
+    ulps_: u64,
+    // never recompute the circle events with exact arithmetic
+    never_recompute_: bool,
+    // always recompute the circle events with exact arithmetic, see compute_both()
+    #[cfg(test)]
+    force_exact_: bool,
+    exact_recomputations_: AtomicUsize,
+}
+
+impl Default for PredicateSettings {
+    fn default() -> Self {
+        Self::new(DEFAULT_ULPS, false)
+    }
+}
+
+impl PredicateSettings {
+    pub(crate) fn new(ulps: u64, never_recompute: bool) -> Self {
+        Self {
+            ulps_: ulps,
+            never_recompute_: never_recompute,
+            #[cfg(test)]
+            force_exact_: false,
+            exact_recomputations_: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of circle events recomputed with exact arithmetic.
+    pub(crate) fn exact_recomputations(&self) -> usize {
+        self.exact_recomputations_.load(AtomicOrdering::Relaxed)
+    }
+}
+
 #[derive(Default)]
 pub struct LazyCircleFormationFunctor<I, F>
 where
@@ -898,8 +936,65 @@ where
     I: InputType + Neg<Output = I>,
     F: OutputType + Neg<Output = F>,
 {
+    /// Overrides the decision to recompute the values with `ExactCircleFormationFunctor`
+    /// according to the settings, and counts the recomputations.
+    fn override_recompute_(
+        settings: &PredicateSettings,
+        c_x: bool,
+        c_y: bool,
+        lower_x: bool,
+    ) -> (bool, bool, bool) {
+        #[cfg(test)]
+        if settings.force_exact_ {
+            return (true, true, true);
+        }
+        if settings.never_recompute_ {
+            return (false, false, false);
+        }
+        if c_x || c_y || lower_x {
+            let _ = settings
+                .exact_recomputations_
+                .fetch_add(1, AtomicOrdering::Relaxed);
+        }
+        (c_x, c_y, lower_x)
+    }
+
+    /// Evaluates the circle event of the three sites twice: once as usual, and once with every
+    /// value recomputed by `ExactCircleFormationFunctor`.
+    /// Returns the `[x, y, lower_x]` of the (lazy, exact) circles, or None if no circle is formed.
+    #[cfg(test)]
+    pub(crate) fn compute_both(
+        site1: &VSE::SiteEvent<I, F>,
+        site2: &VSE::SiteEvent<I, F>,
+        site3: &VSE::SiteEvent<I, F>,
+    ) -> Option<([f64; 3], [f64; 3])> {
+        let compute = |exact: bool| {
+            let settings = PredicateSettings {
+                force_exact_: exact,
+                ..PredicateSettings::default()
+            };
+            let circle = VC::CircleEventC::new_1(VC::CircleEvent::new_1(VB::BeachLineIndex(0)));
+            let formation = CircleFormationFunctor::<I, F>::circle_formation_predicate(
+                site1, site2, site3, &circle, &settings,
+            );
+            let c = circle.0.get();
+            if formation == CircleFormation::Formed {
+                Some([c.raw_x(), c.raw_y(), c.lower_x().into_inner()])
+            } else {
+                None
+            }
+        };
+        Some((compute(false)?, compute(true)?))
+    }
+
     /// Lazy evaluation of point, point, point circle events
-    fn ppp(point1: &Point<I>, point2: &Point<I>, point3: &Point<I>, c_event: &VC::CircleEventType) {
+    fn ppp(
+        point1: &Point<I>,
+        point2: &Point<I>,
+        point3: &Point<I>,
+        c_event: &VC::CircleEventType,
+        settings: &PredicateSettings,
+    ) {
         let i_to_f64 = TC1::<I>::i_to_f64;
         let i_to_i64 = TC1::<I>::i_to_i64;
 
@@ -936,10 +1031,11 @@ where
         c_y -= RF::RobustFpt::new_2(dif_y1 * sum_y1 * dif_x2, error);
         let mut lower_x = RF::RobustDif::new_from(c_x);
         lower_x -= RF::RobustFpt::new_2(
-            ((dif_x1 * dif_x1 + dif_y1 * dif_y1)
-                * (dif_x2 * dif_x2 + dif_y2 * dif_y2)
-                * (dif_x3 * dif_x3 + dif_y3 * dif_y3))
-                .sqrt(),
+            libm::sqrt(
+                (dif_x1 * dif_x1 + dif_y1 * dif_y1)
+                    * (dif_x2 * dif_x2 + dif_y2 * dif_y2)
+                    * (dif_x3 * dif_x3 + dif_y3 * dif_y3),
+            ),
             num::cast::<f32, f64>(5.0f32).unwrap(),
         );
 
@@ -948,10 +1044,9 @@ where
             c_y.dif().fpv() * inv_orientation.fpv(),
             lower_x.dif().fpv() * inv_orientation.fpv(),
         );
-        let ulps = Predicates::<I, F>::ulps() as f64;
-        let recompute_c_x = c_x.dif().ulp() > ulps;
-        let recompute_c_y = c_y.dif().ulp() > ulps;
-        let recompute_lower_x = lower_x.dif().ulp() > ulps;
+        let recompute_c_x = c_x.dif().ulp() > settings.ulps_ as f64;
+        let recompute_c_y = c_y.dif().ulp() > settings.ulps_ as f64;
+        let recompute_lower_x = lower_x.dif().ulp() > settings.ulps_ as f64;
         #[cfg(feature = "console_debug")]
         {
             assert!(!c_x.dif().ulp().is_nan());
@@ -959,6 +1054,8 @@ where
             assert!(!lower_x.dif().ulp().is_nan());
         }
 
+        let (recompute_c_x, recompute_c_y, recompute_lower_x) =
+            Self::override_recompute_(settings, recompute_c_x, recompute_c_y, recompute_lower_x);
         if recompute_c_x || recompute_c_y || recompute_lower_x {
             ExactCircleFormationFunctor::<I, F>::ppp(
                 point1,
@@ -981,6 +1078,7 @@ where
         site3: &VSE::SiteEvent<I, F>,
         segment_index: SiteIndex,
         c_event: &VC::CircleEventType,
+        settings: &PredicateSettings,
     ) {
         let i_to_f64 = TC1::<I>::i_to_f64;
         let i_to_i64 = TC1::<I>::i_to_i64;
@@ -1033,7 +1131,7 @@ where
             1_f64,
         );
         let inv_segm_len =
-            RF::RobustFpt::new_2(1_f64 / (line_a * line_a + line_b * line_b).sqrt(), 3_f64);
+            RF::RobustFpt::new_2(1_f64 / libm::sqrt(line_a * line_a + line_b * line_b), 3_f64);
         let mut t = RF::RobustDif::default();
         tln!("0t:{:?}", t);
         if OrientationTest::<I, F>::eval_f(denom.fpv()) == Orientation::Collinear {
@@ -1104,10 +1202,9 @@ where
 
         tln!("  c_x:{:?}, c_y:{:?}, l_x:{:?}", c_x, c_y, lower_x);
 
-        let ulps = Predicates::<I, F>::ulps() as f64;
-        let recompute_c_x = c_x.dif().ulp() > ulps;
-        let recompute_c_y = c_y.dif().ulp() > ulps;
-        let recompute_lower_x = lower_x.dif().ulp() > ulps;
+        let recompute_c_x = c_x.dif().ulp() > settings.ulps_ as f64;
+        let recompute_c_y = c_y.dif().ulp() > settings.ulps_ as f64;
+        let recompute_lower_x = lower_x.dif().ulp() > settings.ulps_ as f64;
         tln!(
             "  recompute_c_x:{}, recompute_c_y:{}, recompute_lower_x:{}",
             recompute_c_x,
@@ -1122,6 +1219,8 @@ where
             assert!(!lower_x.dif().ulp().is_nan());
         }
 
+        let (recompute_c_x, recompute_c_y, recompute_lower_x) =
+            Self::override_recompute_(settings, recompute_c_x, recompute_c_y, recompute_lower_x);
         if recompute_c_x || recompute_c_y || recompute_lower_x {
             ExactCircleFormationFunctor::<I, F>::pps(
                 site1,
@@ -1202,32 +1301,22 @@ where
                         site3.point1().distance_to(&c_event.0.get())
                     );
                 }
-                match segment_index {
-                    SiteIndex::One => {
-                        LazyCircleFormationFunctor::<I, F>::ppp(
-                            site_3_point,
-                            site1.point0(),
-                            site2.point0(),
-                            c_event,
-                        );
-                    }
-                    SiteIndex::Two => {
-                        LazyCircleFormationFunctor::<I, F>::ppp(
-                            site1.point0(),
-                            site_3_point,
-                            site2.point0(),
-                            c_event,
-                        );
-                    }
-                    SiteIndex::Three => {
-                        LazyCircleFormationFunctor::<I, F>::ppp(
-                            site1.point0(),
-                            site2.point0(),
-                            site_3_point,
-                            c_event,
-                        );
-                    }
+                let (point1, point2, point3) = match segment_index {
+                    SiteIndex::One => (site_3_point, site1.point0(), site2.point0()),
+                    SiteIndex::Two => (site1.point0(), site_3_point, site2.point0()),
+                    SiteIndex::Three => (site1.point0(), site2.point0(), site_3_point),
                 };
+                // ppp expects the points in clockwise order, else lower_x ends up on the
+                // wrong side of the circle
+                if OrientationTest::<I, F>::eval_p(point1, point2, point3) == Orientation::Left {
+                    LazyCircleFormationFunctor::<I, F>::ppp(
+                        point1, point3, point2, c_event, settings,
+                    );
+                } else {
+                    LazyCircleFormationFunctor::<I, F>::ppp(
+                        point1, point2, point3, c_event, settings,
+                    );
+                }
                 #[cfg(feature = "ce_corruption_check")]
                 {
                     println!("//c after ppp");
@@ -1264,6 +1353,7 @@ where
         site3: &VSE::SiteEvent<I, F>,
         point_index: SiteIndex,
         c_event: &VC::CircleEventType,
+        settings: &PredicateSettings,
     ) {
         let i_to_f64 = TC1::<I>::i_to_f64;
         let i_to_i64 = TC1::<I>::i_to_i64;
@@ -1387,15 +1477,14 @@ where
             } else {
                 lower_x += RF::RobustFpt::new_1(0.5) * c / a.sqrt();
             }
-            let ulps = Predicates::<I, F>::ulps() as f64;
-            recompute_c_x = c_x.dif().ulp() > ulps;
-            recompute_c_y = c_y.dif().ulp() > ulps;
-            recompute_lower_x = lower_x.dif().ulp() > ulps;
+            recompute_c_x = c_x.dif().ulp() > settings.ulps_ as f64;
+            recompute_c_y = c_y.dif().ulp() > settings.ulps_ as f64;
+            recompute_lower_x = lower_x.dif().ulp() > settings.ulps_ as f64;
             #[cfg(feature = "console_debug")]
             {
                 tln!(
                     "ulps:{}, x:{:.12}, y:{:.12}, lx:{:.12}",
-                    ulps,
+                    settings.ulps_,
                     c_x.dif().ulp(),
                     c_y.dif().ulp(),
                     lower_x.dif().ulp()
@@ -1413,8 +1502,8 @@ where
             c_event.set_3_raw(c_x.dif().fpv(), c_y.dif().fpv(), lower_x.dif().fpv());
         } else {
             tln!("  LazyCircleFormationFunctor::pss !collinear");
-            let sqr_sum1 = RF::RobustFpt::new_2((a1 * a1 + b1 * b1).sqrt(), 2_f64);
-            let sqr_sum2 = RF::RobustFpt::new_2((a2 * a2 + b2 * b2).sqrt(), 2_f64);
+            let sqr_sum1 = RF::RobustFpt::new_2(libm::sqrt(a1 * a1 + b1 * b1), 2_f64);
+            let sqr_sum2 = RF::RobustFpt::new_2(libm::sqrt(a2 * a2 + b2 * b2), 2_f64);
             let mut a = RF::RobustFpt::new_2(
                 Predicates::<I, F>::robust_cross_product(
                     i_to_i64(segm_end1.x) - i_to_i64(segm_start1.x),
@@ -1567,10 +1656,9 @@ where
                 lower_x.dif().ulp()
             );*/
 
-            let ulps = Predicates::<I, F>::ulps() as f64;
-            recompute_c_x = c_x.dif().ulp() > ulps;
-            recompute_c_y = c_y.dif().ulp() > ulps;
-            recompute_lower_x = lower_x.dif().ulp() > ulps;
+            recompute_c_x = c_x.dif().ulp() > settings.ulps_ as f64;
+            recompute_c_y = c_y.dif().ulp() > settings.ulps_ as f64;
+            recompute_lower_x = lower_x.dif().ulp() > settings.ulps_ as f64;
             #[cfg(feature = "console_debug")]
             {
                 assert!(!c_x.dif().ulp().is_nan());
@@ -1586,6 +1674,8 @@ where
             //println!("  LazyCircleFormationFunctor::pss(recompute_c_x:{},recompute_c_y:{},recompute_lower_x:{}", recompute_c_x, recompute_c_y, recompute_lower_x);
         }
 
+        let (recompute_c_x, recompute_c_y, recompute_lower_x) =
+            Self::override_recompute_(settings, recompute_c_x, recompute_c_y, recompute_lower_x);
         if recompute_c_x || recompute_c_y || recompute_lower_x {
             ExactCircleFormationFunctor::pss(
                 site1,
@@ -1606,6 +1696,7 @@ where
         site2: &VSE::SiteEvent<I, F>,
         site3: &VSE::SiteEvent<I, F>,
         c_event: &VC::CircleEventType,
+        settings: &PredicateSettings,
     ) {
         let i_to_f64 = TC1::<I>::i_to_f64;
         let i_to_i64 = TC1::<I>::i_to_i64;
@@ -1713,10 +1804,9 @@ where
         let c_y_dif = c_y.dif() / denom_dif;
         let lower_x_dif = lower_x.dif() / denom_dif;
 
-        let ulps = Predicates::<I, F>::ulps() as f64;
-        let recompute_c_x = c_x_dif.ulp() > ulps;
-        let recompute_c_y = c_y_dif.ulp() > ulps;
-        let recompute_lower_x = lower_x_dif.ulp() > ulps;
+        let recompute_c_x = c_x_dif.ulp() > settings.ulps_ as f64;
+        let recompute_c_y = c_y_dif.ulp() > settings.ulps_ as f64;
+        let recompute_lower_x = lower_x_dif.ulp() > settings.ulps_ as f64;
 
         t!(" c_x_dif.ulp():{:.12}", c_x_dif.ulp());
         t!("  c_y_dif.ulp() :{:.12}", c_y_dif.ulp());
@@ -1731,6 +1821,8 @@ where
         }
         c_event.set_3_raw(c_x_dif.fpv(), c_y_dif.fpv(), lower_x_dif.fpv());
 
+        let (recompute_c_x, recompute_c_y, recompute_lower_x) =
+            Self::override_recompute_(settings, recompute_c_x, recompute_c_y, recompute_lower_x);
         if recompute_c_x || recompute_c_y || recompute_lower_x {
             ExactCircleFormationFunctor::sss(
                 site1,
@@ -1793,6 +1885,7 @@ where
         site2: &VSE::SiteEvent<I, F>,
         site3: &VSE::SiteEvent<I, F>,
         circle: &VC::CircleEventType,
+        settings: &PredicateSettings,
     ) -> bool {
         tln!(
             "circle_formation_predicate(site1:{:?}, site2:{:?}, site3:{:?}, circle:{:?})",
@@ -1808,37 +1901,51 @@ where
             site3.is_segment()
         );
 
-        let rv = Self::circle_formation_predicate(site1, site2, site3, circle);
+        let rv = Self::circle_formation_predicate(site1, site2, site3, circle, settings)
+            == CircleFormation::Formed;
         rv
     }
 
     /// Create a circle event from the given three sites.
-    /// Returns true if the circle event exists, else false.
-    /// If exists circle event is saved into the c_event variable.
+    /// Returns CircleFormation::Formed if the circle event exists, if so the circle event is
+    /// saved into the c_event variable.
+    /// The collinear and vertical segment rejections are reported separately.
+    /// The lazy evaluation is controlled by, and counted in, `settings`.
     pub(crate) fn circle_formation_predicate(
         site1: &VSE::SiteEvent<I, F>,
         site2: &VSE::SiteEvent<I, F>,
         site3: &VSE::SiteEvent<I, F>,
         circle: &VC::CircleEventType,
-    ) -> bool {
+        settings: &PredicateSettings,
+    ) -> CircleFormation {
         if !site1.is_segment() {
             if !site2.is_segment() {
                 if !site3.is_segment() {
                     // (point, point, point) sites.
-                    if !CircleExistencePredicate::<I, F>::ppp(site1, site2, site3) {
-                        return false;
+                    match OrientationTest::<I, F>::eval_p(
+                        site1.point0(),
+                        site2.point0(),
+                        site3.point0(),
+                    ) {
+                        Orientation::Right => (),
+                        Orientation::Collinear if site1.point0() != site3.point0() => {
+                            return CircleFormation::Collinear
+                        }
+                        Orientation::Collinear => return CircleFormation::NotFormed,
+                        Orientation::Left => return CircleFormation::NotFormed,
                     }
                     LazyCircleFormationFunctor::<I, F>::ppp(
                         site1.point0(),
                         site2.point0(),
                         site3.point0(),
                         circle,
+                        settings,
                     );
                 } else {
                     // (point, point, segment) sites.
                     if !CircleExistencePredicate::<I, F>::pps(site1, site2, site3, SiteIndex::Three)
                     {
-                        return false;
+                        return CircleFormation::NotFormed;
                     }
                     LazyCircleFormationFunctor::<I, F>::pps(
                         site1,
@@ -1846,12 +1953,13 @@ where
                         site3,
                         SiteIndex::Three,
                         circle,
+                        settings,
                     )
                 }
             } else if !site3.is_segment() {
                 // (point, segment, point) sites.
                 if !CircleExistencePredicate::<I, F>::pps(site1, site3, site2, SiteIndex::Two) {
-                    return false;
+                    return CircleFormation::NotFormed;
                 }
                 LazyCircleFormationFunctor::<I, F>::pps(
                     site1,
@@ -1859,11 +1967,12 @@ where
                     site2,
                     SiteIndex::Two,
                     circle,
+                    settings,
                 );
             } else {
                 // (point, segment, segment) sites.
                 if !CircleExistencePredicate::<I, F>::pss(site1, site2, site3, SiteIndex::One) {
-                    return false;
+                    return CircleFormation::NotFormed;
                 }
                 LazyCircleFormationFunctor::<I, F>::pss(
                     site1,
@@ -1871,13 +1980,14 @@ where
                     site3,
                     SiteIndex::One,
                     circle,
+                    settings,
                 );
             }
         } else if !site2.is_segment() {
             if !site3.is_segment() {
                 // (segment, point, point) sites.
                 if !CircleExistencePredicate::<I, F>::pps(site2, site3, site1, SiteIndex::One) {
-                    return false;
+                    return CircleFormation::NotFormed;
                 }
                 LazyCircleFormationFunctor::<I, F>::pps(
                     site2,
@@ -1885,11 +1995,12 @@ where
                     site1,
                     SiteIndex::One,
                     circle,
+                    settings,
                 );
             } else {
                 // (segment, point, segment) sites.
                 if !CircleExistencePredicate::<I, F>::pss(site2, site1, site3, SiteIndex::Two) {
-                    return false;
+                    return CircleFormation::NotFormed;
                 }
                 LazyCircleFormationFunctor::<I, F>::pss(
                     site2,
@@ -1897,33 +2008,47 @@ where
                     site3,
                     SiteIndex::Two,
                     circle,
+                    settings,
                 );
             }
         } else if !site3.is_segment() {
             // (segment, segment, point) sites.
             if !CircleExistencePredicate::<I, F>::pss(site3, site1, site2, SiteIndex::Three) {
-                return false;
+                return CircleFormation::NotFormed;
             }
-            LazyCircleFormationFunctor::<I, F>::pss(site3, site1, site2, SiteIndex::Three, circle);
+            LazyCircleFormationFunctor::<I, F>::pss(
+                site3,
+                site1,
+                site2,
+                SiteIndex::Three,
+                circle,
+                settings,
+            );
         } else {
             // (segment, segment, segment) sites.
             if !CircleExistencePredicate::<I, F>::sss(site1, site2, site3) {
-                return false;
+                return CircleFormation::NotFormed;
             }
-            LazyCircleFormationFunctor::<I, F>::sss(site1, site2, site3, circle);
+            LazyCircleFormationFunctor::<I, F>::sss(site1, site2, site3, circle, settings);
         }
 
         if Self::lies_outside_vertical_segment(circle, site1)
             || Self::lies_outside_vertical_segment(circle, site2)
             || Self::lies_outside_vertical_segment(circle, site3)
         {
-            return false;
+            return CircleFormation::OutsideVerticalSegment;
         }
         #[cfg(all(feature = "geo", feature = "ce_corruption_check"))]
         CircleExistencePredicate::<I, F>::validate_circle_formation_predicate(
             site1, site2, site3, circle,
         );
-        true
+        circle.set_kind(VD::VertexKind::from_segment_count(
+            [site1, site2, site3]
+                .iter()
+                .filter(|s| s.is_segment())
+                .count(),
+        ));
+        CircleFormation::Formed
     }
 }
 
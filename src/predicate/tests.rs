@@ -1,9 +1,10 @@
+#![allow(clippy::bool_assert_comparison)]
 use super::super::beach_line as VB;
 use super::super::geometry::Point;
 use super::super::predicate as VP;
 use super::super::site_event as VSE;
 use super::{InputType, OutputType};
-use std::ops::Neg;
+use core::ops::Neg;
 
 fn new_key<I, F>(x1: I, y1: I, si1: usize, x2: I, y2: I, si2: usize) -> VB::BeachLineNodeKey<I, F>
 where
@@ -53,6 +54,123 @@ fn cross_product_1() {
     assert_eq!(x, 9.0);
 }
 
+#[test]
+fn cross_product_2() {
+    type I = i32;
+    type F = f64;
+    let max = I::MAX as i64;
+    let min = I::MIN as i64;
+    let values = [
+        0,
+        1,
+        -1,
+        7,
+        -13,
+        46_341,
+        -46_341,
+        1 << 20,
+        max - 1,
+        max,
+        min + 1,
+        min,
+    ];
+    for a1 in values {
+        for b1 in values {
+            for a2 in values {
+                for b2 in values {
+                    let u32_path = VP::Predicates::<I, F>::robust_cross_product(a1, b1, a2, b2);
+                    let i128_path = super::robust_cross_product_i128(a1, b1, a2, b2);
+                    assert_eq!(u32_path, i128_path, "{} {} {} {}", a1, b1, a2, b2);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cross_product_3() {
+    type I = i32;
+    type F = f64;
+    // differences of i32 coordinates, the i64 products would overflow here
+    let max = I::MAX as i64 - I::MIN as i64;
+    let values = [max, -max, max - 1, -(max - 1), 3, -3];
+    for a1 in values {
+        for b1 in values {
+            for a2 in values {
+                for b2 in values {
+                    let x = VP::Predicates::<I, F>::robust_cross_product(a1, b1, a2, b2);
+                    let exact = a1 as i128 * b2 as i128 - b1 as i128 * a2 as i128;
+                    assert_eq!(x, exact as f64, "{} {} {} {}", a1, b1, a2, b2);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cross_product_4() {
+    type I = i64;
+    type F = f64;
+    // differences of i64 coordinates within the safe range
+    let max = 2 * crate::builder::Builder::<I, F>::max_safe_coordinate();
+    let values = [max, -max, max - 1, -(max - 1), 3_037_000_500, -3, 0];
+    for a1 in values {
+        for b1 in values {
+            for a2 in values {
+                for b2 in values {
+                    let x = VP::Predicates::<I, F>::robust_cross_product(a1, b1, a2, b2);
+                    let exact = a1 as i128 * b2 as i128 - b1 as i128 * a2 as i128;
+                    assert_eq!(x, exact as f64, "{} {} {} {}", a1, b1, a2, b2);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cross_product_5() {
+    type I = i32;
+    type F = f64;
+    // the u32 path against the generic i128 path, for random differences of i32 coordinates
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        seed
+    };
+    let mut value = || {
+        let r = random();
+        let difference = (r >> 32) as u32 as I as i64 - r as u32 as I as i64;
+        // smaller magnitudes too, so that the products are close to each other more often
+        difference >> (random() % 40)
+    };
+    for _ in 0..2_000_000 {
+        let (a1, b1, a2, b2) = (value(), value(), value(), value());
+        let u32_path = VP::Predicates::<I, F>::robust_cross_product(a1, b1, a2, b2);
+        let i128_path = super::robust_cross_product_i128(a1, b1, a2, b2);
+        assert_eq!(u32_path, i128_path, "{} {} {} {}", a1, b1, a2, b2);
+    }
+}
+
+#[test]
+fn is_vertical_1() {
+    type I = i32;
+    type F = f64;
+    for (p0, p1, expected) in [
+        (Point { x: 5, y: 1 }, Point { x: 5, y: 9 }, true),
+        (Point { x: 5, y: 9 }, Point { x: 5, y: 1 }, true),
+        (Point { x: 5, y: 1 }, Point { x: 6, y: 9 }, false),
+        (Point { x: 6, y: 9 }, Point { x: 5, y: 1 }, false),
+    ] {
+        let mut site = VSE::SiteEvent::<I, F>::new_3(p0, p1, 0);
+        assert_eq!(VP::Predicates::<I, F>::is_vertical_1(&site), expected);
+        assert_eq!(VP::Predicates::<I, F>::is_vertical_2(&p1, &p0), expected);
+        let _ = site.inverse();
+        assert_eq!(VP::Predicates::<I, F>::is_vertical_1(&site), expected);
+    }
+}
+
 #[test]
 fn node_1() {
     type I = i32;
@@ -672,3 +790,150 @@ fn distance_predicate_ss_64() {
         true
     ); //distance_predicate
 }
+
+#[test]
+fn lazy_vs_exact_1() {
+    use super::super::ctypes::UlpComparison;
+    use super::super::geometry::Line;
+    use super::super::predicates::Site;
+    type F = f64;
+    let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut random = |m: i32| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % m as u64) as i32
+    };
+    let distance = |site: &Site<i32>, x: F, y: F| match site {
+        Site::Point(p) => (x - p.x as F).hypot(y - p.y as F),
+        Site::Segment(l) => {
+            let (x0, y0) = (l.start.x as F, l.start.y as F);
+            let (dx, dy) = (l.end.x as F - x0, l.end.y as F - y0);
+            // the circle must touch the inside of the segment
+            let t = ((x - x0) * dx + (y - y0) * dy) / (dx * dx + dy * dy);
+            if t <= 0.0 || t >= 1.0 {
+                return F::INFINITY;
+            }
+            (x - x0 - t * dx).hypot(y - y0 - t * dy)
+        }
+    };
+    let round = |x: F, y: F| Point {
+        x: x.round() as i32,
+        y: y.round() as i32,
+    };
+    let mut formed = [0_usize; 4];
+    for _ in 0..300 {
+        // the sites are placed on (points) or tangent to (segments) a random circle, in three
+        // separate sectors so that the segments never intersect
+        let (cx, cy) = ((random(2000) - 1000) as F, (random(2000) - 1000) as F);
+        let r = (50 + random(1000)) as F;
+        let angles: Vec<F> = (0..3)
+            .map(|k| (120 * k + random(100)) as F * std::f64::consts::PI / 180.0)
+            .collect();
+        for (segments, count) in formed.iter_mut().enumerate() {
+            let sites: Vec<Site<i32>> = angles
+                .iter()
+                .enumerate()
+                .map(|(k, a)| {
+                    let (x, y) = (cx + r * a.cos(), cy + r * a.sin());
+                    if k + segments >= 3 {
+                        let h = r * (1 + random(15)) as F / 100.0;
+                        Site::Segment(Line::new(
+                            round(x + h * a.sin(), y - h * a.cos()),
+                            round(x - h * a.sin(), y + h * a.cos()),
+                        ))
+                    } else {
+                        Site::Point(round(x, y))
+                    }
+                })
+                .collect();
+            if sites
+                .iter()
+                .any(|s| matches!(s, Site::Segment(l) if l.start == l.end))
+            {
+                continue;
+            }
+            for order in [
+                [0, 1, 2],
+                [0, 2, 1],
+                [1, 0, 2],
+                [1, 2, 0],
+                [2, 0, 1],
+                [2, 1, 0],
+            ]
+            .iter()
+            {
+                // the circle is on the left side of the segments, the reversed segments have
+                // it on their right side
+                for reverse in [false, true].iter() {
+                    let site_events: Vec<VSE::SiteEvent<i32, F>> = order
+                        .iter()
+                        .map(|i| match sites[*i] {
+                            Site::Segment(l) if *reverse => {
+                                Site::Segment(Line::new(l.end, l.start)).site_event(*i)
+                            }
+                            site => site.site_event(*i),
+                        })
+                        .collect();
+                    if let Some((lazy, exact)) =
+                        VP::LazyCircleFormationFunctor::<i32, F>::compute_both(
+                            &site_events[0],
+                            &site_events[1],
+                            &site_events[2],
+                        )
+                    {
+                        // only compare circles that really touch all three sites, with the
+                        // lower x to the right of the center. The predicates are not defined
+                        // for site triples that can't occur on the beach-line
+                        let radius = exact[2] - exact[0];
+                        if sites.iter().any(|s| {
+                            (distance(s, exact[0], exact[1]) - radius).abs() > 1e-6 * radius
+                        }) {
+                            continue;
+                        }
+                        *count += 1;
+                        for (l, e) in lazy.iter().zip(exact.iter()) {
+                            assert!(
+                                UlpComparison::ulp_comparison(*l, *e, 128)
+                                    == std::cmp::Ordering::Equal,
+                                "lazy:{:?} exact:{:?} sites:{:?}",
+                                lazy,
+                                exact,
+                                order.iter().map(|i| sites[*i]).collect::<Vec<_>>()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // ppp, pps, pss and sss circles
+    assert!(formed.iter().all(|f| *f > 500), "{:?}", formed);
+}
+
+#[test]
+fn pps_endpoint_fallback_1() {
+    use super::super::circle_event as VC;
+    // the segment endpoint is picked as the third point of the circle, the three points then
+    // form a counter clockwise triangle
+    let site1 = VSE::SiteEvent::<i32, f64>::new_2(Point { x: 3, y: -6 }, 0);
+    let site2 = VSE::SiteEvent::<i32, f64>::new_2(Point { x: 6, y: 3 }, 1);
+    let mut site3 =
+        VSE::SiteEvent::<i32, f64>::new_3(Point { x: -9, y: -1 }, Point { x: -7, y: 0 }, 2);
+    let _ = site3.inverse();
+    let circle = VC::CircleEventC::new_1(VC::CircleEvent::new_1(VB::BeachLineIndex(0)));
+    VP::LazyCircleFormationFunctor::<i32, f64>::pps(
+        &site1,
+        &site2,
+        &site3,
+        VP::SiteIndex::Three,
+        &circle,
+        &VP::PredicateSettings::default(),
+    );
+    let c = circle.0.get();
+    let radius = (c.raw_x() - 3.0).hypot(c.raw_y() + 6.0);
+    approx::assert_abs_diff_eq!(c.raw_x(), -1.0 / 6.0, epsilon = 1e-4);
+    approx::assert_abs_diff_eq!(c.raw_y(), 1.0 / 18.0, epsilon = 1e-4);
+    // lower_x is the rightmost point of the circle
+    approx::assert_abs_diff_eq!(c.lower_x().into_inner(), c.raw_x() + radius, epsilon = 1e-4);
+}
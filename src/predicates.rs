@@ -0,0 +1,124 @@
+// Boost.Polygon library detail/voronoi_predicates.hpp header file
+
+//          Copyright Andrii Sydorchuk 2010-2012.
+// Distributed under the Boost Software License, Version 1.0.
+//    (See accompanying file LICENSE_1_0.txt or copy at
+//          http://www.boost.org/LICENSE_1_0.txt)
+
+// See http://www.boost.org for updates, documentation, and revision history of C++ code.
+
+// Ported from C++ boost 1.76.0 to Rust in 2020/2021 by Eadf (github.com/eadf)
+
+//! Public geometric primitives built on top of the robust (lazy and exact) predicates used by
+//! the sweep-line algorithm.
+
+use super::beach_line as VB;
+use super::circle_event as VC;
+use super::geometry::{Line, Point};
+use super::predicate as VP;
+use super::site_event as VSE;
+use super::TypeConverter2 as TC2;
+use super::{InputType, OutputType};
+use core::ops::Neg;
+
+/// An input site: a point or a segment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Site<I: InputType> {
+    Point(Point<I>),
+    Segment(Line<I>),
+}
+
+impl<I: InputType + Neg<Output = I>> Site<I> {
+    pub(crate) fn site_event<F: OutputType + Neg<Output = F>>(
+        &self,
+        index: usize,
+    ) -> VSE::SiteEvent<I, F> {
+        let mut site = match self {
+            Site::Point(p) => VSE::SiteEvent::<I, F>::new_3(*p, *p, index),
+            Site::Segment(l) => {
+                if VP::PointComparisonPredicate::<I>::point_comparison_predicate(&l.end, &l.start) {
+                    *VSE::SiteEvent::<I, F>::new_3(l.end, l.start, index).inverse()
+                } else {
+                    VSE::SiteEvent::<I, F>::new_3(l.start, l.end, index)
+                }
+            }
+        };
+        site.set_sorted_index(index);
+        site
+    }
+}
+
+/// Computes the circle touching the three sites, the same way a Voronoi vertex is computed.
+/// Returns the center and the radius of the circle, or None if no such circle exists.
+///
+/// The sites are expected in the order they would appear on the beach-line: i.e. clockwise
+/// around the circle. Three points in counter-clockwise, or collinear, order forms no circle.
+///
+/// Segment sites:
+/// * If only one of the sites is a segment its direction is ignored.
+/// * Otherwise the direction of the segments must be consistent, i.e. the circle should be
+///   located on the same side (left or right) of every segment, seen from `start` towards
+///   `end`. Reversing one of the segments selects the circle on its other side.
+/// * The two directions of the same segment are considered to be the same site.
+/// * Segments sharing an endpoint form a circle with zero radius located at that endpoint,
+///   just like the Voronoi vertex at the joint of two connected segments.
+/// * Just like in the sweep-line algorithm it is assumed that the circle touches the segment
+///   somewhere between its endpoints. This is not verified, so if the sites are configured
+///   such that the circle would touch the extension of a segment, the result is meaningless.
+/// ```
+/// # use boostvoronoi::geometry::{Line, Point};
+/// # use boostvoronoi::predicates::{circle_event, Site};
+/// let p = |x, y| Site::Point(Point { x, y });
+/// let (center, radius) = circle_event::<i32, f64>(&p(0, 0), &p(0, 10), &p(10, 0)).unwrap();
+/// assert!((center[0] - 5.0).abs() < 1e-9 && (center[1] - 5.0).abs() < 1e-9);
+/// assert!((radius - 50_f64.sqrt()).abs() < 1e-9);
+/// // counter-clockwise order
+/// assert!(circle_event::<i32, f64>(&p(0, 0), &p(10, 0), &p(0, 10)).is_none());
+/// ```
+pub fn circle_event<I, F>(site1: &Site<I>, site2: &Site<I>, site3: &Site<I>) -> Option<([F; 2], F)>
+where
+    I: InputType + Neg<Output = I>,
+    F: OutputType + Neg<Output = F>,
+{
+    // the two directions of the same segment are the same site
+    let same = |a: &Site<I>, b: &Site<I>| match (a, b) {
+        (Site::Segment(a), Site::Segment(b)) => {
+            (a.start == b.start && a.end == b.end) || (a.start == b.end && a.end == b.start)
+        }
+        _ => a == b,
+    };
+    let index2 = if same(site1, site2) { 0 } else { 1 };
+    let index3 = if same(site1, site3) {
+        0
+    } else if same(site2, site3) {
+        index2
+    } else {
+        2
+    };
+    let site1 = site1.site_event::<F>(0);
+    let site2 = site2.site_event::<F>(index2);
+    let site3 = site3.site_event::<F>(index3);
+    let c_event = VC::CircleEventC::new_1(VC::CircleEvent::new_1(VB::BeachLineIndex(0)));
+
+    if VP::CircleFormationFunctor::<I, F>::circle_formation_predicate(
+        &site1,
+        &site2,
+        &site3,
+        &c_event,
+        &VP::PredicateSettings::default(),
+    ) != VP::CircleFormation::Formed
+    {
+        return None;
+    }
+    let c = c_event.0.get();
+    if !(c.raw_x().is_finite() && c.raw_y().is_finite() && c.lower_x().into_inner().is_finite()) {
+        return None;
+    }
+    Some((
+        [
+            TC2::<I, F>::f64_to_f(c.raw_x()),
+            TC2::<I, F>::f64_to_f(c.raw_y()),
+        ],
+        TC2::<I, F>::f64_to_f(c.lower_x().into_inner() - c.raw_x()),
+    ))
+}
@@ -0,0 +1,38 @@
+// Boost.Polygon library detail/robust_fpt.hpp header file
+
+//          Copyright Andrii Sydorchuk 2010-2012.
+// Distributed under the Boost Software License, Version 1.0.
+//    (See accompanying file LICENSE_1_0.txt or copy at
+//          http://www.boost.org/LICENSE_1_0.txt)
+
+// See http://www.boost.org for updates, documentation, and revision history of C++ code.
+
+// Ported from C++ boost 1.76.0 to Rust in 2020/2021 by Eadf (github.com/eadf)
+
+//! Robust floating point arithmetic, usable for building your own robust predicates.
+//!
+//! [`RobustFpt`] is a floating point value paired with an upper bound of its relative error
+//! (measured in EPS). The operators `+`, `-`, `*`, `/` (and their assign variants) as well as
+//! `sqrt()` keep track of the error. Use `fpv()` to get the value and `ulp()` for the error.
+//!
+//! Subtraction of values of the same sign may produce a huge relative error. [`RobustDif`]
+//! avoids this by keeping the positive and the negative terms in two separate sums. The
+//! difference is only evaluated, by calling `dif()`, as the very last step.
+//!
+//! If the error of a result is too large for your purpose (compared to e.g. 64 ULPs) you should
+//! fall back to exact arithmetic, see [`crate::extended_int`].
+//! ```
+//! # use boostvoronoi::robust::{RobustDif, RobustFpt};
+//! let a = RobustFpt::new_1(10.0);
+//! let b = RobustFpt::new_1(4.0);
+//!
+//! // (a*a) - (b*b) evaluated without intermediate cancellation
+//! let mut d = RobustDif::new();
+//! d += a * a;
+//! d -= b * b;
+//! let r = d.dif();
+//! assert_eq!(r.fpv(), 84.0);
+//! assert!(r.ulp() < 64.0);
+//! ```
+
+pub use crate::robust_fpt::{RobustDif, RobustFpt, ROUNDING_ERROR};
@@ -22,11 +22,11 @@ use super::extended_exp_fpt as EX;
 use super::extended_int as EI;
 #[allow(unused_imports)]
 use crate::{t, tln};
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops;
 use num::{Float, NumCast, Zero};
 use ordered_float::OrderedFloat;
-use std::fmt;
-use std::marker::PhantomData;
-use std::ops;
 
 /// Rounding error is at most 1 EPS.
 pub const ROUNDING_ERROR: f64 = 1_f64;
@@ -100,6 +100,7 @@ impl Default for RobustFpt {
 }
 
 impl RobustFpt {
+    /// Creates a value without any relative error.
     pub fn new_1(fpv: f64) -> Self {
         Self {
             fpv_: fpv,
@@ -107,6 +108,7 @@ impl RobustFpt {
         }
     }
 
+    /// Creates a value with the given relative error, measured in EPS.
     pub fn new_2(fpv: f64, error: f64) -> Self {
         Self {
             fpv_: fpv,
@@ -114,17 +116,29 @@ impl RobustFpt {
         }
     }
 
+    /// Returns the floating point value.
     #[inline(always)]
     pub fn fpv(&self) -> f64 {
         self.fpv_
     }
 
+    /// Returns the relative error of the value, measured in EPS.
     #[inline(always)]
     #[allow(dead_code)]
     pub fn re(&self) -> f64 {
         self.re_.into_inner()
     }
 
+    /// Returns the relative error of the value, measured in EPS.
+    /// An error of one EPS is at most one ULP, so this is an upper bound for the ULP error.
+    /// ```
+    /// # use boostvoronoi::robust::RobustFpt;
+    /// let a = RobustFpt::new_1(2.0);
+    /// let b = RobustFpt::new_1(3.0);
+    /// // every multiplication adds one rounding error
+    /// assert_eq!((a * b).ulp(), 1.0);
+    /// assert_eq!((a * b * b).ulp(), 2.0);
+    /// ```
     #[inline(always)]
     pub fn ulp(&self) -> f64 {
         self.re()
@@ -184,6 +198,8 @@ impl RobustFpt {
         self.fpv_.is_zero()
     }
 
+    /// Returns the square root of the value. The relative error is halved and one
+    /// rounding error is added.
     pub fn sqrt(&self) -> RobustFpt {
         Self {
             //fpv_: Self::get_sqrt(self.fpv_),
@@ -206,7 +222,7 @@ impl ops::Add<RobustFpt> for RobustFpt {
     fn add(self, _rhs: RobustFpt) -> Self {
         let fpv: f64 = self.fpv_ + _rhs.fpv_;
         let re = if (!self.is_neg() && !_rhs.is_neg()) || (!self.is_pos() && !_rhs.is_pos()) {
-            std::cmp::max(self.re_, _rhs.re_) + ROUNDING_ERROR
+            core::cmp::max(self.re_, _rhs.re_) + ROUNDING_ERROR
         } else {
             let mut temp =
                 (self.fpv_ * self.re_.into_inner() - _rhs.fpv_ * _rhs.re_.into_inner()) / fpv;
@@ -243,7 +259,7 @@ impl ops::AddAssign<RobustFpt> for RobustFpt {
 
         let fpv: f64 = self.fpv_ + _rhs.fpv_;
         let re = if (!self.is_neg() && !_rhs.is_neg()) || (!self.is_pos() && !_rhs.is_pos()) {
-            std::cmp::max(self.re_, _rhs.re_) + OrderedFloat(ROUNDING_ERROR)
+            core::cmp::max(self.re_, _rhs.re_) + OrderedFloat(ROUNDING_ERROR)
         } else {
             let mut temp =
                 (self.fpv_ * self.re_.into_inner() - _rhs.fpv_ * _rhs.re_.into_inner()) / fpv;
@@ -303,7 +319,7 @@ impl ops::Sub<RobustFpt> for RobustFpt {
 
         let fpv: f64 = self.fpv_ - _rhs.fpv_;
         let re = if (!self.is_neg() && !_rhs.is_pos()) || (!self.is_pos() && !_rhs.is_neg()) {
-            std::cmp::max(self.re_, _rhs.re_) + OrderedFloat(ROUNDING_ERROR)
+            core::cmp::max(self.re_, _rhs.re_) + OrderedFloat(ROUNDING_ERROR)
         } else {
             let mut temp =
                 (self.fpv_ * self.re_.into_inner() + _rhs.fpv_ * _rhs.re_.into_inner()) / fpv;
@@ -346,7 +362,7 @@ impl ops::SubAssign<RobustFpt> for RobustFpt {
 
         let fpv = self.fpv_ - _rhs.fpv_;
         if (!self.is_neg() && !_rhs.is_pos()) || (!self.is_pos() && !_rhs.is_neg()) {
-            self.re_ = std::cmp::max(self.re_, _rhs.re_) + OrderedFloat(ROUNDING_ERROR);
+            self.re_ = core::cmp::max(self.re_, _rhs.re_) + OrderedFloat(ROUNDING_ERROR);
         } else {
             let mut temp: f64 =
                 (self.fpv_ * self.re_.into_inner() + _rhs.fpv_ * _rhs.re_.into_inner()) / fpv;
@@ -442,6 +458,7 @@ pub struct RobustDif {
 }
 
 impl RobustDif {
+    /// Creates a zero value.
     pub fn new() -> Self {
         Self {
             positive_sum_: RobustFpt::default(),
@@ -457,6 +474,7 @@ impl RobustDif {
         }
     }
 
+    /// Creates the difference `a - b`, both `a` and `b` should be non-negative.
     #[allow(dead_code)]
     pub fn new_from_2(a: &RobustFpt, b: &RobustFpt) -> Self {
         Self {
@@ -465,6 +483,14 @@ impl RobustDif {
         }
     }
 
+    /// Creates a value, negative values are stored as a positive negative_sum.
+    /// ```
+    /// # use boostvoronoi::robust::RobustDif;
+    /// let a = RobustDif::new_1(-3.0);
+    /// assert_eq!(a.positive().fpv(), 0.0);
+    /// assert_eq!(a.negative().fpv(), 3.0);
+    /// assert_eq!(a.dif().fpv(), -3.0);
+    /// ```
     #[allow(dead_code)]
     pub fn new_1(value: f64) -> Self {
         if is_pos_f(value) {
@@ -475,11 +501,12 @@ impl RobustDif {
         } else {
             Self {
                 positive_sum_: RobustFpt::default(),
-                negative_sum_: RobustFpt::new_1(value),
+                negative_sum_: RobustFpt::new_1(-value),
             }
         }
     }
 
+    /// Creates the difference `pos - neg`, both values should be non-negative.
     #[allow(dead_code)]
     pub fn new_2(pos: f64, neg: f64) -> Self {
         #[cfg(feature = "console_debug")]
@@ -493,15 +520,18 @@ impl RobustDif {
         }
     }
 
+    /// Evaluates the difference, this is where the precision may be lost.
     pub fn dif(&self) -> RobustFpt {
         self.positive_sum_ - self.negative_sum_
     }
 
+    /// Returns the sum of all the positive terms.
     #[inline]
     pub fn positive(&self) -> RobustFpt {
         self.positive_sum_
     }
 
+    /// Returns the sum of all the negative terms (as a positive value).
     #[inline]
     // neg() will collide with the trait RobustDif
     pub fn negative(&self) -> RobustFpt {
@@ -510,7 +540,7 @@ impl RobustDif {
 
     #[inline]
     fn swap(&mut self) {
-        std::mem::swap(&mut self.positive_sum_, &mut self.negative_sum_);
+        core::mem::swap(&mut self.positive_sum_, &mut self.negative_sum_);
     }
 }
 
@@ -9,21 +9,26 @@
 
 // Ported from C++ boost 1.76.0 to Rust in 2020/2021 by Eadf (github.com/eadf)
 
+//! The site event, the representation of the input sites used by the sweep-line algorithm.
+//! It is public so that custom algorithms can be built on the same representation, but note
+//! that the builder creates its own site events from the input.
+
 #[cfg(test)]
 mod tests;
 
 use super::diagram as VD;
 use super::predicate as VP;
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
-use super::geometry::Point;
+use super::geometry::{Line, Point};
 use super::{InputType, OutputType};
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::marker::PhantomData;
-use std::mem;
-use std::ops::Neg;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Neg;
 
+/// The index type of the site events, see `SiteEvent::sorted_index()`.
 pub type SiteEventIndexType = usize;
 
 /// Site event type.
@@ -31,6 +36,7 @@ pub type SiteEventIndexType = usize;
 ///   1) point site
 ///   2) start-point of the segment site
 ///   3) endpoint of the segment site
+///
 /// Implicit segment direction is defined: the start-point of
 /// the segment compares less than its endpoint.
 /// Each input segment is divided onto two site events:
@@ -38,6 +44,7 @@ pub type SiteEventIndexType = usize;
 ///      (is_inverse() = false)
 ///   2) Another going from the endpoint to the start-point
 ///      (is_inverse() = true)
+///
 /// In beach line data structure segment sites of the first
 /// type precede sites of the second type for the same segment.
 /// Members:
@@ -102,7 +109,7 @@ where
     O: OutputType + Neg<Output = O>,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(VP::EventComparisonPredicate::<I, O>::event_comparison_predicate_ii(self, other))
+        Some(self.cmp(other))
     }
 }
 
@@ -160,23 +167,32 @@ where
             sorted_index_: 0,
             initial_index_: initial_index,
             flags_: VD::ColorBits::SINGLE_POINT__BIT.0,
-            #[doc(hidden)]
             pdf_: PhantomData,
         }
     }
 
-    pub(crate) fn new_3(
-        a: Point<I>,
-        b: Point<I>,
-        initial_index: SiteEventIndexType,
-    ) -> SiteEvent<I, F> {
+    /// Creates a new site event, `a == b` creates a point site and `a != b` a segment site
+    /// going from `a` to `b`. The sorted index is zero and no source category is set.
+    /// Note that the builder always creates segment sites with `a` less than `b` (x first,
+    /// then y), followed by an `inverse()`d copy.
+    /// ```
+    /// # use boostvoronoi::geometry::Point;
+    /// # use boostvoronoi::site_event::SiteEvent;
+    /// let mut site = SiteEvent::<i32, f64>::new_3(Point { x: 1, y: 2 }, Point { x: 5, y: 3 }, 7);
+    /// assert!(site.is_segment());
+    /// assert_eq!((site.x0(), site.y0(), site.x1(), site.y1()), (1, 2, 5, 3));
+    /// assert_eq!(site.initial_index(), 7);
+    /// site.inverse();
+    /// assert!(site.is_inverse());
+    /// assert_eq!((site.x0(), site.y0(), site.x1(), site.y1()), (5, 3, 1, 2));
+    /// ```
+    pub fn new_3(a: Point<I>, b: Point<I>, initial_index: SiteEventIndexType) -> SiteEvent<I, F> {
         Self {
             point0_: a,
             point1_: b,
             sorted_index_: 0,
             initial_index_: initial_index,
             flags_: 0,
-            #[doc(hidden)]
             pdf_: PhantomData,
         }
     }
@@ -222,68 +238,88 @@ where
         }
     }
 
+    /// Returns the x coordinate of the point site, or of the first point of the segment site.
     #[inline(always)]
     pub fn x(&self) -> I {
         self.point0_.x
     }
 
+    /// Returns the y coordinate of the point site, or of the first point of the segment site.
     #[inline(always)]
     pub fn y(&self) -> I {
         self.point0_.y
     }
 
+    /// Same as `x()`.
     #[inline(always)]
     pub fn x0(&self) -> I {
         self.point0_.x
     }
 
+    /// Same as `y()`.
     #[inline(always)]
     pub fn y0(&self) -> I {
         self.point0_.y
     }
 
+    /// Returns the x coordinate of the second point of the segment site, equal to `x0()` for
+    /// point sites.
     #[inline(always)]
     pub fn x1(&self) -> I {
         self.point1_.x
     }
 
+    /// Returns the y coordinate of the second point of the segment site, equal to `y0()` for
+    /// point sites.
     #[inline(always)]
     pub fn y1(&self) -> I {
         self.point1_.y
     }
 
+    /// Returns the point site, or the first point of the segment site.
     #[inline(always)]
     pub fn point0(&self) -> &Point<I> {
         &self.point0_
     }
 
+    /// Returns the second point of the segment site, equal to `point0()` for point sites.
     #[inline(always)]
     pub fn point1(&self) -> &Point<I> {
         &self.point1_
     }
 
+    /// Returns the index of the site among the sorted site events, this is also the
+    /// `CellIndex` of the cell of the site.
     #[inline(always)]
     pub fn sorted_index(&self) -> SiteEventIndexType {
         self.sorted_index_
     }
 
+    /// Sets the index of the site among the sorted site events.
     #[inline(always)]
     pub fn set_sorted_index(&mut self, index: SiteEventIndexType) {
         self.sorted_index_ = index;
     }
 
+    /// Changes the source index, used by the builder when points are added after segments.
+    pub(crate) fn set_initial_index(&mut self, index: SiteEventIndexType) {
+        self.initial_index_ = index;
+    }
+
+    /// Returns the index of the site among the input, i.e. the source index.
     #[inline(always)]
-    pub(crate) fn initial_index(&self) -> SiteEventIndexType {
+    pub fn initial_index(&self) -> SiteEventIndexType {
         self.initial_index_
     }
 
-    // todo this looks suspicious
-    pub(crate) fn is_inverse(&self) -> bool {
+    /// Returns true if the segment site has been `inverse()`d an odd number of times.
+    pub fn is_inverse(&self) -> bool {
         (self.flags_ & VD::ColorBits::IS_INVERSE__BIT.0) != 0
     }
 
-    // todo this looks suspicious
-    pub(crate) fn inverse(&mut self) -> &mut Self {
+    /// Mutates the site in place: swaps the two points of the segment site and toggles the
+    /// `is_inverse()` flag.
+    pub fn inverse(&mut self) -> &mut Self {
         mem::swap(&mut self.point0_, &mut self.point1_);
         self.flags_ ^= VD::ColorBits::IS_INVERSE__BIT.0;
         self
@@ -304,16 +340,35 @@ where
         self.flags_ = flags;
     }
 
+    /// Returns the input geometry of the site, segments in their original direction.
+    pub(crate) fn site_geometry(&self) -> VD::SiteGeometry<I> {
+        if self.is_point() {
+            return VD::SiteGeometry::Point(self.point0_);
+        }
+        // undo the sorting of the end points, and any later inversion
+        let reversed =
+            (self.source_category() == VD::ColorBits::REVERSE_SEGMENT) != self.is_inverse();
+        if reversed {
+            VD::SiteGeometry::Segment(Line::new(self.point1_, self.point0_))
+        } else {
+            VD::SiteGeometry::Segment(Line::new(self.point0_, self.point1_))
+        }
+    }
+
+    /// Returns true if this is a point site.
     #[inline(always)]
     pub fn is_point(&self) -> bool {
         (self.point0_.x == self.point1_.x) && (self.point0_.y == self.point1_.y)
     }
 
+    /// Returns true if this is a segment site.
     #[inline(always)]
     pub fn is_segment(&self) -> bool {
         (self.point0_.x != self.point1_.x) || (self.point0_.y != self.point1_.y)
     }
 
+    /// Returns true if the bisector of the two sites is a primary edge, i.e. the sites are not
+    /// a segment and one of its own endpoints.
     #[allow(unknown_lints)]
     #[allow(clippy::suspicious_operation_groupings)]
     pub fn is_primary_edge(site1: &SiteEvent<I, F>, site2: &SiteEvent<I, F>) -> bool {
@@ -328,6 +383,7 @@ where
         true
     }
 
+    /// Returns true if the bisector of the two sites is a straight line.
     pub fn is_linear_edge(site1: &SiteEvent<I, F>, site2: &SiteEvent<I, F>) -> bool {
         if !Self::is_primary_edge(site1, site2) {
             return true;
@@ -1,4 +1,5 @@
 #![allow(unused_imports)]
+#![allow(clippy::bool_assert_comparison)]
 use super::super::diagram::Diagram;
 use super::super::geometry::{Line, Point};
 use super::super::site_event as VSE;
@@ -13,8 +13,10 @@
 use super::diagram as VD;
 pub use super::{InputType, OutputType};
 use crate::BvError;
-use std::marker::PhantomData;
-use std::ops::Neg;
+use alloc::format;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Neg;
 
 /// Sync version of the boostvoronoi::diagram::VoronoiDiagram struct.
 /// This is useful when traversing the diagram in a multi threaded environment.
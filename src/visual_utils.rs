@@ -15,9 +15,12 @@ use super::{InputType, OutputType};
 use crate::BvError;
 
 use super::geometry::{Line, Point};
-use std::fmt;
-use std::marker::PhantomData;
-use std::ops::Neg;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Neg;
 
 /// Utilities class, that contains set of routines handful for visualization.
 pub struct VoronoiVisualUtils<I, F>
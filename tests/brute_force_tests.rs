@@ -0,0 +1,129 @@
+use boostvoronoi::builder as VB;
+use boostvoronoi::diagram as VD;
+use boostvoronoi::BvError;
+use rand::{Rng, SeedableRng};
+
+type I = i32;
+type F = f64;
+
+/// The plane rasterized into `resolution` x `resolution` pixels, each pixel assigned to the
+/// site nearest to its center.
+struct Raster {
+    low: [F; 2],
+    pixel_size: F,
+    resolution: usize,
+    nearest: Vec<usize>,
+}
+
+impl Raster {
+    /// Returns the site assigned to the pixel containing `position`.
+    fn lookup(&self, position: [F; 2]) -> usize {
+        let pixel = |i: usize| {
+            (((position[i] - self.low[i]) / self.pixel_size) as usize).min(self.resolution - 1)
+        };
+        self.nearest[pixel(1) * self.resolution + pixel(0)]
+    }
+
+    /// The largest difference in distance, from a position, between the site found by
+    /// `lookup()` and the true nearest site.
+    fn tolerance(&self) -> F {
+        self.pixel_size * F::sqrt(2.0)
+    }
+}
+
+fn distance(a: [F; 2], b: [F; 2]) -> F {
+    (a[0] - b[0]).hypot(a[1] - b[1])
+}
+
+/// Brute force ground truth for the Voronoi diagram of points: rasterizes the bounding box
+/// of `sites` and assigns every pixel to its nearest site, ties going to the lowest index.
+fn brute_force_nearest(sites: &[[I; 2]], grid_resolution: usize) -> Raster {
+    let sites: Vec<[F; 2]> = sites.iter().map(|s| [s[0] as F, s[1] as F]).collect();
+    let low = sites
+        .iter()
+        .fold([F::MAX; 2], |l, s| [l[0].min(s[0]), l[1].min(s[1])]);
+    let high = sites
+        .iter()
+        .fold([F::MIN; 2], |h, s| [h[0].max(s[0]), h[1].max(s[1])]);
+    let pixel_size = (high[0] - low[0]).max(high[1] - low[1]) / grid_resolution as F;
+    let mut nearest = Vec::with_capacity(grid_resolution * grid_resolution);
+    for y in 0..grid_resolution {
+        for x in 0..grid_resolution {
+            let center = [
+                low[0] + (x as F + 0.5) * pixel_size,
+                low[1] + (y as F + 0.5) * pixel_size,
+            ];
+            let (index, _) = sites.iter().enumerate().fold((0, F::MAX), |best, (i, s)| {
+                let d = distance(center, *s);
+                if d < best.1 {
+                    (i, d)
+                } else {
+                    best
+                }
+            });
+            nearest.push(index);
+        }
+    }
+    Raster {
+        low,
+        pixel_size,
+        resolution: grid_resolution,
+        nearest,
+    }
+}
+
+#[test]
+fn brute_force_locate_1() -> Result<(), BvError> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(556);
+    for _ in 0..5 {
+        let mut points: Vec<[I; 2]> = (0..50)
+            .map(|_| [rng.gen_range(0..1000), rng.gen_range(0..1000)])
+            .collect();
+        points.sort_unstable();
+        points.dedup();
+        let input = VB::to_points::<I, I>(&points);
+        let output = {
+            let mut vb = VB::Builder::<I, F>::default();
+            vb.with_vertices(input.iter())?;
+            vb.build()?
+        };
+        output.validate_topology()?;
+        let raster = brute_force_nearest(&points, 200);
+        let site = |i: usize| [points[i][0] as F, points[i][1] as F];
+        let mut polygons = vec![None; points.len()];
+        for cell in output.cells().iter() {
+            let cell = cell.get();
+            polygons[cell.source_index()] = output.cell_polygon(cell.id())?;
+        }
+
+        for _ in 0..1000 {
+            let side = raster.pixel_size * raster.resolution as F;
+            let q = [
+                rng.gen_range(raster.low[0]..raster.low[0] + side),
+                rng.gen_range(raster.low[1]..raster.low[1] + side),
+            ];
+            let (located, category) = output.locate(q).unwrap();
+            assert_eq!(category, VD::SourceCategory::SinglePoint);
+            let expected = raster.lookup(q);
+            if located != expected {
+                // the pixel straddles the border between the two cells
+                assert!(
+                    (distance(q, site(located)) - distance(q, site(expected))).abs()
+                        <= raster.tolerance(),
+                    "{:?} located in the cell of {:?}, expected {:?}",
+                    q,
+                    points[located],
+                    points[expected]
+                );
+            }
+            // the query point must be inside the (convex, CCW) polygon of the cell
+            if let Some(polygon) = &polygons[located] {
+                for (a, b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+                    let cross = (b[0] - a[0]) * (q[1] - a[1]) - (b[1] - a[1]) * (q[0] - a[0]);
+                    assert!(cross / distance(*a, *b) >= -raster.tolerance());
+                }
+            }
+        }
+    }
+    Ok(())
+}
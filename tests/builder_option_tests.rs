@@ -0,0 +1,970 @@
+use boostvoronoi::builder as VB;
+use boostvoronoi::{BvError, DegenerateKind};
+
+type I = i32;
+type F = f64;
+
+fn strict_build(points: &[[I; 2]], segments: &[[I; 4]]) -> Result<(), BvError> {
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.strict(true)
+        .with_vertices(VB::to_points::<I, I>(points).iter())?;
+    vb.with_segments(VB::to_segments::<I, I>(segments).iter())?;
+    let _ = vb.build()?;
+    Ok(())
+}
+
+fn degenerate_kind(result: Result<(), BvError>) -> Option<DegenerateKind> {
+    match result {
+        Err(BvError::DegenerateInput { kind, .. }) => Some(kind),
+        _ => None,
+    }
+}
+
+#[test]
+fn strict_1() -> Result<(), BvError> {
+    // general position input is accepted
+    strict_build(&[[0, 0], [10, 3], [4, 9], [7, -5]], &[])?;
+    strict_build(
+        &[[50, 51]],
+        &[[0, 0, 100, 3], [100, 3, 40, 90], [40, 90, 0, 0]],
+    )
+}
+
+#[test]
+fn strict_2() {
+    let r = strict_build(&[[0, 0], [5, 5], [0, 0]], &[]);
+    assert_eq!(degenerate_kind(r), Some(DegenerateKind::CoincidentPoints));
+    let r = strict_build(&[[0, 0]], &[[0, 0, 10, 3]]);
+    assert_eq!(degenerate_kind(r), Some(DegenerateKind::CoincidentPoints));
+}
+
+#[test]
+fn strict_3() {
+    let r = strict_build(&[[0, 0], [5, 5], [10, 10]], &[]);
+    assert_eq!(degenerate_kind(r), Some(DegenerateKind::CollinearPoints));
+}
+
+#[test]
+fn strict_4() {
+    let r = strict_build(&[[0, 0], [0, 5], [0, 10]], &[]);
+    assert_eq!(
+        degenerate_kind(r),
+        Some(DegenerateKind::VerticalCollinearSites)
+    );
+}
+
+#[test]
+fn strict_5() -> Result<(), BvError> {
+    // the default mode handles the degenerate input
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points::<I, I>(&[[0, 0], [5, 5], [10, 10], [0, 0]]).iter())?;
+    let _ = vb.build()?;
+    Ok(())
+}
+
+#[test]
+fn strict_6() {
+    // a polygon starting with a vertical segment hits the vertical special case
+    let r = strict_build(
+        &[],
+        &[
+            [200, 200, 200, 400],
+            [200, 400, 400, 400],
+            [400, 400, 400, 200],
+            [400, 200, 200, 200],
+        ],
+    );
+    assert_eq!(
+        degenerate_kind(r),
+        Some(DegenerateKind::VerticalCollinearSites)
+    );
+}
+
+#[test]
+fn strict_7() {
+    // an input point on the interior of a segment
+    let r = strict_build(&[[5, 3], [2, 8]], &[[0, 0, 10, 6]]);
+    assert_eq!(degenerate_kind(r), Some(DegenerateKind::PointOnSegment));
+    // a segment endpoint on the interior of another segment
+    let r = strict_build(&[], &[[0, 0, 10, 0], [5, 0, 7, 9]]);
+    assert_eq!(degenerate_kind(r), Some(DegenerateKind::PointOnSegment));
+    // a point on the line of the segment, but outside of it
+    assert!(strict_build(&[[15, 9], [2, 8]], &[[0, 0, 10, 6]]).is_ok());
+}
+
+#[test]
+fn snap_distance_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [100, 0], [3, 0], [0, 100], [101, 1], [1, 2]]);
+    let segments = VB::to_segments::<I, I>(&[[50, 50, 60, 60]]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_snap_distance(5.0).with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let output = vb.build()?;
+    assert_eq!(vb.snapped_sites(), &[(2, 0), (4, 1), (5, 0)]);
+    // three points and the segment (two endpoints + the segment itself) remain
+    assert_eq!(output.num_cells(), 6);
+    output.check_invariants()?;
+
+    // the snapped points no longer trip the strict coincident points check
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [1, 0]]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.strict(true)
+        .with_snap_distance(1.5)
+        .with_vertices(points.iter())?;
+    let _ = vb.build()?;
+    assert_eq!(vb.snapped_sites(), &[(2, 0)]);
+
+    // exact distance is not "closer than"
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_snap_distance(1.0).with_vertices(points.iter())?;
+    let _ = vb.build()?;
+    assert!(vb.snapped_sites().is_empty());
+    Ok(())
+}
+
+#[test]
+fn with_polygon_1() -> Result<(), BvError> {
+    let square = VB::to_points::<I, I>(&[[0, 0], [10, 0], [10, 10], [0, 10]]);
+    let polygon = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_polygon(&square, true)?;
+        vb.build()?
+    };
+    let by_hand = {
+        let segments = VB::to_segments::<I, I>(&[
+            [0, 0, 10, 0],
+            [10, 0, 10, 10],
+            [10, 10, 0, 10],
+            [0, 10, 0, 0],
+        ]);
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    assert_eq!(polygon.counts(), by_hand.counts());
+    for (a, b) in polygon.cell_iter().zip(by_hand.cell_iter()) {
+        assert_eq!(a.get().source_index_2(), b.get().source_index_2());
+    }
+
+    // an open polyline has no closing segment
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_polygon(&square, false)?;
+    assert_eq!(vb.build()?.num_cells(), 3 + 4);
+
+    let mut vb = VB::Builder::<I, F>::default();
+    assert!(vb.with_polygon(&square[0..2], false).is_ok());
+    assert!(matches!(
+        vb.with_polygon(&square[0..2], true),
+        Err(BvError::ValueError(_))
+    ));
+    assert!(matches!(
+        vb.with_polygon(&square[0..1], false),
+        Err(BvError::ValueError(_))
+    ));
+
+    // coincident consecutive vertices are rejected before anything is added
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_polygon(&square, true)?;
+    let closed_twice = VB::to_points::<I, I>(&[[2, 2], [8, 2], [8, 8], [2, 2]]);
+    match vb.with_polygon(&closed_twice, true) {
+        Err(BvError::DegenerateInput { kind, indices }) => {
+            assert_eq!(kind, DegenerateKind::ZeroLengthSegment);
+            assert_eq!(indices, vec![7]);
+        }
+        _ => panic!("expected a DegenerateInput error"),
+    }
+    let repeated = VB::to_points::<I, I>(&[[2, 2], [8, 2], [8, 2], [8, 8]]);
+    assert!(vb.with_polygon(&repeated, true).is_err());
+    assert_eq!(vb.dump_input().segments.len(), 4);
+
+    // several rings, a square with a triangular hole
+    let hole = VB::to_points::<I, I>(&[[2, 2], [8, 2], [8, 8]]);
+    vb.with_polygon(&hole, true)?;
+    let output = vb.build()?;
+    output.check_invariants()?;
+    assert_eq!(output.num_cells(), (4 + 4) + (3 + 3));
+    Ok(())
+}
+
+#[test]
+fn intersection_check_1() -> Result<(), BvError> {
+    let build = |segments: &[[I; 4]], method| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_intersection_check(method)
+            .with_vertices(VB::to_points::<I, I>(&[[50, 50]]).iter())?;
+        vb.with_segments(VB::to_segments::<I, I>(segments).iter())?;
+        vb.build()
+    };
+    for method in [
+        VB::IntersectionCheck::BruteForce,
+        VB::IntersectionCheck::SweepLine,
+    ]
+    .iter()
+    {
+        // a closed triangle is fine
+        let _ = build(&[[0, 0, 100, 0], [100, 0, 0, 100], [0, 100, 0, 0]], *method)?;
+        // a T-junction
+        let r = build(&[[0, 0, 100, 0], [50, 0, 50, 20]], *method);
+        match r {
+            Err(BvError::SelfIntersecting {
+                seg_a,
+                seg_b,
+                point,
+            }) => assert_eq!((seg_a, seg_b, point), (1, 2, [50.0, 0.0])),
+            _ => panic!("expected a SelfIntersecting error"),
+        }
+        // collinear overlap
+        let r = build(&[[0, 0, 100, 0], [120, 0, 60, 0]], *method);
+        assert!(matches!(
+            r,
+            Err(BvError::SelfIntersecting {
+                point: [60.0, 0.0],
+                ..
+            })
+        ));
+        // two crossing diagonals
+        let r = build(&[[0, 0, 100, 100], [0, 100, 100, 0]], *method);
+        assert!(matches!(
+            r,
+            Err(BvError::SelfIntersecting {
+                seg_a: 1,
+                seg_b: 2,
+                point: [50.0, 50.0],
+            })
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn max_safe_coordinate_1() -> Result<(), BvError> {
+    let max = VB::Builder::<i64, F>::max_safe_coordinate();
+    let mut vb = VB::Builder::<i64, F>::default();
+    vb.with_vertices(VB::to_points::<i64, i64>(&[[max, -max], [0, 0]]).iter())?;
+    let r = vb.with_vertices(VB::to_points::<i64, i64>(&[[max + 1, 0]]).iter());
+    assert!(matches!(r, Err(BvError::CoordinateOutOfRange(_))));
+    // the message tells which point failed
+    let message = r.unwrap_err().to_string();
+    assert!(message.contains(&format!("({},0)", max + 1)), "{}", message);
+    let r = vb.with_segments(VB::to_segments::<i64, i64>(&[[0, 0, 1, -max - 1]]).iter());
+    assert!(matches!(r, Err(BvError::CoordinateOutOfRange(_))));
+
+    let mut vb = VB::Builder::<I, F>::default();
+    let r = vb.with_vertices(VB::to_points::<I, I>(&[[0, I::MIN]]).iter());
+    assert!(matches!(r, Err(BvError::CoordinateOutOfRange(_))));
+    vb.with_vertices(VB::to_points::<I, I>(&[[I::MAX, -I::MAX]]).iter())?;
+    Ok(())
+}
+
+#[test]
+/// Input at the extremes of the i32 range gives valid vertices, not overflow or NaN
+fn max_safe_coordinate_2() -> Result<(), BvError> {
+    let max = I::MAX;
+    let points = VB::to_points::<I, I>(&[[-max, -max], [max, -max], [max, max], [0, 1], [3, -max]]);
+    let segments = VB::to_segments::<I, I>(&[[-max, max, -max, 0], [-max + 1, max, 0, max - 7]]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let output = vb.build()?;
+    output.check_invariants()?;
+    assert!(output.num_vertices() > 0);
+    for v in output.vertex_iter() {
+        let v = v.get();
+        assert!(v.x().is_finite() && v.y().is_finite(), "{:?}", v);
+    }
+    // i32::MIN is the only value out of range
+    let r = vb.with_segments(VB::to_segments::<I, I>(&[[0, 0, I::MIN, 0]]).iter());
+    assert!(matches!(r, Err(BvError::CoordinateOutOfRange(_))));
+    Ok(())
+}
+
+#[test]
+fn dump_input_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [5, 5]]);
+    // the second segment is given right to left
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 20, 0, 20]]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let input = vb.dump_input();
+    assert_eq!(input.points, points);
+    assert_eq!(input.segments, segments);
+
+    let mut replay = VB::Builder::<I, F>::default();
+    replay.load_input(&input)?;
+    assert_eq!(replay.dump_input(), input);
+
+    // a rejected segment leaves nothing behind, not even the points
+    let mut rejected = input.clone();
+    rejected
+        .segments
+        .extend(VB::to_segments::<I, I>(&[[7, 7, 7, 7]]));
+    let mut vb2 = VB::Builder::<I, F>::default();
+    match vb2.load_input(&rejected) {
+        Err(BvError::DegenerateInput { indices, .. }) => assert_eq!(indices, vec![3 + 2]),
+        _ => panic!("expected a DegenerateInput error"),
+    }
+    assert_eq!(vb2.dump_input(), VB::InputSet::default());
+    let (output, replay_output) = (vb.build()?, replay.build()?);
+    assert_eq!(output.counts(), replay_output.counts());
+    for (a, b) in output
+        .vertices()
+        .iter()
+        .zip(replay_output.vertices().iter())
+    {
+        assert_eq!((a.get().x(), a.get().y()), (b.get().x(), b.get().y()));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn dump_input_serde_1() -> Result<(), BvError> {
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points::<I, I>(&[[5, 5], [-3, 7]]).iter())?;
+    vb.with_segments(VB::to_segments::<I, I>(&[[10, 20, 0, 20]]).iter())?;
+    let input = vb.dump_input();
+    let json = serde_json::to_string(&input).unwrap();
+    let restored: VB::InputSet<I> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, input);
+    Ok(())
+}
+
+#[test]
+fn zero_length_segment_1() {
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points::<I, I>(&[[5, 5]]).iter())
+        .unwrap();
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [20, 20, 20, 20]]);
+    match vb.with_segments(segments.iter()) {
+        Err(BvError::DegenerateInput { kind, indices }) => {
+            assert_eq!(kind, DegenerateKind::ZeroLengthSegment);
+            assert_eq!(indices, vec![2]);
+        }
+        _ => panic!("expected a DegenerateInput error"),
+    }
+    // the valid segment before the rejected one is not added either
+    assert!(vb.dump_input().segments.is_empty());
+    vb.with_segments(segments[..1].iter()).unwrap();
+    assert_eq!(vb.dump_input().segments, segments[..1]);
+    let output = vb.build().unwrap();
+    assert_eq!(output.num_cells(), 1 + 3);
+    assert!(output.cells().iter().all(|c| c.get().source_index() <= 1));
+}
+
+#[test]
+fn dedup_points_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 3], [0, 0], [4, 9], [10, 3], [0, 0]]);
+    let segments = VB::to_segments::<I, I>(&[[20, 20, 30, 2]]);
+    let build = |dedup: bool| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.dedup_points(dedup).with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build_with_warnings()
+            .map(|(output, warnings)| (output, warnings, vb.snapped_sites().to_vec()))
+    };
+    let (output, warnings, snapped) = build(true)?;
+    output.check_invariants()?;
+    // 3 unique points, the segment and its 2 endpoints
+    assert_eq!(output.num_cells(), 6);
+    assert!(warnings.is_empty());
+    assert_eq!(snapped, vec![(2, 0), (4, 1), (5, 0)]);
+    // the first occurrence of every point is kept
+    let mut sources: Vec<usize> = output
+        .cells()
+        .iter()
+        .map(|c| c.get().source_index())
+        .collect();
+    sources.sort_unstable();
+    sources.dedup();
+    assert_eq!(sources, vec![0, 1, 3, 6]);
+
+    // without dedup the duplicates still collapse, but they are reported
+    let (plain, warnings, snapped) = build(false)?;
+    assert_eq!(plain.counts(), output.counts());
+    assert!(warnings
+        .iter()
+        .all(|w| w.kind == DegenerateKind::CoincidentPoints));
+    assert!(!warnings.is_empty());
+    assert!(snapped.is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "geo")]
+#[test]
+fn geo_input_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let expected = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let geo_points: geo::MultiPoint<f64> = vec![(5., 5.), (-3., 7.)].into();
+    let geo_lines = geo::MultiLineString(vec![
+        vec![(0., 0.), (10., 0.), (10., 10.)].into(),
+        vec![(20., 20.), (30., 2.)].into(),
+    ]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_geo_multipoint(&geo_points)?;
+    vb.with_geo_multilinestring(&geo_lines)?;
+    assert_eq!(vb.dump_input().points, points);
+    assert_eq!(vb.dump_input().segments, segments);
+    let output = vb.build()?;
+    assert_eq!(output.counts(), expected.counts());
+
+    // values that can't be represented exactly by I are rejected, and nothing is added
+    let mut vb = VB::Builder::<I, F>::default();
+    let fractional = geo::MultiLineString(vec![vec![(0., 0.), (10., 0.5)].into()]);
+    assert!(matches!(
+        vb.with_geo_multilinestring(&fractional),
+        Err(BvError::NumberConversion(_))
+    ));
+    let out_of_range: geo::MultiPoint<i64> = vec![(0, 0), (i64::MAX, 0)].into();
+    assert!(matches!(
+        vb.with_geo_multipoint(&out_of_range),
+        Err(BvError::NumberConversion(_))
+    ));
+    let nan: geo::MultiPoint<f64> = vec![(f64::NAN, 0.)].into();
+    assert!(vb.with_geo_multipoint(&nan).is_err());
+    assert!(vb.dump_input().points.is_empty());
+    assert!(vb.dump_input().segments.is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "never_recompute")]
+#[test]
+fn never_recompute_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [400, 3]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let build = |never_recompute: bool| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.never_recompute(never_recompute);
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()
+    };
+    let (exact, lazy) = (build(false)?, build(true)?);
+    assert_eq!(exact.counts(), lazy.counts());
+    for (a, b) in exact.vertices().iter().zip(lazy.vertices().iter()) {
+        let (a, b) = (a.get(), b.get());
+        assert!((a.x() - b.x()).abs() < 1e-6 && (a.y() - b.y()).abs() < 1e-6);
+    }
+    Ok(())
+}
+
+#[test]
+fn exactness_1() -> Result<(), BvError> {
+    // the segments of segment_4_1
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+        [680, 608, 669, 291],
+        [572, 96, 363, 51],
+        [147, 103, 96, 170],
+    ]);
+    let build = |exactness: VB::Exactness| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.exactness(exactness).with_segments(segments.iter())?;
+        vb.build()
+    };
+    let (full, lazy) = (build(VB::Exactness::Full)?, build(VB::Exactness::LazyOnly)?);
+    assert_eq!(lazy.stats().exact_circle_events, 0);
+    lazy.check_invariants()?;
+    assert_eq!(full.counts(), lazy.counts());
+    for v in lazy.vertices().iter() {
+        let v = v.get();
+        assert!(v.x().is_finite() && v.y().is_finite());
+    }
+    Ok(())
+}
+
+#[test]
+fn owned_input_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [0, 0, 10, 0],
+        [10, 0, 10, 10],
+        [20, 20, 30, 2],
+        [-5, 9, 2, 30],
+    ]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let borrowed = vb.build()?;
+
+    // yield the segments one at a time, without a slice to borrow from
+    let mut next = 0;
+    let generator = std::iter::from_fn(|| {
+        next += 1;
+        segments.get(next - 1).copied()
+    });
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices_owned(points.iter().copied())?;
+    vb.with_segments_owned(generator)?;
+    let owned = vb.build()?;
+
+    assert_eq!(borrowed.counts(), owned.counts());
+    for (a, b) in borrowed.vertices().iter().zip(owned.vertices().iter()) {
+        let (a, b) = (a.get(), b.get());
+        assert_eq!((a.x(), a.y()), (b.x(), b.y()));
+    }
+    for (a, b) in borrowed.cells().iter().zip(owned.cells().iter()) {
+        assert_eq!(a.get().source_index_2(), b.get().source_index_2());
+    }
+    Ok(())
+}
+
+fn farthest_point_build(
+    points: &[[I; 2]],
+) -> Result<boostvoronoi::diagram::Diagram<I, F>, BvError> {
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.farthest_point(true)
+        .with_vertices(VB::to_points::<I, I>(points).iter())?;
+    vb.build()
+}
+
+#[test]
+fn farthest_point_1() -> Result<(), BvError> {
+    // a rectangle that is not a square, with one point inside
+    let points = [[0, 0], [20, 0], [20, 10], [0, 10], [8, 4]];
+    let output = farthest_point_build(&points)?;
+    output.check_invariants()?;
+    for cell in output.cells().iter() {
+        let cell = cell.get();
+        // only the hull points have a cell
+        assert_eq!(cell.is_degenerate(), cell.source_index() == 4);
+    }
+    // the two vertices (co-circular corners) collapse into the center of the rectangle
+    assert_eq!(output.vertices().len(), 1);
+    assert_eq!(
+        output.vertex_position(boostvoronoi::diagram::VertexIndex(0))?,
+        [10.0, 5.0]
+    );
+    assert_eq!(output.edges().len(), 8);
+
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.farthest_point(true)
+        .with_segments(VB::to_segments::<I, I>(&[[0, 0, 10, 0]]).iter())?;
+    assert!(matches!(vb.build(), Err(BvError::ValueError(_))));
+    Ok(())
+}
+
+#[test]
+fn farthest_point_2() -> Result<(), BvError> {
+    let mut seed: u64 = 12345;
+    let mut random = |m: u64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % m) as I - (m / 2) as I
+    };
+    let distance = |a: [F; 2], b: [I; 2]| (a[0] - b[0] as F).hypot(a[1] - b[1] as F);
+    for _ in 0..20 {
+        let points: Vec<[I; 2]> = (0..30).map(|_| [random(1000), random(1000)]).collect();
+        let output = farthest_point_build(&points)?;
+        output.check_invariants()?;
+        let non_degenerate = output
+            .cells()
+            .iter()
+            .filter(|c| !c.get().is_degenerate())
+            .count();
+        // a tree with an unbounded edge between every pair of neighbouring hull points
+        assert_eq!(output.vertices().len(), non_degenerate - 2);
+        assert_eq!(output.edges().len(), (2 * non_degenerate - 3) * 2);
+
+        let site = |edge_id| -> Result<[I; 2], BvError> {
+            let cell = output
+                .get_cell(output.get_edge(edge_id)?.get().cell()?)?
+                .get();
+            Ok(points[cell.source_index()])
+        };
+        for edge in output.edges().iter() {
+            let edge = edge.get();
+            let (v0, v1) = output.edge_vertices(edge.id())?;
+            let (v0, v1) = match (v0, v1) {
+                (Some(v0), Some(v1)) => (output.vertex_position(v0)?, output.vertex_position(v1)?),
+                _ => continue,
+            };
+            // a point to the left of the edge is farther away from the site of the edge than
+            // from any other site
+            let length = (v1[0] - v0[0]).hypot(v1[1] - v0[1]);
+            if length < 1e-3 {
+                continue;
+            }
+            let offset = 1e-3 / length;
+            let probe = [
+                (v0[0] + v1[0]) / 2.0 - (v1[1] - v0[1]) * offset,
+                (v0[1] + v1[1]) / 2.0 + (v1[0] - v0[0]) * offset,
+            ];
+            let own = distance(probe, site(edge.id())?);
+            for p in points.iter() {
+                assert!(distance(probe, *p) <= own + 1e-9);
+            }
+        }
+        for vertex in output.vertices().iter() {
+            let vertex = vertex.get();
+            let position = [vertex.x(), vertex.y()];
+            let edge_id = vertex.get_incident_edge()?;
+            let radius = distance(position, site(edge_id)?);
+            // the circle through the sites of the vertex contains all the points
+            for p in points.iter() {
+                assert!(distance(position, *p) <= radius * (1.0 + 1e-9));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn identical_points_1() -> Result<(), BvError> {
+    for n in [1, 2, 3, 4, 50, 500] {
+        let points = vec![[5, 5]; n];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points::<I, I>(&points).iter())?;
+        let output = vb.build()?;
+        output.check_invariants()?;
+        // the duplicates collapse into a single cell without edges
+        assert_eq!(output.cells().len(), 1);
+        assert_eq!(output.cells()[0].get().source_index(), 0);
+        assert!(output.edges().is_empty());
+        assert!(output.vertices().is_empty());
+    }
+
+    // duplicates mixed with other points give the same diagram as the unique points
+    let unique: Vec<[I; 2]> = (0..40).map(|i| [(i * 7) % 23, (i * 13) % 29]).collect();
+    let mut duplicated = Vec::new();
+    for i in 0..3 {
+        duplicated.extend(unique.iter().skip(i));
+    }
+    let build = |points: &[[I; 2]]| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points::<I, I>(points).iter())?;
+        vb.build()
+    };
+    let (a, b) = (build(&unique)?, build(&duplicated)?);
+    b.check_invariants()?;
+    assert_eq!(a.counts(), b.counts());
+    for (a, b) in a.cells().iter().zip(b.cells().iter()) {
+        // the first occurrence of a point is kept
+        assert_eq!(a.get().source_index(), b.get().source_index());
+    }
+    Ok(())
+}
+
+#[test]
+fn build_with_warnings_1() -> Result<(), BvError> {
+    let warnings = |points: &[[I; 2]], segments: &[[I; 4]]| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points::<I, I>(points).iter())?;
+        vb.with_segments(VB::to_segments::<I, I>(segments).iter())?;
+        let (output, warnings) = vb.build_with_warnings()?;
+        output.check_invariants()?;
+        Ok::<_, BvError>(warnings)
+    };
+    // general position input
+    assert!(warnings(&[[0, 0], [10, 3], [4, 9], [7, -5]], &[])?.is_empty());
+
+    let w = warnings(&[[0, 0], [5, 5], [0, 0], [5, 5], [9, 2]], &[])?;
+    assert_eq!(w.len(), 2);
+    assert_eq!(w[0].kind, DegenerateKind::CoincidentPoints);
+    assert_eq!(w[0].indices, vec![0, 2]);
+    assert_eq!(w[1].indices, vec![1, 3]);
+
+    let w = warnings(&[[0, 0], [0, 5], [0, 10], [7, 3]], &[])?;
+    assert_eq!(w[0].kind, DegenerateKind::VerticalCollinearSites);
+    assert_eq!(w[0].indices, vec![0, 1, 2]);
+
+    let w = warnings(&[[0, 0], [5, 5], [10, 10], [20, 0]], &[])?;
+    assert!(w.iter().all(|w| w.kind == DegenerateKind::CollinearPoints));
+    assert!(!w.is_empty());
+
+    // four points on a circle of radius 5
+    let w = warnings(&[[3, 4], [4, -3], [-3, -4], [-4, 3]], &[])?;
+    assert_eq!(w.len(), 1);
+    assert_eq!(w[0].kind, DegenerateKind::CocircularSites);
+    assert_eq!(w[0].indices, vec![0, 1, 2, 3]);
+
+    let w = warnings(&[[5, 3], [2, 8]], &[[0, 0, 10, 6]])?;
+    // the point also gives rise to the other kinds of degenerate cases
+    assert_eq!(w[0].kind, DegenerateKind::PointOnSegment);
+    assert_eq!(w[0].indices, vec![2, 0]);
+
+    // strict mode still rejects the input
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.strict(true)
+        .with_vertices(VB::to_points::<I, I>(&[[0, 0], [5, 5], [0, 0]]).iter())?;
+    assert_eq!(
+        degenerate_kind(vb.build_with_warnings().map(|_| ())),
+        Some(DegenerateKind::CoincidentPoints)
+    );
+    Ok(())
+}
+
+#[test]
+fn tuple_input_1() -> Result<(), BvError> {
+    let square: Vec<(I, I, I, I)> = vec![
+        (0, 0, 10, 0),
+        (10, 0, 10, 10),
+        (10, 10, 0, 10),
+        (0, 10, 0, 0),
+    ];
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_segment_tuples(square.iter().copied())?;
+    let output = vb.build()?;
+    // 4 segments and 4 corners
+    assert_eq!(output.cells().len(), 8);
+
+    // the same result as the array helpers
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_point_tuples(vec![(3, 4), (20, 7)])?;
+    vb.with_segment_tuples(square)?;
+    let tuples = vb.build()?;
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points::<I, I>(&[[3, 4], [20, 7]]).iter())?;
+    vb.with_segments(
+        VB::to_segments::<I, I>(&[
+            [0, 0, 10, 0],
+            [10, 0, 10, 10],
+            [10, 10, 0, 10],
+            [0, 10, 0, 0],
+        ])
+        .iter(),
+    )?;
+    let arrays = vb.build()?;
+    assert_eq!(tuples.counts(), arrays.counts());
+    for (a, b) in tuples.vertices().iter().zip(arrays.vertices().iter()) {
+        assert_eq!(a.get().x(), b.get().x());
+        assert_eq!(a.get().y(), b.get().y());
+    }
+    Ok(())
+}
+
+#[test]
+fn scale_1() -> Result<(), BvError> {
+    // a unit square with a point inside, scaled by 1000
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_scale(1000.0)
+        .with_float_vertices(&[[0.25, 0.5004]])?;
+    vb.with_float_segments(&[
+        [0.0, 0.0, 1.0, 0.0],
+        [1.0, 0.0, 1.0, 1.0],
+        [1.0, 1.0, 0.0, 1.0],
+        [0.0, 1.0, 0.0, 0.0],
+    ])?;
+    assert_eq!(vb.scale(), 1000.0);
+    let input = vb.dump_input();
+    // the coordinates are rounded to the nearest integer
+    assert_eq!(input.points, VB::to_points::<I, I>(&[[250, 500]]));
+    assert_eq!(
+        input.segments,
+        VB::to_segments::<I, I>(&[
+            [0, 0, 1000, 0],
+            [1000, 0, 1000, 1000],
+            [1000, 1000, 0, 1000],
+            [0, 1000, 0, 0],
+        ])
+    );
+    let output = vb.build()?;
+    let reference = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.load_input(&input)?;
+        vb.build()?
+    };
+    output.check_invariants()?;
+    assert_eq!(output.counts(), reference.counts());
+    for (v, r) in output.vertices().iter().zip(reference.vertices().iter()) {
+        let (v, r) = (v.get(), r.get());
+        assert_eq!((v.x(), v.y()), (r.x(), r.y()));
+        // unscaled, the vertices are back in the units of the unit square
+        for c in [vb.unscale(v.x()), vb.unscale(v.y())] {
+            assert!((-1e-9..=1.0 + 1e-9).contains(&c));
+        }
+        assert!((vb.unscale(v.clearance()) - r.clearance() / 1000.0).abs() < 1e-12);
+    }
+
+    // out of range and invalid scale factors are rejected
+    let mut vb = VB::Builder::<I, F>::default();
+    assert!(matches!(
+        vb.with_scale(1e10).with_float_vertices(&[[1.0, 0.0]]),
+        Err(BvError::CoordinateOutOfRange(_))
+    ));
+    assert!(matches!(
+        vb.with_scale(0.0).with_float_vertices(&[[1.0, 0.0]]),
+        Err(BvError::ValueError(_))
+    ));
+    Ok(())
+}
+
+#[test]
+/// with_vertices() and with_segments() can be called in any order, any number of times
+fn ingestion_order_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[250, 300], [300, 250], [50, 60], [700, 20]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+    ]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let input = vb.dump_input();
+    let reference = vb.build()?;
+
+    let mut interleaved = Vec::new();
+    {
+        // segments first
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_segments(segments.iter())?;
+        vb.with_vertices(points.iter())?;
+        interleaved.push(vb);
+    }
+    {
+        // several calls of each, mixed
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_segments(segments[..2].iter())?;
+        vb.with_vertices(points[..1].iter())?;
+        vb.with_segments(segments[2..3].iter())?;
+        vb.with_vertices(points[1..3].iter())?;
+        vb.with_vertices([].iter())?;
+        vb.with_segments(segments[3..].iter())?;
+        vb.with_vertices(points[3..].iter())?;
+        interleaved.push(vb);
+    }
+    for mut vb in interleaved {
+        assert_eq!(vb.dump_input(), input);
+        let output = vb.build()?;
+        output.check_invariants()?;
+        assert_eq!(output.counts(), reference.counts());
+        for (c, r) in output.cells().iter().zip(reference.cells().iter()) {
+            let (c, r) = (c.get(), r.get());
+            assert_eq!(c.source_index(), r.source_index());
+            assert_eq!(c.source_category(), r.source_category());
+        }
+        for (v, r) in output.vertices().iter().zip(reference.vertices().iter()) {
+            let (v, r) = (v.get(), r.get());
+            assert_eq!((v.x(), v.y()), (r.x(), r.y()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+/// The builder keeps the input after build(), more input can be added and built again
+fn rebuild_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[250, 300], [300, 250], [301, 250]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+    ]);
+    let extra = VB::to_segments::<I, I>(&[[529, 242, 367, 107]]);
+
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_snap_distance(2.0);
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let input = vb.dump_input();
+    let first = vb.build()?;
+    // the input is intact, and building again gives the same diagram
+    assert_eq!(vb.dump_input(), input);
+    assert_eq!(vb.build()?.counts(), first.counts());
+    assert_eq!(vb.snapped_sites(), &[(2, 1)]);
+
+    // one more segment adds three cells: the segment and its two endpoints
+    vb.with_segments(extra.iter())?;
+    let second = vb.build()?;
+    second.check_invariants()?;
+    assert_eq!(second.num_cells(), first.num_cells() + 3);
+    assert_eq!(vb.snapped_sites(), &[(2, 1)]);
+
+    // identical to building all of the input at once
+    let reference = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_snap_distance(2.0);
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter().chain(extra.iter()))?;
+        vb.build()?
+    };
+    assert_eq!(second.counts(), reference.counts());
+    for (v, r) in second.vertices().iter().zip(reference.vertices().iter()) {
+        let (v, r) = (v.get(), r.get());
+        assert_eq!((v.x(), v.y()), (r.x(), r.y()));
+    }
+    Ok(())
+}
+
+#[test]
+/// Duplicate input sites are removed by the sweep, but they are kept in the builder
+fn rebuild_2() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [12, 14], [5, 5], [-3, 7]]);
+    let segments = VB::to_segments::<I, I>(&[[20, 0, 30, 10], [20, 0, 30, 10]]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let input = vb.dump_input();
+    let first = vb.build()?;
+    assert_eq!(vb.dump_input(), input);
+    let second = vb.build()?;
+    assert_eq!(second.counts(), first.counts());
+    for (a, b) in first.cells().iter().zip(second.cells().iter()) {
+        assert_eq!(a.get().source_index(), b.get().source_index());
+    }
+    Ok(())
+}
+
+#[test]
+fn site_events_1() -> Result<(), BvError> {
+    let segments = VB::to_segments::<I, I>(&[[200, 400, 200, 200], [94, 628, 512, 632]]);
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_segments(segments.iter())?;
+    let sites = vb.site_events();
+    for site in sites.iter() {
+        println!(
+            "#{} {:?}-{:?} segment:{} inverse:{} source:{}",
+            site.sorted_index(),
+            site.point0(),
+            site.point1(),
+            site.is_segment(),
+            site.is_inverse(),
+            site.initial_index()
+        );
+    }
+    assert_eq!(sites.len(), 6);
+    for (i, site) in sites.iter().enumerate() {
+        assert_eq!(site.sorted_index(), i);
+        assert!(!site.is_inverse());
+        if site.is_segment() {
+            // the segment sites go from the lowest to the highest endpoint
+            let s = segments[site.initial_index()];
+            let (low, high) = if (s.start.x, s.start.y) < (s.end.x, s.end.y) {
+                (s.start, s.end)
+            } else {
+                (s.end, s.start)
+            };
+            assert_eq!((*site.point0(), *site.point1()), (low, high));
+        } else {
+            assert_eq!(site.point0(), site.point1());
+        }
+    }
+    assert_eq!(sites.iter().filter(|s| s.is_segment()).count(), 2);
+    // sorted by x
+    assert!(sites.windows(2).all(|w| w[0].x0() <= w[1].x0()));
+
+    // the builder is not modified, the cells are created in the same order
+    let output = vb.build()?;
+    assert_eq!(output.num_cells(), sites.len());
+    for (cell, site) in output.cell_iter().zip(sites.iter()) {
+        let cell = cell.get();
+        assert_eq!(cell.source_index(), site.initial_index());
+        assert_eq!(cell.contains_segment(), site.is_segment());
+    }
+    Ok(())
+}
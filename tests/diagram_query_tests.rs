@@ -0,0 +1,1966 @@
+use boostvoronoi::builder as VB;
+use boostvoronoi::diagram as VD;
+use boostvoronoi::BvError;
+
+type I = i32;
+type F = f64;
+
+#[test]
+fn edges_by_length_1() -> Result<(), BvError> {
+    let output = {
+        let points: [[I; 2]; 5] = [[0, 0], [10, 0], [0, 10], [10, 10], [4, 6]];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.build()?
+    };
+    let edges = output.edges_by_length(0.0);
+    assert!(!edges.is_empty());
+    // one half-edge per twin pair, only finite edges
+    for (edge_id, _) in edges.iter() {
+        assert!(output.edge_is_finite(*edge_id)?);
+        let twin = output.edge_get_twin(*edge_id)?;
+        assert!(!edges.iter().any(|(e, _)| *e == twin));
+    }
+    for w in edges.windows(2) {
+        assert!(w[0].1 <= w[1].1);
+    }
+    // a tolerance larger than any edge puts every edge in the same bucket
+    let edges = output.edges_by_length(1000.0);
+    for w in edges.windows(2) {
+        assert!(w[0].0 .0 < w[1].0 .0);
+    }
+    Ok(())
+}
+
+#[test]
+fn vertex_kind_1() -> Result<(), BvError> {
+    let output = {
+        let points: [[I; 2]; 5] = [[0, 0], [10, 0], [0, 10], [10, 10], [4, 6]];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.build()?
+    };
+    assert!(output.num_vertices() > 0);
+    for v in output.vertex_iter() {
+        assert_eq!(v.get().kind(), VD::VertexKind::PPP);
+    }
+    Ok(())
+}
+
+#[test]
+fn vertex_kind_2() -> Result<(), BvError> {
+    let output = {
+        let points: [[I; 2]; 1] = [[250, 300]];
+        let segments: [[I; 4]; 4] = [
+            [200, 200, 200, 400],
+            [200, 400, 400, 400],
+            [400, 400, 400, 200],
+            [400, 200, 200, 200],
+        ];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.with_segments(VB::to_segments(&segments).iter())?;
+        vb.build()?
+    };
+    let kinds: Vec<VD::VertexKind> = output.vertex_iter().map(|v| v.get().kind()).collect();
+    // every vertex is generated by a point (an input point or a segment endpoint) and two segments
+    assert!(!kinds.is_empty());
+    assert!(kinds.iter().all(|k| *k == VD::VertexKind::PSS));
+    Ok(())
+}
+
+#[test]
+fn delaunay_1() -> Result<(), BvError> {
+    // four cocircular points and the center
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    let triangles = VB::delaunay(&points)?;
+    assert_eq!(triangles.len(), 4);
+    for t in triangles.iter() {
+        assert!(t.contains(&4));
+    }
+    // four cocircular points, the vertex is split into two triangles
+    let triangles = VB::delaunay(&points[0..4])?;
+    assert_eq!(triangles.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn delaunay_2() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [5, 5], [10, 10], [15, 15]]);
+    assert!(VB::delaunay(&points)?.is_empty());
+    assert!(VB::delaunay::<I>(&[])?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn cell_edges_from_1() -> Result<(), BvError> {
+    let output = {
+        let points: [[I; 2]; 5] = [[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.build()?
+    };
+    for cell in output.cell_iter() {
+        let cell = cell.get();
+        let edges: Vec<VD::EdgeIndex> = output.cell_edge_iterator(cell.id()).collect();
+        for (i, start_edge) in edges.iter().enumerate() {
+            let rotated: Vec<VD::EdgeIndex> = output.cell_edges_from(*start_edge).collect();
+            assert_eq!(rotated.len(), edges.len());
+            for (j, e) in rotated.iter().enumerate() {
+                assert_eq!(*e, edges[(i + j) % edges.len()]);
+                assert_eq!(output.edge_get_cell(*e)?, cell.id());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn triangle_area(v: &[[F; 2]], t: &[u32; 3]) -> F {
+    let [a, b, c] = [v[t[0] as usize], v[t[1] as usize], v[t[2] as usize]];
+    ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])) / 2.0
+}
+
+#[test]
+fn triangulate_cells_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let (vertices, triangles) = output.triangulate_cells(0.1)?;
+    // only the center cell is finite, it's a diamond with area 50
+    assert_eq!(triangles.len(), 4);
+    let area: F = triangles.iter().map(|t| triangle_area(&vertices, t)).sum();
+    assert!((area - 50.0).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn triangulate_cells_2() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[300, 300]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let (vertices, triangles) = output.triangulate_cells(0.1)?;
+    // the parabolic edges of the center point cell are discretized
+    assert!(triangles.len() > 4);
+    for t in triangles.iter() {
+        assert!(triangle_area(&vertices, t) > 0.0);
+    }
+    Ok(())
+}
+
+#[test]
+fn counts_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 10], [4, 6]]);
+    let segments = VB::to_segments::<I, I>(&[[20, 0, 30, 10], [30, 10, 40, 0], [-10, 5, -5, 20]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let counts = output.counts();
+    assert_eq!(counts.cells, output.num_cells());
+    assert_eq!(counts.edges, output.num_edges());
+    assert_eq!(counts.vertices, output.num_vertices());
+
+    let estimate = VD::Diagram::<I, F>::estimate_counts(points.len(), segments.len());
+    assert!(counts.cells <= estimate.cells);
+    assert!(counts.edges <= estimate.edges);
+    assert!(counts.vertices <= estimate.vertices);
+    assert_eq!(
+        VD::Diagram::<I, F>::estimate_counts(0, 0),
+        VD::Counts::default()
+    );
+    Ok(())
+}
+
+#[test]
+fn prune_edges_shorter_than_1() -> Result<(), BvError> {
+    // almost cocircular points, there is a short edge between two vertices close to (5,5)
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 11], [20, 5]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let short_edges = output
+        .edges_by_length(0.0)
+        .iter()
+        .filter(|(_, l)| *l < 1.0)
+        .count();
+    assert_eq!(short_edges, 1);
+
+    let pruned = output.prune_edges_shorter_than(1.0);
+    pruned.check_invariants()?;
+    assert_eq!(pruned.num_cells(), output.num_cells());
+    assert_eq!(pruned.num_edges(), output.num_edges() - 2);
+    assert_eq!(pruned.num_vertices(), output.num_vertices() - 1);
+    assert!(pruned.edges_by_length(0.0).iter().all(|(_, l)| *l >= 1.0));
+    // every vertex is still the origin of the edges around it
+    for v in pruned.vertex_iter() {
+        let v = v.get();
+        let incident_edge = v.get_incident_edge()?;
+        for e in pruned.edge_rot_next_iterator(incident_edge) {
+            assert_eq!(pruned.edge_get_vertex0(e)?, Some(v.get_id()));
+        }
+    }
+    // the source diagram is unchanged
+    output.check_invariants()?;
+    assert_eq!(
+        output.prune_edges_shorter_than(0.0).num_edges(),
+        output.num_edges()
+    );
+    Ok(())
+}
+
+#[test]
+fn weld_vertices_1() -> Result<(), BvError> {
+    // almost cocircular points, two vertices at (50,50) and (50.5,50.5)
+    let points = VB::to_points::<I, I>(&[[0, 0], [100, 0], [0, 100], [100, 101]]);
+    let output = || {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()
+    };
+    let mut welded = output()?;
+    assert_eq!(welded.num_vertices(), 2);
+    assert_eq!(welded.num_edges(), 10);
+    welded.weld_vertices(0.5);
+    assert_eq!(welded.num_vertices(), 2);
+
+    welded.weld_vertices(1.0);
+    welded.check_invariants()?;
+    assert_eq!(welded.num_cells(), 4);
+    assert_eq!(welded.num_vertices(), 1);
+    assert_eq!(welded.num_edges(), 8);
+    let v = welded.vertices()[0].get();
+    assert!((v.x() - 50.25).abs() < 1e-9 && (v.y() - 50.25).abs() < 1e-9);
+    // the clearance is the distance to the closest of the four sites, [100,0] and [0,100]
+    assert!((v.clearance() - F::hypot(49.75, 50.25)).abs() < 1e-9);
+    assert_eq!(v.kind(), VD::VertexKind::PPP);
+    assert!(v.generating_sites().is_none());
+    for e in welded.edges().iter() {
+        let e = e.get();
+        assert!(!welded.edge_is_finite(e.id())?);
+        assert_eq!(welded.get_edge(e.prev()?)?.get().next()?, e.id());
+        assert_eq!(welded.get_edge(e.next()?)?.get().prev()?, e.id());
+        let twin = e.twin()?;
+        assert_eq!(
+            e.vertex0().is_some(),
+            welded.get_edge(twin)?.get().vertex0().is_none()
+        );
+    }
+    for c in welded.cell_iter() {
+        assert_eq!(welded.cell_edge_iterator(c.get().id()).count(), 2);
+    }
+    let incident_edge = v.get_incident_edge()?;
+    assert_eq!(welded.edge_rot_next_iterator(incident_edge).count(), 4);
+    for e in welded.edge_rot_next_iterator(incident_edge) {
+        assert_eq!(welded.edge_get_vertex0(e)?, Some(v.get_id()));
+    }
+    Ok(())
+}
+
+#[test]
+fn vertex_degree_1() -> Result<(), BvError> {
+    let output = {
+        // four cocircular points
+        let points: [[I; 2]; 4] = [[0, 0], [10, 0], [0, 10], [10, 10]];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.build()?
+    };
+    assert_eq!(output.num_vertices(), 1);
+    assert_eq!(output.vertex_degree(VD::VertexIndex(0))?, 4);
+    assert!(output.vertex_degree(VD::VertexIndex(1)).is_err());
+
+    let output = {
+        let points: [[I; 2]; 5] = [[0, 0], [10, 0], [0, 10], [10, 11], [20, 5]];
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.build()?
+    };
+    for v in output.vertex_iter() {
+        assert_eq!(output.vertex_degree(v.get().get_id())?, 3);
+    }
+    Ok(())
+}
+
+#[test]
+fn segment_medial_edges_1() -> Result<(), BvError> {
+    let square = VB::to_points::<I, I>(&[[0, 0], [10, 0], [10, 10], [0, 10]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_polygon(&square, true)?;
+        vb.build()?
+    };
+    for segment_index in 0..4 {
+        let edges = output.segment_medial_edges(segment_index)?;
+        // the two half-diagonals of the square surrounding the segment
+        assert_eq!(edges.len(), 2);
+        for e in edges {
+            let [x0, y0, x1, y1] = output.edge_as_line(e)?;
+            assert!(x0.min(x1) >= 0.0 && x0.max(x1) <= 10.0);
+            assert!(y0.min(y1) >= 0.0 && y0.max(y1) <= 10.0);
+            assert!((x1 - x0).abs() == (y1 - y0).abs());
+        }
+    }
+    assert!(output.segment_medial_edges(4).is_err());
+
+    // color_exterior_edges() colors every edge but the medial ones
+    output.color_exterior_edges(1);
+    let mut interior: Vec<_> = output
+        .edges()
+        .iter()
+        .filter(|e| e.get().get_color() & 1 == 0)
+        .map(|e| e.get().id().0)
+        .collect();
+    let mut medial: Vec<_> = (0..4)
+        .map(|s| output.segment_medial_edges(s))
+        .collect::<Result<Vec<_>, _>>()?
+        .concat()
+        .iter()
+        .map(|e| e.0)
+        .collect();
+    interior.sort_unstable();
+    medial.sort_unstable();
+    assert_eq!(interior, medial);
+
+    // an open polyline has no interior
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_polygon(&square, false)?;
+        vb.build()?
+    };
+    assert!(output.segment_medial_edges(0)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn edge_is_left_of_segment_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [5, -5]]);
+    for (segments, left_point) in [
+        (VB::to_segments::<I, I>(&[[0, 0, 10, 0]]), 0),
+        (VB::to_segments::<I, I>(&[[10, 0, 0, 0]]), 1),
+    ]
+    .iter()
+    {
+        let output = {
+            let mut vb = VB::Builder::<I, F>::default();
+            vb.with_vertices(points.iter())?;
+            vb.with_segments(segments.iter())?;
+            vb.build()?
+        };
+        let mut found = 0;
+        for edge in output.edge_iter() {
+            let edge_id = edge.get().id();
+            let cell = output.get_cell(output.edge_get_cell(edge_id)?)?.get();
+            if !cell.contains_point() || cell.source_index() >= points.len() {
+                continue;
+            }
+            let twin_cell_id = output.edge_get_cell(output.edge_get_twin(edge_id)?)?;
+            if !output.get_cell(twin_cell_id)?.get().contains_segment() {
+                continue;
+            }
+            // the parabolic arc between an input point and the segment
+            let is_left = output.edge_is_left_of_segment(edge_id)?;
+            assert_eq!(is_left, Some(cell.source_index() == *left_point));
+            // the twin, in the segment cell, is on the same side
+            let twin_is_left = output.edge_is_left_of_segment(output.edge_get_twin(edge_id)?)?;
+            assert_eq!(twin_is_left, is_left);
+            found += 1;
+        }
+        assert_eq!(found, 2);
+        // the secondary edges cross the segment line at the endpoints
+        for edge in output.edge_iter().filter(|e| e.get().is_secondary()) {
+            assert_eq!(output.edge_is_left_of_segment(edge.get().id())?, None);
+        }
+    }
+    // edges not touching a segment cell
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    for edge in output.edge_iter() {
+        assert_eq!(output.edge_is_left_of_segment(edge.get().id())?, None);
+    }
+    Ok(())
+}
+
+#[test]
+fn edge_segment_side_1() -> Result<(), BvError> {
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0]]);
+    let build = |points: &[boostvoronoi::geometry::Point<I>]| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()
+    };
+    let segment_cell_sides = |output: &VD::Diagram<I, F>| {
+        let cell_id = output
+            .cells()
+            .iter()
+            .find(|c| c.get().contains_segment())
+            .unwrap()
+            .get()
+            .id();
+        output
+            .cell_edge_iterator(cell_id)
+            .map(|e| output.edge_segment_side(e))
+            .collect::<Result<Vec<_>, BvError>>()
+    };
+
+    // a lone segment: one cell, bounded by the two perpendicular lines through the endpoints
+    let output = build(&[])?;
+    assert_eq!(output.cells().len(), 3);
+    assert_eq!(segment_cell_sides(&output)?, vec![None, None]);
+
+    // a point on each side, the segment cell gets a parabolic arc on either side, and the
+    // secondary edges at the endpoints cross the segment line
+    let points = VB::to_points::<I, I>(&[[5, 5], [5, -5]]);
+    let output = build(&points)?;
+    let sides = segment_cell_sides(&output)?;
+    assert_eq!(sides.len(), 4);
+    assert_eq!(sides.iter().filter(|s| s.is_none()).count(), 2);
+    assert_eq!(
+        sides
+            .iter()
+            .filter(|s| **s == Some(VD::SegmentSide::Left))
+            .count(),
+        1
+    );
+    assert_eq!(
+        sides
+            .iter()
+            .filter(|s| **s == Some(VD::SegmentSide::Right))
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn cell_diameter_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    for cell in output.cell_iter() {
+        let cell = cell.get();
+        let diameter = output.cell_diameter(cell.id(), 0.1)?;
+        if cell.source_index() == 4 {
+            // the center cell is a diamond with diagonals of length 10
+            assert!((diameter.unwrap() - 10.0).abs() < 1e-9);
+        } else {
+            assert!(diameter.is_none());
+        }
+    }
+
+    // a point inside a square of segments, the point cell is bounded by parabolic arcs
+    let points = VB::to_points::<I, I>(&[[300, 300]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let cell = output
+        .cell_iter()
+        .map(|c| c.get())
+        .find(|c| c.contains_point() && c.source_index() == 0)
+        .unwrap();
+    let diameter = output.cell_diameter(cell.id(), 0.01)?.unwrap();
+    // the cell corners are on the diagonals, 100/(1+1/sqrt(2)) from the center point
+    let corner = 100.0 / (1.0 + 1.0 / 2_f64.sqrt());
+    assert!((diameter - 2.0 * corner).abs() < 0.02);
+    Ok(())
+}
+
+#[test]
+fn vertices_in_creation_order_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 11], [20, 5], [4, 6]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let order: Vec<VD::VertexIndex> = output.vertices_in_creation_order().collect();
+    assert_eq!(order.len(), output.num_vertices());
+    let sequence = order
+        .iter()
+        .map(|v| Ok(output.vertex_get(*v)?.get().creation_sequence()))
+        .collect::<Result<Vec<usize>, BvError>>()?;
+    assert!(sequence.windows(2).all(|w| w[0] < w[1]));
+    // circle events are processed in the order of the lower x of their circles
+    let lower_x = |v: &VD::VertexIndex| -> Result<F, BvError> {
+        let v = output.vertex_get(*v)?.get();
+        let e = output.vertex_get_incident_edge(Some(v.get_id())).unwrap();
+        let p = points[output
+            .get_cell(output.edge_get_cell(e)?)?
+            .get()
+            .source_index()];
+        Ok(v.x() + (v.x() - p.x as F).hypot(v.y() - p.y as F))
+    };
+    for w in order.windows(2) {
+        assert!(lower_x(&w[0])? <= lower_x(&w[1])? + 1e-9);
+    }
+    Ok(())
+}
+
+#[test]
+fn edge_vertices_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 11], [20, 5]]);
+    let segments = VB::to_segments::<I, I>(&[[-10, -10, -10, 20]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let mut infinite = 0;
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        let (v0, v1) = output.edge_vertices(edge.id())?;
+        assert_eq!(v0, edge.vertex0());
+        assert_eq!(v0, output.edge_get_vertex0(edge.id())?);
+        assert_eq!(v1, output.edge_get_vertex1(edge.id())?);
+        // the twin goes the other way
+        assert_eq!(output.edge_vertices(edge.twin()?)?, (v1, v0));
+        // the end of an edge is the start of the next edge
+        assert_eq!(
+            v1,
+            output.edge_get_vertex0(output.edge_get_next(edge.id())?)?
+        );
+        assert_eq!(
+            output.edge_is_finite(edge.id())?,
+            v0.is_some() && v1.is_some()
+        );
+        if v0.is_none() || v1.is_none() {
+            infinite += 1;
+        }
+    }
+    assert!(infinite > 0);
+    assert!(output
+        .edge_vertices(VD::EdgeIndex(output.edges().len()))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn iter_cells_with_sites_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 12]]);
+    // the second segment is given right to left, the third one top to bottom
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 20, 0, 20], [20, 20, 20, 0]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let sites: Vec<(VD::CellIndex, VD::SiteGeometry<I>)> = output.iter_cells_with_sites().collect();
+    assert_eq!(sites.len(), output.cells().len());
+    for (cell_id, site) in sites {
+        assert_eq!(output.cell_site_geometry(cell_id)?, site);
+        let (index, category) = output.get_cell(cell_id)?.get().source_index_2();
+        let expected = match category {
+            VD::SourceCategory::SinglePoint => VD::SiteGeometry::Point(points[index]),
+            VD::SourceCategory::SegmentStart => {
+                VD::SiteGeometry::Point(segments[index - points.len()].start)
+            }
+            VD::SourceCategory::SegmentEnd => {
+                VD::SiteGeometry::Point(segments[index - points.len()].end)
+            }
+            VD::SourceCategory::Segment => {
+                VD::SiteGeometry::Segment(segments[index - points.len()])
+            }
+        };
+        assert_eq!(site, expected);
+    }
+    assert!(output
+        .cell_site_geometry(VD::CellIndex(output.cells().len()))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn bridges_1() -> Result<(), BvError> {
+    // points in convex position: the finite edges form a tree, every edge is a bridge
+    let mut points =
+        VB::to_points::<I, I>(&[[0, 0], [10, -1], [21, 4], [22, 15], [9, 21], [-2, 12]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let mut finite: Vec<VD::EdgeIndex> = output
+        .edges_by_length(0.0)
+        .into_iter()
+        .map(|(e, _)| e)
+        .collect();
+    finite.sort_unstable_by_key(|e| e.0);
+    assert!(!finite.is_empty());
+    assert_eq!(output.bridges(), finite);
+
+    // a point in the middle gets a closed cell, its edges are on a cycle
+    points.push(VB::to_points::<I, I>(&[[10, 9]])[0]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let bridges = output.bridges();
+    let middle = output
+        .cells()
+        .iter()
+        .find(|c| c.get().source_index() == 6)
+        .unwrap()
+        .get();
+    for edge_id in output.cell_edge_iterator(middle.id()) {
+        let twin_id = output.edge_get_twin(edge_id)?;
+        assert!(!bridges.contains(&edge_id) && !bridges.contains(&twin_id));
+    }
+    for edge_id in bridges.iter() {
+        assert!(output.edge_is_finite(*edge_id)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn edge_is_linear_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let mut curved = 0;
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        let cell = output.get_cell(edge.cell()?)?.get();
+        let twin_cell = output
+            .get_cell(output.get_edge(edge.twin()?)?.get().cell()?)?
+            .get();
+        let point_and_segment = cell.contains_segment() != twin_cell.contains_segment();
+        assert_eq!(edge.is_curved(), edge.is_primary() && point_and_segment);
+        assert_eq!(edge.is_linear(), !edge.is_curved());
+        if edge.is_curved() {
+            curved += 1;
+        }
+    }
+    assert!(curved > 0);
+    Ok(())
+}
+
+#[test]
+fn vertex_position_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    for (i, vertex) in output.vertices().iter().enumerate() {
+        let vertex = vertex.get();
+        let vertex_id = VD::VertexIndex(i);
+        assert_eq!(output.vertex_position(vertex_id)?, [vertex.x(), vertex.y()]);
+    }
+    let vertex_count = output.vertices().len();
+    assert!(output
+        .vertex_position(VD::VertexIndex(vertex_count))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn edge_radius_profile_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let distance = |site: VD::SiteGeometry<I>, p: [F; 2]| match site {
+        VD::SiteGeometry::Point(s) => (p[0] - s.x as F).hypot(p[1] - s.y as F),
+        VD::SiteGeometry::Segment(s) => {
+            let (x0, y0) = (s.start.x as F, s.start.y as F);
+            let (dx, dy) = (s.end.x as F - x0, s.end.y as F - y0);
+            let t = (((p[0] - x0) * dx + (p[1] - y0) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+            (p[0] - x0 - t * dx).hypot(p[1] - y0 - t * dy)
+        }
+    };
+    let mut curved = 0;
+    let mut linear = 0;
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        let profile = match output.edge_radius_profile(edge.id(), 7) {
+            Ok(profile) => profile,
+            Err(_) => {
+                assert!(edge.is_secondary() || !output.edge_is_finite(edge.id())?);
+                continue;
+            }
+        };
+        if edge.is_curved() {
+            curved += 1;
+        } else {
+            linear += 1;
+        }
+        assert_eq!(profile.len(), 7);
+        let (v0, v1) = output.edge_vertices(edge.id())?;
+        let v0 = output.vertex_position(v0.unwrap())?;
+        let v1 = output.vertex_position(v1.unwrap())?;
+        for (expected, actual) in [(v0, profile[0].0), (v1, profile[6].0)] {
+            assert!((expected[0] - actual[0]).hypot(expected[1] - actual[1]) < 1e-6);
+        }
+        let site = output.cell_site_geometry(edge.cell()?)?;
+        let twin_site = output.cell_site_geometry(output.get_edge(edge.twin()?)?.get().cell()?)?;
+        for (position, radius) in profile {
+            assert!((distance(site, position) - radius).abs() < 1e-6);
+            assert!((distance(twin_site, position) - radius).abs() < 1e-6);
+            for (_, other) in output.iter_cells_with_sites() {
+                assert!(distance(other, position) >= radius - 1e-6);
+            }
+        }
+    }
+    assert!(curved > 0 && linear > 0);
+    let infinite = output
+        .edges()
+        .iter()
+        .find(|e| !output.edge_is_finite(e.get().id()).unwrap())
+        .unwrap()
+        .get();
+    assert!(output.edge_radius_profile(infinite.id(), 7).is_err());
+    Ok(())
+}
+
+#[test]
+fn analyze_cells_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [4, 20], [16, 3]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let analysis = output.analyze_cells(0.01).collect::<Result<Vec<_>, _>>()?;
+    assert!(!analysis.is_empty());
+
+    // the triangle fans of the same cells cover the same area
+    let (vertices, triangles) = output.triangulate_cells(0.01)?;
+    let triangle_area: F = triangles
+        .iter()
+        .map(|t| {
+            let [a, b, c] = [
+                vertices[t[0] as usize],
+                vertices[t[1] as usize],
+                vertices[t[2] as usize],
+            ];
+            ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) / 2.0
+        })
+        .sum();
+    let area: F = analysis.iter().map(|a| a.area).sum();
+    assert!((area - triangle_area).abs() < 1e-6 * area);
+
+    for a in analysis.iter() {
+        assert!(a.area > 0.0);
+        let cell = output.get_cell(a.cell_id)?.get();
+        assert!(!cell.is_degenerate());
+        // the perimeter is at least the sum of the straight edges
+        let mut straight = 0.0;
+        for edge_id in output.cell_edge_iterator(a.cell_id) {
+            let line = output.edge_as_line(edge_id)?;
+            straight += (line[2] - line[0]).hypot(line[3] - line[1]);
+        }
+        assert!(a.perimeter >= straight - 1e-9);
+        assert!(a.perimeter <= straight * 1.1);
+    }
+    Ok(())
+}
+
+#[test]
+fn edge_bisected_geometry_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let is_endpoint = |p: &boostvoronoi::geometry::Point<I>,
+                       s: &boostvoronoi::geometry::Line<I>| {
+        *p == s.start || *p == s.end
+    };
+    // point-point, point-segment (primary), segment endpoint-segment (secondary), segment-segment
+    let mut found = [false; 4];
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        let (a, b) = output.edge_bisected_geometry(edge.id())?;
+        assert_eq!(a, output.cell_site_geometry(edge.cell()?)?);
+        // the twin sees the same sites in the other order
+        assert_eq!(output.edge_bisected_geometry(edge.twin()?)?, (b, a));
+        let kind = match (a, b) {
+            (VD::SiteGeometry::Point(p), VD::SiteGeometry::Point(q)) => {
+                assert_ne!(p, q);
+                assert!(edge.is_primary() && edge.is_linear());
+                0
+            }
+            (VD::SiteGeometry::Point(p), VD::SiteGeometry::Segment(s))
+            | (VD::SiteGeometry::Segment(s), VD::SiteGeometry::Point(p)) => {
+                if is_endpoint(&p, &s) {
+                    assert!(edge.is_secondary() && edge.is_linear());
+                    2
+                } else {
+                    assert!(edge.is_primary() && edge.is_curved());
+                    1
+                }
+            }
+            (VD::SiteGeometry::Segment(s), VD::SiteGeometry::Segment(t)) => {
+                assert!(segments.contains(&s) && segments.contains(&t));
+                assert!(edge.is_primary() && edge.is_linear());
+                3
+            }
+        };
+        found[kind] = true;
+    }
+    assert_eq!(found, [true; 4]);
+    assert!(output
+        .edge_bisected_geometry(VD::EdgeIndex(output.edges().len()))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn edge_discretize_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let distance = |site: VD::SiteGeometry<I>, p: [F; 2]| match site {
+        VD::SiteGeometry::Point(s) => (p[0] - s.x as F).hypot(p[1] - s.y as F),
+        VD::SiteGeometry::Segment(s) => {
+            let (x0, y0) = (s.start.x as F, s.start.y as F);
+            let (dx, dy) = (s.end.x as F - x0, s.end.y as F - y0);
+            let t = (((p[0] - x0) * dx + (p[1] - y0) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+            (p[0] - x0 - t * dx).hypot(p[1] - y0 - t * dy)
+        }
+    };
+    let mut curved = 0;
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        if !output.edge_is_finite(edge.id())? {
+            assert!(output.edge_discretize(edge.id(), 0.01).is_err());
+            continue;
+        }
+        let polyline = output.edge_discretize(edge.id(), 0.01)?;
+        let (v0, v1) = output.edge_vertices(edge.id())?;
+        assert_eq!(polyline[0], output.vertex_position(v0.unwrap())?);
+        assert_eq!(
+            polyline[polyline.len() - 1],
+            output.vertex_position(v1.unwrap())?
+        );
+        if !edge.is_curved() {
+            assert_eq!(polyline.len(), 2);
+            continue;
+        }
+        curved += 1;
+        // every sample is located on the parabola
+        let (a, b) = output.edge_bisected_geometry(edge.id())?;
+        for p in polyline.iter() {
+            assert!((distance(a, *p) - distance(b, *p)).abs() < 1e-6);
+        }
+        // and the midpoints of the polyline are close to it
+        for w in polyline.windows(2) {
+            let mid = [(w[0][0] + w[1][0]) / 2.0, (w[0][1] + w[1][1]) / 2.0];
+            assert!((distance(a, mid) - distance(b, mid)).abs() < 0.1);
+        }
+    }
+    assert!(curved > 0);
+    Ok(())
+}
+
+#[test]
+fn cell_edge_iterator_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [4, 20], [16, 3]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let mut finite_cells = 0;
+    for cell in output.cells().iter() {
+        let cell = cell.get();
+        let edges: Vec<VD::EdgeIndex> = output.cell_edge_iterator(cell.id()).collect();
+        if cell.is_degenerate() {
+            assert!(edges.is_empty());
+            continue;
+        }
+        let mut unique = edges.clone();
+        unique.sort_unstable_by_key(|e| e.0);
+        unique.dedup();
+        assert_eq!(unique.len(), edges.len());
+        let mut infinite = 0;
+        let mut area = 0.0;
+        for (i, edge_id) in edges.iter().enumerate() {
+            assert_eq!(output.edge_get_cell(*edge_id)?, cell.id());
+            let next = edges[(i + 1) % edges.len()];
+            let (v0, v1) = output.edge_vertices(*edge_id)?;
+            // the edges are connected
+            if v1.is_some() {
+                assert_eq!(v1, output.edge_vertices(next)?.0);
+            }
+            match (v0, v1) {
+                (Some(v0), Some(v1)) => {
+                    let (a, b) = (output.vertex_position(v0)?, output.vertex_position(v1)?);
+                    area += a[0] * b[1] - b[0] * a[1];
+                }
+                _ => infinite += 1,
+            }
+        }
+        if infinite == 0 {
+            // the edges of a finite cell are ordered counter-clockwise
+            assert!(area > 0.0);
+            finite_cells += 1;
+        } else {
+            assert_eq!(infinite, 2);
+        }
+    }
+    assert!(finite_cells > 0);
+    Ok(())
+}
+
+#[test]
+fn cell_polygon_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [4, 20], [16, 3]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let mut curved_cells = 0;
+    for cell in output.cells().iter() {
+        let cell = cell.get();
+        let polygon = output.cell_polygon(cell.id())?;
+        let discretized = output.cell_polygon_discretized(cell.id(), 0.01)?;
+        let finite = !cell.is_degenerate()
+            && output
+                .cell_edge_iterator(cell.id())
+                .all(|e| output.edge_is_finite(e).unwrap());
+        assert_eq!(polygon.is_some(), finite);
+        assert_eq!(discretized.is_some(), finite);
+        let (polygon, discretized) = match (polygon, discretized) {
+            (Some(p), Some(d)) => (p, d),
+            _ => continue,
+        };
+        let vertices: Vec<[F; 2]> = output
+            .cell_edge_iterator(cell.id())
+            .map(|e| output.vertex_position(output.edge_vertices(e).unwrap().0.unwrap()))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(polygon, vertices);
+        // the discretized polygon contains the same vertices, in the same order
+        let mut it = discretized.iter();
+        for v in polygon.iter() {
+            assert!(it.any(|d| d == v));
+        }
+        if discretized.len() > polygon.len() {
+            curved_cells += 1;
+        }
+    }
+    assert!(curved_cells > 0);
+    Ok(())
+}
+
+#[test]
+fn cell_area_1() -> Result<(), BvError> {
+    // a 3x3 grid, the center cell is a 10x10 square
+    let points: Vec<[I; 2]> = (0..9).map(|i| [(i % 3) * 10, (i / 3) * 10]).collect();
+    let points = VB::to_points::<I, I>(&points);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    for cell in output.cells().iter() {
+        let cell = cell.get();
+        if cell.source_index() == 4 {
+            assert_eq!(output.cell_area(cell.id())?, Some(100.0));
+            assert_eq!(output.cell_perimeter(cell.id())?, Some(40.0));
+        } else {
+            assert_eq!(output.cell_area(cell.id())?, None);
+            assert_eq!(output.cell_perimeter(cell.id())?, None);
+        }
+    }
+
+    // the chords of curved edges are never longer than the discretized arcs
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [4, 20], [16, 3]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    for analysis in output.analyze_cells(0.001) {
+        let analysis = analysis?;
+        let area = output.cell_area(analysis.cell_id)?.unwrap();
+        let perimeter = output.cell_perimeter(analysis.cell_id)?.unwrap();
+        assert!(area > 0.0 && perimeter > 0.0);
+        assert!(perimeter <= analysis.perimeter + 1e-9);
+        if perimeter == analysis.perimeter {
+            assert_eq!(area, analysis.area);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn diagram_serde_1() -> Result<(), BvError> {
+    let segments: [[I; 4]; 9] = [
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+        [680, 608, 669, 291],
+        [572, 96, 363, 51],
+        [147, 103, 96, 170],
+    ];
+    let output = {
+        let segments = VB::to_segments::<I, I>(&segments);
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let json = serde_json::to_string(&output).unwrap();
+    let restored: VD::Diagram<I, F> = serde_json::from_str(&json).unwrap();
+    restored.check_invariants()?;
+
+    assert_eq!(output.cells().len(), restored.cells().len());
+    assert_eq!(output.edges().len(), restored.edges().len());
+    assert_eq!(output.vertices().len(), restored.vertices().len());
+    for (a, b) in output.edges().iter().zip(restored.edges().iter()) {
+        let (a, b) = (a.get(), b.get());
+        assert_eq!(a.id(), b.id());
+        assert_eq!(a.twin()?, b.twin()?);
+        assert_eq!(a.next()?, b.next()?);
+        assert_eq!(a.prev()?, b.prev()?);
+        assert_eq!(a.vertex0(), b.vertex0());
+        assert_eq!(a.cell()?, b.cell()?);
+    }
+    for (a, b) in output.vertices().iter().zip(restored.vertices().iter()) {
+        let (a, b) = (a.get(), b.get());
+        assert_eq!((a.x(), a.y()), (b.x(), b.y()));
+        assert_eq!(a.get_incident_edge()?, b.get_incident_edge()?);
+    }
+    for (a, b) in output.cells().iter().zip(restored.cells().iter()) {
+        let (a, b) = (a.get(), b.get());
+        assert_eq!(a.source_index(), b.source_index());
+        assert_eq!(a.source_category(), b.source_category());
+    }
+
+    // inconsistent data is rejected
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["edges"][0]["next_ccw_"] = serde_json::json!(100_000);
+    assert!(serde_json::from_value::<VD::Diagram<I, F>>(value).is_err());
+    for (field, index) in [
+        ("vertex_", output.vertices().len()),
+        ("twin_", output.edges().len()),
+        ("prev_ccw_", output.edges().len()),
+    ] {
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let edge = output
+            .edges()
+            .iter()
+            .position(|e| e.get().vertex0().is_some())
+            .unwrap();
+        value["edges"][edge][field] = serde_json::json!(index);
+        assert!(
+            serde_json::from_value::<VD::Diagram<I, F>>(value).is_err(),
+            "{}",
+            field
+        );
+    }
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["vertices"][0]["incident_edge_"] = serde_json::json!(output.edges().len());
+    assert!(serde_json::from_value::<VD::Diagram<I, F>>(value).is_err());
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["sites"].as_array_mut().unwrap().pop();
+    assert!(serde_json::from_value::<VD::Diagram<I, F>>(value).is_err());
+    Ok(())
+}
+
+#[test]
+fn to_svg_1() -> Result<(), BvError> {
+    // a single infinite edge is clipped to the bounding box
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let options = VD::SvgOptions {
+        bounding_box: Some(([-10.0, -10.0], [20.0, 10.0])),
+        flip_y: false,
+        edge_color: "#123456".to_string(),
+        ..VD::SvgOptions::default()
+    };
+    let svg = VD::to_svg(&output, &options)?;
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-10 -10 30 20\">"));
+    assert!(svg.contains("<g stroke=\"#123456\""));
+    assert!(svg.contains("<path d=\"M5 -10 L5 10\"/>"));
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert_eq!(svg.matches("<circle").count(), 2);
+
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let svg = VD::to_svg(&output, &VD::SvgOptions::default())?;
+    assert!(svg.ends_with("</svg>\n"));
+    // the midpoint of every clipped ray is equidistant from the two closest input sites, the
+    // bounding box contains all the vertices so every infinite edge is visible
+    let options = VD::SvgOptions {
+        bounding_box: Some(([-40.0, -40.0], [80.0, 80.0])),
+        flip_y: false,
+        ..VD::SvgOptions::default()
+    };
+    let clipped_svg = VD::to_svg(&output, &options)?;
+    let distance = |p: [F; 2], s: &boostvoronoi::geometry::Line<I>| {
+        let (x0, y0) = (s.start.x as F, s.start.y as F);
+        let (dx, dy) = (s.end.x as F - x0, s.end.y as F - y0);
+        let t = (((p[0] - x0) * dx + (p[1] - y0) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+        (p[0] - x0 - t * dx).hypot(p[1] - y0 - t * dy)
+    };
+    let edge_group = clipped_svg.split("</g>").next().unwrap();
+    let mut rays = 0;
+    for d in edge_group.split("<path d=\"").skip(1) {
+        let d = d.split('"').next().unwrap();
+        let coordinates: Vec<F> = d
+            .split([' ', 'M', 'L'])
+            .filter(|c| !c.is_empty())
+            .map(|c| c.parse().unwrap())
+            .collect();
+        if !coordinates
+            .iter()
+            .any(|c| (c + 40.0).abs() < 1e-9 || (c - 80.0).abs() < 1e-9)
+        {
+            continue;
+        }
+        rays += 1;
+        let mid = [
+            (coordinates[0] + coordinates[2]) / 2.0,
+            (coordinates[1] + coordinates[3]) / 2.0,
+        ];
+        let mut distances: Vec<F> = points
+            .iter()
+            .map(|p| (mid[0] - p.x as F).hypot(mid[1] - p.y as F))
+            .chain(segments.iter().map(|s| distance(mid, s)))
+            // the endpoints of the segments are sites of their own
+            .chain(segments.iter().flat_map(|s| {
+                [s.start, s.end].map(|p| (mid[0] - p.x as F).hypot(mid[1] - p.y as F))
+            }))
+            .collect();
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((distances[0] - distances[1]).abs() < 1e-6);
+    }
+    assert_eq!(
+        rays,
+        output
+            .edges()
+            .iter()
+            .filter(|e| e.get().vertex0().is_none())
+            .count()
+    );
+    // every edge is drawn once, every infinite edge crosses the default bounding box
+    assert_eq!(
+        svg.matches("<path").count(),
+        output.num_edges() / 2 + segments.len()
+    );
+    assert_eq!(
+        svg.matches("<circle").count(),
+        output.num_vertices() + points.len()
+    );
+    let options = VD::SvgOptions {
+        secondary_edges: false,
+        ..VD::SvgOptions::default()
+    };
+    let secondary = output
+        .edges()
+        .iter()
+        .filter(|e| e.get().is_secondary())
+        .count();
+    assert!(secondary > 0);
+    let svg = VD::to_svg(&output, &options)?;
+    assert_eq!(
+        svg.matches("<path").count(),
+        (output.num_edges() - secondary) / 2 + segments.len()
+    );
+    Ok(())
+}
+
+#[test]
+fn delaunay_edges_1() -> Result<(), BvError> {
+    use rand::{Rng, SeedableRng};
+    // a triangular hull with points strictly inside, a full triangulation has 3n-6 edges
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let mut points: Vec<[I; 2]> = vec![[0, 0], [10000, 0], [0, 10000]];
+    while points.len() < 50 {
+        let p = [rng.gen_range(1..9999), rng.gen_range(1..9999)];
+        if p[0] + p[1] < 9999 && !points.contains(&p) {
+            points.push(p);
+        }
+    }
+    let points = VB::to_points::<I, I>(&points);
+    let build = |segments: &[[I; 4]]| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(VB::to_segments::<I, I>(segments).iter())?;
+        vb.build()
+    };
+    let edges = build(&[])?.delaunay_edges();
+    assert_eq!(edges.len(), 3 * points.len() - 6);
+    assert!(edges.windows(2).all(|w| w[0] < w[1]));
+    assert!(edges.iter().all(|(a, b)| a < b));
+    // the hull edges are part of the triangulation
+    for hull_edge in [(0, 1), (0, 2), (1, 2)] {
+        assert!(edges.contains(&hull_edge));
+    }
+
+    // the segment cells are skipped, and the segment hides some of the point neighbours
+    let with_segment = build(&[[10000, 10000, 12000, 3000]])?.delaunay_edges();
+    assert!(with_segment.len() < edges.len());
+    assert!(with_segment.iter().all(|e| edges.contains(e)));
+    Ok(())
+}
+
+#[test]
+fn locate_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[5, 5], [-3, 7], [12, 14], [4, 20], [16, 3]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10], [20, 20, 30, 2]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    // right next to an input point
+    assert_eq!(
+        output.locate([12.1, 13.9]),
+        Some((2, VD::SourceCategory::SinglePoint))
+    );
+    // close to the middle of a segment
+    assert_eq!(
+        output.locate([25.2, 11.5]),
+        Some((7, VD::SourceCategory::Segment))
+    );
+    // beyond the end of a segment
+    assert_eq!(
+        output.locate([31.0, 0.0]),
+        Some((7, VD::SourceCategory::SegmentEnd))
+    );
+
+    // the cells of points are convex, so their centroids are located inside them
+    let mut located = 0;
+    for analysis in output.analyze_cells(0.01) {
+        let analysis = analysis?;
+        let cell = output.get_cell(analysis.cell_id)?.get();
+        if !cell.contains_point() {
+            continue;
+        }
+        assert_eq!(
+            output.locate(analysis.centroid),
+            Some(cell.source_index_2())
+        );
+        located += 1;
+    }
+    assert!(located > 3);
+
+    let empty = VB::Builder::<I, F>::default().build()?;
+    assert_eq!(empty.locate([0.0, 0.0]), None);
+    Ok(())
+}
+
+#[test]
+fn bounding_box_1() -> Result<(), BvError> {
+    let mut vb = VB::Builder::<I, F>::default();
+    assert!(vb.input_bounding_box().is_none());
+    vb.with_vertices(VB::to_points::<I, I>(&[[0, 0], [10, 1], [-3, 4]]).iter())?;
+    vb.with_segments(VB::to_segments::<I, I>(&[[2, -5, 6, 8]]).iter())?;
+    assert_eq!(vb.input_bounding_box(), Some(([-3.0, -5.0], [10.0, 8.0])));
+    let output = vb.build()?;
+    let (low, high) = output.bounding_box().unwrap();
+    for v in output.vertices().iter() {
+        let v = v.get();
+        assert!(low[0] <= v.x() && v.x() <= high[0]);
+        assert!(low[1] <= v.y() && v.y() <= high[1]);
+    }
+    assert!(output
+        .vertices()
+        .iter()
+        .any(|v| v.get().x() == low[0] || v.get().y() == low[1]));
+
+    let output = VB::Builder::<I, F>::default().build()?;
+    assert!(output.bounding_box().is_none());
+    Ok(())
+}
+
+#[test]
+fn medial_axis_1() -> Result<(), BvError> {
+    // the input of segment_4_1, a square and some open segments
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+        [680, 608, 669, 291],
+        [572, 96, 363, 51],
+        [147, 103, 96, 170],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let edges = output.medial_axis()?;
+    // the four half-diagonals of the square
+    assert_eq!(edges.len(), 4);
+    let mut corners = Vec::new();
+    for e in edges {
+        assert!(output.get_edge(e)?.get().is_primary());
+        let [x0, y0, x1, y1] = output.edge_as_line(e)?;
+        let (corner, center) = if (x0, y0) == (300.0, 300.0) {
+            ([x1, y1], [x0, y0])
+        } else {
+            ([x0, y0], [x1, y1])
+        };
+        assert_eq!(center, [300.0, 300.0]);
+        corners.push(corner);
+    }
+    corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(
+        corners,
+        vec![
+            [200.0, 200.0],
+            [200.0, 400.0],
+            [400.0, 200.0],
+            [400.0, 400.0]
+        ]
+    );
+
+    // a square with a square hole and a point inside the polygon
+    let outer = VB::to_points::<I, I>(&[[0, 0], [100, 0], [100, 100], [0, 100]]);
+    let hole = VB::to_points::<I, I>(&[[40, 40], [60, 40], [60, 60], [40, 60]]);
+    let points = VB::to_points::<I, I>(&[[20, 80]]);
+    let mut segments = Vec::new();
+    for polygon in [&outer, &hole] {
+        for i in 0..polygon.len() {
+            segments.push(boostvoronoi::geometry::Line::new(
+                polygon[i],
+                polygon[(i + 1) % polygon.len()],
+            ));
+        }
+    }
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let edges = output.medial_axis()?;
+    assert!(!edges.is_empty());
+    assert!(edges
+        .iter()
+        .any(|e| output.get_edge(*e).unwrap().get().is_curved()));
+    for e in edges {
+        for [x, y] in output.edge_discretize(e, 0.1)? {
+            assert!((0.0..=100.0).contains(&x) && (0.0..=100.0).contains(&y));
+            // nothing inside the hole
+            assert!(!(x > 40.0 && x < 60.0 && y > 40.0 && y < 60.0));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn to_obj_1() -> Result<(), BvError> {
+    // a single infinite edge is clipped to the bounding box
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let options = VD::ObjOptions {
+        bounding_box: Some(([-10.0, -10.0], [20.0, 10.0])),
+        ..VD::ObjOptions::default()
+    };
+    let obj = VD::to_obj(&output, &options)?;
+    assert_eq!(obj, "# Voronoi skeleton\nv 5 -10 0\nv 5 10 0\nl 1 2\n");
+    let obj = VD::to_obj(&output, &VD::ObjOptions::default())?;
+    assert_eq!(obj, "# Voronoi skeleton\n");
+
+    // the clipped infinite edges start at the vertex of the triangle, they reuse its `v` line
+    let points = VB::to_points::<I, I>(&[[0, 0], [1, 1], [2, 5]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    assert_eq!(output.num_vertices(), 1);
+    let options = VD::ObjOptions {
+        bounding_box: Some(([-100.0, -100.0], [100.0, 100.0])),
+        ..VD::ObjOptions::default()
+    };
+    let obj = VD::to_obj(&output, &options)?;
+    assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 4);
+    let lines: Vec<&str> = obj.lines().filter(|l| l.starts_with("l ")).collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|l| l.split(' ').any(|i| i == "1")));
+
+    // a point inside a square of segments, the point cell is bounded by parabolic arcs
+    let points = VB::to_points::<I, I>(&[[300, 300]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let obj = VD::to_obj(&output, &VD::ObjOptions::default())?;
+    let num_v = obj.lines().filter(|l| l.starts_with("v ")).count();
+    let lines: Vec<Vec<usize>> = obj
+        .lines()
+        .filter(|l| l.starts_with("l "))
+        .map(|l| l[2..].split(' ').map(|i| i.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(lines.len(), output.edges_by_length(0.0).len());
+    // the arcs are discretized into extra vertices
+    assert!(num_v > output.num_vertices());
+    for line in lines.iter() {
+        assert!(line.len() >= 2);
+        assert!(line.iter().all(|i| *i >= 1 && *i <= num_v));
+        // the polylines start and end at Voronoi vertices
+        assert!(line[0] <= output.num_vertices());
+        assert!(line[line.len() - 1] <= output.num_vertices());
+    }
+    Ok(())
+}
+
+#[test]
+fn to_dxf_1() -> Result<(), BvError> {
+    // a point inside a square of segments, clipped to a box around the square
+    let points = VB::to_points::<I, I>(&[[300, 300]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let options = VD::DxfOptions {
+        bounding_box: Some(([0.0, 0.0], [600.0, 600.0])),
+        layers: true,
+        ..VD::DxfOptions::default()
+    };
+    let dxf = VD::to_dxf(&output, &options)?;
+
+    // the document is a sequence of (integer group code, value) line pairs
+    let lines: Vec<&str> = dxf.lines().collect();
+    assert_eq!(lines.len() % 2, 0);
+    let pairs: Vec<(i32, &str)> = lines
+        .chunks(2)
+        .map(|pair| (pair[0].trim().parse().unwrap(), pair[1]))
+        .collect();
+    assert_eq!(pairs[..2], [(0, "SECTION"), (2, "ENTITIES")]);
+    assert_eq!(pairs[pairs.len() - 2..], [(0, "ENDSEC"), (0, "EOF")]);
+    for (code, value) in pairs.iter() {
+        match code {
+            // coordinates
+            10 | 20 | 11 | 21 => {
+                let c: F = value.parse().unwrap();
+                assert!((0.0..=600.0).contains(&c), "{}", c);
+            }
+            8 => assert!(*value == "PRIMARY" || *value == "SECONDARY"),
+            _ => (),
+        }
+    }
+    let count = |entity: &str| pairs.iter().filter(|p| *p == &(0, entity)).count();
+    // the arcs around the point are polylines
+    assert!(count("POLYLINE") > 0);
+    assert_eq!(count("POLYLINE"), count("SEQEND"));
+    assert!(count("VERTEX") >= 2 * count("POLYLINE"));
+    assert!(pairs.contains(&(8, "SECONDARY")));
+
+    // without the secondary edges nothing is put on that layer
+    let options = VD::DxfOptions {
+        secondary_edges: false,
+        ..options
+    };
+    let dxf = VD::to_dxf(&output, &options)?;
+    assert!(!dxf.contains("SECONDARY"));
+    Ok(())
+}
+
+#[test]
+fn to_geojson_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 3], [4, 12], [-6, 7], [3, 4]]);
+    let segments = VB::to_segments::<I, I>(&[[12, -4, 20, 6], [-8, -5, -2, -9]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let infinite_cells = output
+        .cells()
+        .iter()
+        .filter(|c| {
+            let c = c.get();
+            !c.is_degenerate() && output.cell_polygon(c.id()).unwrap().is_none()
+        })
+        .count();
+    let (geojson, skipped) = VD::to_geojson(&output, None)?;
+    assert_eq!(skipped, infinite_cells);
+    let json: serde_json::Value = serde_json::from_str(&geojson).unwrap();
+    assert_eq!(json["type"], "FeatureCollection");
+    let features = json["features"].as_array().unwrap();
+    assert!(!features.is_empty());
+    assert_eq!(
+        features.len() + skipped,
+        output
+            .cells()
+            .iter()
+            .filter(|c| !c.get().is_degenerate())
+            .count()
+    );
+
+    // with a bounding box the clipped cells cover it exactly
+    let (low, high) = ([-30.0, -30.0], [40.0, 35.0]);
+    let (geojson, skipped) = VD::to_geojson(&output, Some((low, high)))?;
+    assert_eq!(skipped, 0);
+    let json: serde_json::Value = serde_json::from_str(&geojson).unwrap();
+    let mut area = 0.0;
+    for feature in json["features"].as_array().unwrap() {
+        assert_eq!(feature["geometry"]["type"], "Polygon");
+        let category = feature["properties"]["source_category"].as_str().unwrap();
+        assert!(["SinglePoint", "SegmentStart", "SegmentEnd", "Segment"].contains(&category));
+        let ring: Vec<[F; 2]> = feature["geometry"]["coordinates"][0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| [p[0].as_f64().unwrap(), p[1].as_f64().unwrap()])
+            .collect();
+        assert_eq!(ring.first(), ring.last());
+        for p in ring.iter() {
+            assert!(p[0] >= low[0] && p[0] <= high[0] && p[1] >= low[1] && p[1] <= high[1]);
+        }
+        // CCW rings have a positive area
+        let polygon_area: F = ring
+            .windows(2)
+            .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+            .sum::<F>()
+            / 2.0;
+        assert!(polygon_area > 0.0);
+        area += polygon_area;
+    }
+    let box_area = (high[0] - low[0]) * (high[1] - low[1]);
+    assert!(
+        (area - box_area).abs() < box_area * 0.001,
+        "{} != {}",
+        area,
+        box_area
+    );
+
+    let (wkt, skipped) = VD::to_wkt(&output, Some((low, high)))?;
+    assert_eq!(skipped, 0);
+    assert_eq!(
+        wkt.lines().count(),
+        json["features"].as_array().unwrap().len()
+    );
+    assert!(wkt
+        .lines()
+        .all(|l| l.starts_with("POLYGON ((") && l.ends_with("))")));
+
+    // collinear points, the edges have no vertices at all
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [20, 0]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let (wkt, skipped) = VD::to_wkt(&output, Some(([-5.0, -5.0], [25.0, 5.0])))?;
+    assert_eq!(skipped, 0);
+    assert_eq!(
+        wkt,
+        "POLYGON ((5 -5, 5 5, -5 5, -5 -5, 5 -5))\n\
+         POLYGON ((5 5, 5 -5, 15 -5, 15 5, 5 5))\n\
+         POLYGON ((15 5, 15 -5, 25 -5, 25 5, 15 5))\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn edge_kind_1() -> Result<(), BvError> {
+    // a triangle, every cell is unbounded
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let mut kinds = Vec::new();
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        let kind = output.edge_kind(edge.id())?;
+        let twin_kind = output.edge_kind(edge.twin()?)?;
+        match kind {
+            VD::EdgeKind::InfiniteAt0 => assert_eq!(twin_kind, VD::EdgeKind::InfiniteAt1),
+            VD::EdgeKind::InfiniteAt1 => assert_eq!(twin_kind, VD::EdgeKind::InfiniteAt0),
+            _ => assert_eq!(twin_kind, kind),
+        }
+        assert_eq!(
+            kind == VD::EdgeKind::Finite,
+            output.edge_is_finite(edge.id())?
+        );
+        kinds.push(kind);
+    }
+    assert_eq!(
+        kinds
+            .iter()
+            .filter(|k| **k == VD::EdgeKind::InfiniteAt1)
+            .count(),
+        3
+    );
+    assert_eq!(
+        kinds
+            .iter()
+            .filter(|k| **k == VD::EdgeKind::InfiniteAt0)
+            .count(),
+        3
+    );
+    Ok(())
+}
+
+#[test]
+fn cell_neighbors_1() -> Result<(), BvError> {
+    // a point inside a square of segments
+    let points = VB::to_points::<I, I>(&[[300, 300]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let cell = output
+        .cell_iter()
+        .map(|c| c.get())
+        .find(|c| c.contains_point() && c.source_index() == 0)
+        .unwrap();
+    // the point cell is bounded by one parabolic arc towards each of the four segments
+    let neighbors: Vec<VD::CellIndex> = cell.neighbors(&output).collect();
+    assert_eq!(neighbors.len(), 4);
+    for (i, n) in neighbors.iter().enumerate() {
+        assert!(!neighbors[i + 1..].contains(n));
+        let neighbor = output.get_cell(*n)?.get();
+        assert!(neighbor.contains_segment());
+        assert!(neighbor.neighbors(&output).any(|c| c == cell.id()));
+    }
+    // every cell is a neighbor of the cells across its edges
+    for c in output.cell_iter() {
+        let c = c.get();
+        assert_eq!(
+            c.neighbors(&output).count(),
+            output.cell_edge_iterator(c.id()).count()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn edge_length_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 10], [5, 5]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        if output.edge_is_finite(edge.id())? {
+            // the center cell is a square with the side 10/√2
+            assert!((edge.length(&output).unwrap() - 50_f64.sqrt()).abs() < 1e-9);
+        } else {
+            assert!(edge.length(&output).is_none());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn cell_order_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[10, 10], [0, 0]]);
+    let segments = VB::to_segments::<I, I>(&[[5, 20, 5, -20]]);
+    let build = || {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()
+    };
+    let output = build()?;
+    // the cells are in sweep-line order, not in input order
+    let sources: Vec<_> = output
+        .cell_iter()
+        .map(|c| c.get().source_index_2())
+        .collect();
+    assert_eq!(
+        sources,
+        vec![
+            (1, VD::SourceCategory::SinglePoint),
+            (2, VD::SourceCategory::SegmentEnd),
+            (2, VD::SourceCategory::Segment),
+            (2, VD::SourceCategory::SegmentStart),
+            (0, VD::SourceCategory::SinglePoint),
+        ]
+    );
+    for (i, c) in output.cell_iter().enumerate() {
+        assert_eq!(c.get().id().0, i);
+    }
+    let in_input_order: Vec<usize> = output.cells_in_input_order().map(|c| c.0).collect();
+    assert_eq!(in_input_order, vec![4, 0, 3, 1, 2]);
+
+    // the order is deterministic
+    let again = build()?;
+    for (a, b) in output.cell_iter().zip(again.cell_iter()) {
+        assert_eq!(a.get().source_index_2(), b.get().source_index_2());
+    }
+    Ok(())
+}
+
+#[test]
+fn color_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[300, 300]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    // the custom colors don't affect the reserved bits
+    for (i, e) in output.edges().iter().enumerate().take(6) {
+        let (is_primary, is_linear) = (e.get().is_primary(), e.get().is_linear());
+        output.edge_set_color(e.get().id(), i as u32 + 1)?;
+        output.edge_or_color(e.get().id(), 0x100)?;
+        assert_eq!(output.edge_get_color(e.get().id())?, (i as u32 + 1) | 0x100);
+        assert_eq!(e.get().is_primary(), is_primary);
+        assert_eq!(e.get().is_linear(), is_linear);
+    }
+    for (i, c) in output.cells().iter().enumerate() {
+        let source = c.get().source_index_2();
+        output.cell_set_color(c.get().id(), i as u32 + 7)?;
+        assert_eq!(output.cell_get_color(c.get().id())?, i as u32 + 7);
+        assert_eq!(c.get().source_index_2(), source);
+        output.cell_or_color(c.get().id(), 0x10)?;
+        assert_eq!(c.get().get_color(), (i as u32 + 7) | 0x10);
+    }
+    for (i, v) in output.vertices().iter().enumerate() {
+        let is_site_point = v.get().is_site_point();
+        output.vertex_set_color(v.get().get_id(), i as u32 + 3)?;
+        assert_eq!(
+            output.vertex_get_color(Some(v.get().get_id())),
+            Some(i as u32 + 3)
+        );
+        assert_eq!(v.get().is_site_point(), is_site_point);
+    }
+    assert!(output.cell_set_color(VD::CellIndex(1000), 1).is_err());
+    Ok(())
+}
+
+#[test]
+/// The clearance of a vertex is the distance to the point sites around it
+fn vertex_clearance_1() -> Result<(), BvError> {
+    let points: [[I; 2]; 6] = [[0, 0], [10, 0], [0, 10], [10, 10], [4, 6], [23, -7]];
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points(&points).iter())?;
+    let output = vb.build()?;
+    assert!(output.num_vertices() > 0);
+    for vertex in output.vertex_iter() {
+        let vertex = vertex.get();
+        for edge_id in vertex.incident_edges(&output) {
+            let cell_id = output.edge_get_cell(edge_id)?;
+            match output.cell_site_geometry(cell_id)? {
+                VD::SiteGeometry::Point(p) => {
+                    let distance = (p.x as F - vertex.x()).hypot(p.y as F - vertex.y());
+                    assert!((vertex.clearance() - distance).abs() < 1e-9);
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn point_to_site_distance_sq_1() -> Result<(), BvError> {
+    use VD::SourceCategory as Sc;
+    let points = VB::to_points::<I, I>(&[[5, 5], [-2, 7]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 4, 4], [10, 10, 10, 0]]);
+    let d = |p: [F; 2], index, category| {
+        VD::point_to_site_distance_sq(p, index, category, &points, &segments)
+    };
+    // point sites
+    assert_eq!(d([8.0, 9.0], 0, Sc::SinglePoint), 25.0);
+    assert_eq!(d([-2.0, 7.0], 1, Sc::SinglePoint), 0.0);
+    assert_eq!(d([-3.0, -4.0], 2, Sc::SegmentStart), 25.0);
+    assert_eq!(d([4.0, 5.0], 2, Sc::SegmentEnd), 1.0);
+    assert_eq!(d([13.0, 6.0], 3, Sc::SegmentStart), 25.0);
+    // perpendicular distance to the segment interior, the foot of (0,4) is (2,2)
+    assert_eq!(d([0.0, 4.0], 2, Sc::Segment), 8.0);
+    assert_eq!(d([7.0, 3.0], 3, Sc::Segment), 9.0);
+    // beyond the endpoints the distance is measured to the nearest endpoint
+    assert_eq!(d([-3.0, -4.0], 2, Sc::Segment), 25.0);
+    assert_eq!(d([7.0, 8.0], 2, Sc::Segment), 25.0);
+    assert_eq!(d([10.0, -2.0], 3, Sc::Segment), 4.0);
+
+    // the site returned by locate() is the nearest one
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(points.iter())?;
+    vb.with_segments(segments.iter())?;
+    let output = vb.build()?;
+    for position in [[1.0, 3.0], [6.0, 6.0], [12.0, 4.0], [-5.0, 0.0]] {
+        let (index, category) = output.locate(position).unwrap();
+        let nearest = d(position, index, category);
+        for cell in output.cell_iter() {
+            let (index, category) = cell.get().source_index_2();
+            assert!(nearest <= d(position, index, category) + 1e-9);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+/// Every vertex of point input in general position joins three edges
+fn vertex_degrees_1() -> Result<(), BvError> {
+    let mut seed: u32 = 0x1234_5678;
+    let mut random = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        (seed % 100_000) as I
+    };
+    let points: Vec<[I; 2]> = (0..200).map(|_| [random(), random()]).collect();
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points(&points).iter())?;
+    let output = vb.build()?;
+    output.check_invariants()?;
+    let degrees = output.vertex_degrees();
+    assert_eq!(degrees.len(), output.num_vertices());
+    assert!(degrees.len() > 100);
+    assert!(degrees.iter().all(|d| *d == 3), "{:?}", degrees);
+    // every half-edge has one origin, except the ones going out to infinity
+    let finite_origins = output
+        .edges()
+        .iter()
+        .filter(|e| e.get().vertex0().is_some())
+        .count();
+    assert_eq!(degrees.iter().sum::<usize>(), finite_origins);
+    Ok(())
+}
+
+#[test]
+fn generating_sites_1() -> Result<(), BvError> {
+    let mut seed: u32 = 0x0bad_cafe;
+    let mut random = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        (seed % 10_000) as I
+    };
+    let mut points: Vec<[I; 2]> = (0..100).map(|_| [random(), random()]).collect();
+    points.sort_unstable();
+    points.dedup();
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(VB::to_points(&points).iter())?;
+    let output = vb.build()?;
+    assert!(output.num_vertices() > 50);
+    let distance = |v: &VD::Vertex<I, F>, p: [I; 2]| (v.x() - p[0] as F).hypot(v.y() - p[1] as F);
+    for vertex in output.vertices().iter() {
+        let vertex = vertex.get();
+        let sites = vertex.generating_sites().unwrap();
+        assert!(sites.iter().all(|s| s.1 == VD::SourceCategory::SinglePoint));
+        assert!(sites[0].0 != sites[1].0 && sites[1].0 != sites[2].0 && sites[0].0 != sites[2].0);
+        // the three sites are on the empty circle around the vertex
+        for (index, _) in sites.iter() {
+            let d = distance(&vertex, points[*index]);
+            assert!((d - vertex.clearance()).abs() < 1e-6 * d.max(1.0));
+        }
+        assert!(points.iter().all(
+            |p| distance(&vertex, *p) > vertex.clearance() - 1e-6 * vertex.clearance().max(1.0)
+        ));
+    }
+    Ok(())
+}
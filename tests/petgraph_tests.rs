@@ -0,0 +1,81 @@
+#![cfg(feature = "petgraph")]
+use boostvoronoi::builder as VB;
+use boostvoronoi::BvError;
+
+type I = i32;
+type F = f64;
+
+#[test]
+fn to_petgraph_1() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[0, 0], [10, 0], [0, 10], [10, 11], [20, 5], [4, 6]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    let graph = output.to_petgraph();
+    assert_eq!(graph.node_count(), output.num_vertices());
+    assert_eq!(graph.edge_count(), output.edges_by_length(0.0).len());
+    for node in graph.node_indices() {
+        let v = output.vertex_get(boostvoronoi::diagram::VertexIndex(node.index()))?;
+        assert_eq!(graph[node], [v.get().x(), v.get().y()]);
+    }
+
+    // the shortest path between two vertices is never longer than any direct edge
+    let start = petgraph::graph::NodeIndex::new(0);
+    let distances = petgraph::algo::dijkstra(&graph, start, None, |e| *e.weight());
+    for e in graph.edge_indices() {
+        let (a, b) = graph.edge_endpoints(e).unwrap();
+        if let (Some(da), Some(db)) = (distances.get(&a), distances.get(&b)) {
+            assert!((da - db).abs() <= graph[e] + 1e-9);
+        }
+    }
+    assert_eq!(distances.len(), graph.node_count());
+    Ok(())
+}
+
+#[test]
+fn to_petgraph_with_maps_1() -> Result<(), BvError> {
+    let segments = VB::to_segments::<I, I>(&[
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+    ]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let (graph, nodes, edges) = output.to_petgraph_with_maps();
+    assert_eq!(nodes.len(), graph.node_count());
+    assert_eq!(edges.len(), graph.edge_count());
+    for node in graph.node_indices() {
+        let v = output.vertex_get(nodes[node.index()])?;
+        assert_eq!(graph[node], [v.get().x(), v.get().y()]);
+    }
+    for e in graph.edge_indices() {
+        let edge = edges[e.index()];
+        let (a, b) = graph.edge_endpoints(e).unwrap();
+        assert!(output.edge_is_finite(edge)?);
+        assert_eq!(
+            output.edge_vertices(edge)?,
+            (Some(nodes[a.index()]), Some(nodes[b.index()]))
+        );
+    }
+
+    // the skeleton of the square is connected, the corners are 100√2 from the center
+    assert_eq!(petgraph::algo::connected_components(&graph), 1);
+    let center = graph
+        .node_indices()
+        .find(|n| graph[*n] == [300.0, 300.0])
+        .unwrap();
+    let distances = petgraph::algo::dijkstra(&graph, center, None, |e| *e.weight());
+    assert_eq!(distances.len(), graph.node_count());
+    for node in graph.node_indices() {
+        if graph[node] == [200.0, 200.0] {
+            assert!((distances[&node] - 100.0 * 2_f64.sqrt()).abs() < 1e-9);
+        }
+    }
+    Ok(())
+}
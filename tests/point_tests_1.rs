@@ -1,3 +1,4 @@
+#![allow(clippy::bool_assert_comparison, clippy::get_first, clippy::useless_vec)]
 use boostvoronoi::builder::Builder;
 use boostvoronoi::geometry::Point;
 use boostvoronoi::BvError;
@@ -13,6 +14,21 @@ fn almost_equal(x1: F, x2: F, y1: F, y2: F) -> bool {
     (F::abs(x1 - x2) < delta) && (F::abs(y1 - y2) < delta)
 }
 
+#[test]
+fn empty_input_1() -> Result<(), BvError> {
+    let output = Builder::<I, F>::default().build()?;
+    output.check_invariants()?;
+    assert_eq!(output.cells().len(), 0);
+    assert_eq!(output.vertices().len(), 0);
+    assert_eq!(output.edges().len(), 0);
+
+    // an empty iterator is no input either
+    let mut vb = Builder::<I, F>::default();
+    vb.with_vertices(Vec::<Point<I>>::new().iter())?;
+    assert_eq!(vb.build()?.counts(), output.counts());
+    Ok(())
+}
+
 #[test]
 fn single_point_1() {
     let output = {
@@ -28,6 +44,8 @@ fn single_point_1() {
     assert_eq!(cell.source_index(), 0);
     assert_eq!(output.vertices().len(), 0);
     assert_eq!(output.edges().len(), 0);
+    assert_eq!(cell.get_incident_edge(), None);
+    output.check_invariants().expect("single_point_1");
 }
 
 #[test]
@@ -1367,3 +1385,131 @@ fn eighth_points_1() -> Result<(), BvError> {
     assert_eq!(e.is_secondary(), false);
     Ok(())
 }
+
+#[test]
+fn cocircular_points_1() -> Result<(), BvError> {
+    // The two circle events of four cocircular points coincide, the degenerate zero length
+    // edge between them is removed when the diagram is finalized. So the result is a single
+    // vertex of degree 4, not two coincident vertices of degree 3.
+    for square in [
+        [[0, 0], [10, 0], [10, 10], [0, 10]],
+        [[5, 0], [10, 5], [5, 10], [0, 5]],
+        [[-3, 1], [4, -2], [7, 5], [0, 8]],
+        [
+            [-1000000, -1000000],
+            [1000000, -1000000],
+            [1000000, 1000000],
+            [-1000000, 1000000],
+        ],
+    ]
+    .iter()
+    {
+        let points: Vec<Point<I>> = square.iter().map(|p| Point { x: p[0], y: p[1] }).collect();
+        let output = {
+            let mut vb = Builder::<I, f64>::default();
+            vb.with_vertices(points.iter())?;
+            vb.build()?
+        };
+        output.check_invariants()?;
+        assert_eq!(output.num_cells(), 4);
+        assert_eq!(output.num_vertices(), 1);
+        assert_eq!(output.num_edges(), 8);
+        let v = output.vertices()[0].get();
+        assert!(v.x().is_finite() && v.y().is_finite());
+        let center_x = square.iter().map(|p| p[0] as f64).sum::<f64>() / 4.0;
+        let center_y = square.iter().map(|p| p[1] as f64).sum::<f64>() / 4.0;
+        assert!((v.x() - center_x).abs() < 1e-9 && (v.y() - center_y).abs() < 1e-9);
+        assert_eq!(output.vertex_degree(v.get_id())?, 4);
+    }
+    Ok(())
+}
+
+/// Checks the diagram of sites all located on one line: no vertices and only infinite,
+/// straight edges.
+fn assert_collinear_diagram(
+    output: &boostvoronoi::diagram::Diagram<I, f64>,
+    cells: usize,
+) -> Result<(), BvError> {
+    output.validate_topology()?;
+    assert_eq!(output.num_cells(), cells);
+    assert_eq!(output.num_vertices(), 0);
+    assert_eq!(output.num_edges(), 2 * (cells - 1));
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        assert!(output.edge_is_infinite(edge.id())?);
+        assert!(edge.is_linear());
+    }
+    Ok(())
+}
+
+#[test]
+fn collinear_points_1() -> Result<(), BvError> {
+    // horizontal, diagonal and steep lines, given in random order
+    for (n, dx, dy) in [(5, 10, 0), (20, 3, 7), (12, 1, 1000), (7, -40, 3)].iter() {
+        let mut points: Vec<Point<I>> = (0..*n)
+            .map(|i| Point {
+                x: i * dx - 7,
+                y: i * dy + 1,
+            })
+            .collect();
+        points.swap(0, *n as usize / 2);
+        points.reverse();
+        let output = {
+            let mut vb = Builder::<I, f64>::default();
+            vb.with_vertices(points.iter())?;
+            vb.build()?
+        };
+        assert_collinear_diagram(&output, *n as usize)?;
+        // every cell is a strip (or a half plane at the ends) between two neighbours
+        for cell in output.cells().iter() {
+            let edges = output.cell_edge_iterator(cell.get().id()).count();
+            assert!(edges == 1 || edges == 2);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn collinear_points_2() -> Result<(), BvError> {
+    // collinear points and a segment on the same line
+    let points: Vec<Point<I>> = [[0, 0], [10, 0], [20, 0]]
+        .iter()
+        .map(|p| Point { x: p[0], y: p[1] })
+        .collect();
+    let segments = boostvoronoi::builder::to_segments::<I, I>(&[[30, 0, 40, 0]]);
+    let output = {
+        let mut vb = Builder::<I, f64>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    // 3 points, the segment and its 2 endpoints
+    assert_collinear_diagram(&output, 6)?;
+
+    // collinear points and one point off the line: one vertex per pair of neighbours
+    let mut points: Vec<Point<I>> = (0..6).map(|i| Point { x: i * 10, y: 0 }).collect();
+    points.push(Point { x: 25, y: 40 });
+    let output = {
+        let mut vb = Builder::<I, f64>::default();
+        vb.with_vertices(points.iter())?;
+        vb.build()?
+    };
+    output.validate_topology()?;
+    assert_eq!(output.num_cells(), 7);
+    assert_eq!(output.num_vertices(), 5);
+    assert_eq!(output.num_edges(), 22);
+    let mut xs: Vec<f64> = output
+        .vertices()
+        .iter()
+        .map(|v| {
+            let v = v.get();
+            assert!(v.x().is_finite() && v.y().is_finite());
+            v.x()
+        })
+        .collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (x, expected) in xs.iter().zip([5.0, 15.0, 25.0, 35.0, 45.0].iter()) {
+        assert!((x - expected).abs() < 1e-9, "{} != {}", x, expected);
+    }
+    Ok(())
+}
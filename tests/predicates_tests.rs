@@ -0,0 +1,83 @@
+use boostvoronoi::builder as VB;
+use boostvoronoi::geometry::{Line, Point};
+use boostvoronoi::predicates::{circle_event, Site};
+use boostvoronoi::BvError;
+
+type I = i32;
+type F = f64;
+
+fn p(x: I, y: I) -> Site<I> {
+    Site::Point(Point { x, y })
+}
+
+fn s(x1: I, y1: I, x2: I, y2: I) -> Site<I> {
+    Site::Segment(Line::new(Point { x: x1, y: y1 }, Point { x: x2, y: y2 }))
+}
+
+fn almost_equal(a: F, b: F) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn circle_event_1() {
+    let (c, r) = circle_event::<I, F>(&p(0, 0), &p(0, 10), &p(10, 0)).unwrap();
+    assert!(almost_equal(c[0], 5.0) && almost_equal(c[1], 5.0));
+    assert!(almost_equal(r, 50_f64.sqrt()));
+    // any rotation of the clockwise order is fine
+    assert!(circle_event::<I, F>(&p(0, 10), &p(10, 0), &p(0, 0)).is_some());
+    // counter-clockwise
+    assert!(circle_event::<I, F>(&p(0, 0), &p(10, 0), &p(0, 10)).is_none());
+    // collinear
+    assert!(circle_event::<I, F>(&p(0, 0), &p(5, 5), &p(10, 10)).is_none());
+}
+
+#[test]
+fn circle_event_2() {
+    // the circle touching (0,10), (10,10) and the x axis
+    for seg in [s(0, 0, 10, 0), s(10, 0, 0, 0)].iter() {
+        for (a, b, c) in [
+            (&p(0, 10), &p(10, 10), seg),
+            (&p(10, 10), seg, &p(0, 10)),
+            (seg, &p(0, 10), &p(10, 10)),
+        ]
+        .iter()
+        {
+            let (center, r) = circle_event::<I, F>(a, b, c).unwrap();
+            assert!(almost_equal(center[0], 5.0) && almost_equal(center[1], 6.25));
+            assert!(almost_equal(r, 6.25));
+        }
+        assert!(circle_event::<I, F>(&p(10, 10), &p(0, 10), seg).is_none());
+    }
+}
+
+#[test]
+fn circle_event_3() -> Result<(), BvError> {
+    // the circle events must agree with the vertices of the diagram
+    let points = VB::to_points::<I, I>(&[[5, 5]]);
+    let segments = VB::to_segments::<I, I>(&[[0, 0, 10, 0], [10, 0, 10, 10]]);
+    let output = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let (center, r) = circle_event::<I, F>(
+        &Site::Point(points[0]),
+        &Site::Segment(segments[0]),
+        &Site::Segment(segments[1]),
+    )
+    .unwrap();
+    assert!(almost_equal(r, (center[0] - 5.0).hypot(center[1] - 5.0)));
+    assert!(almost_equal(r, center[1]));
+    assert!(almost_equal(r, 10.0 - center[0]));
+    assert!(output
+        .vertex_iter()
+        .any(|v| almost_equal(v.get().x(), center[0]) && almost_equal(v.get().y(), center[1])));
+    // inconsistent segment directions
+    assert!(circle_event::<I, F>(&p(5, 5), &s(0, 0, 10, 0), &s(10, 10, 10, 0)).is_none());
+    // both segments reversed
+    assert!(circle_event::<I, F>(&p(5, 5), &s(10, 10, 10, 0), &s(10, 0, 0, 0)).is_some());
+    // the same segment twice
+    assert!(circle_event::<I, F>(&p(5, 5), &s(0, 0, 10, 0), &s(10, 0, 0, 0)).is_none());
+    Ok(())
+}
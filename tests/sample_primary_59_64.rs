@@ -1,3 +1,8 @@
+#![allow(
+    clippy::approx_constant,
+    clippy::bool_assert_comparison,
+    clippy::excessive_precision
+)]
 use boostvoronoi::builder as VB;
 use boostvoronoi::diagram as VD;
 use boostvoronoi::file_reader as FR;
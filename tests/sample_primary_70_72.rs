@@ -1,3 +1,4 @@
+#![allow(clippy::bool_assert_comparison)]
 use boostvoronoi::builder as VB;
 use boostvoronoi::diagram as VD;
 use boostvoronoi::file_reader as FR;
@@ -1,3 +1,9 @@
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::excessive_precision,
+    clippy::get_first,
+    clippy::useless_vec
+)]
 use boostvoronoi::builder::Builder;
 use boostvoronoi::geometry::{Line, Point};
 use boostvoronoi::BvError;
@@ -24,6 +30,7 @@ fn single_segment_point_1() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.check_invariants()?;
     assert_eq!(output.cells().len(), 4);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -85,6 +92,7 @@ fn single_segment_point_2() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.check_invariants()?;
     assert_eq!(output.cells().len(), 4);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -283,6 +291,7 @@ fn single_segment_point_3() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.check_invariants()?;
     assert_eq!(output.cells().len(), 5);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -401,6 +410,7 @@ fn single_segment_point_4() -> Result<(), BvError> {
         vb.with_segments(_s.iter()).expect("single_segment_point_3");
         vb.build().expect("single_segment_point_3")
     };
+    output.check_invariants()?;
     assert_eq!(output.cells().len(), 6);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -586,6 +596,7 @@ fn single_segment_point_5() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.check_invariants()?;
     assert_eq!(output.cells().len(), 6);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
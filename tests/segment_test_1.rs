@@ -1,3 +1,10 @@
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::excessive_precision,
+    clippy::get_first,
+    clippy::ptr_arg,
+    clippy::useless_vec
+)]
 use boostvoronoi::builder as VB;
 use boostvoronoi::diagram as VD;
 use boostvoronoi::geometry::{Line, Point};
@@ -40,6 +47,7 @@ fn single_segment_1() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     // results verified against c++ boost
     assert_eq!(output.cells().len(), 3);
     let cell = output.cells()[0].get();
@@ -87,6 +95,7 @@ fn single_segment_2() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 3);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -130,6 +139,7 @@ fn single_segment_3() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 3);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -173,6 +183,7 @@ fn single_segment_4() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 3);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -221,6 +232,7 @@ fn two_segments_1() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 6);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -391,6 +403,7 @@ fn two_segments_2() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 7);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -800,6 +813,7 @@ fn two_segments_3() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 8);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -895,6 +909,7 @@ fn two_segments_5() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 10);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -1013,6 +1028,7 @@ fn two_segments_6() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 6);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -1368,6 +1384,7 @@ fn two_segments_7() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 6);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
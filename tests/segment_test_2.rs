@@ -35,6 +35,7 @@ fn two_segments_9() -> Result<(), BvError> {
         vb.with_segments(_s.iter()).expect("two_segments_9");
         vb.build().expect("two_segments_9")
     };
+    output.validate_topology()?;
     let v = output.vertices()[0].get();
     assert!(almost_equal(v.x(), 200.0000000, v.y(), 200.0000000));
     let v = output.vertices()[1].get();
@@ -59,3 +60,34 @@ fn two_segments_9() -> Result<(), BvError> {
     assert!(almost_equal(v.x(), 561.2596899, v.y(), 400.0000000));
     Ok(())
 }
+
+/// The same vertical segment, given in both endpoint orders, should produce identical diagrams
+#[test]
+fn vertical_segment_order_1() -> Result<(), BvError> {
+    let points: [[I; 2]; 3] = [[100, 250], [350, 150], [300, 420]];
+    let build = |segment: [I; 4]| -> Result<_, BvError> {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points(&points).iter())?;
+        vb.with_segments(VB::to_segments(&[segment, [400, 100, 500, 300]]).iter())?;
+        vb.build()
+    };
+    let output1 = build([200, 100, 200, 400])?;
+    let output2 = build([200, 400, 200, 100])?;
+
+    assert_eq!(output1.num_cells(), output2.num_cells());
+    assert_eq!(output1.num_edges(), output2.num_edges());
+    assert_eq!(output1.num_vertices(), output2.num_vertices());
+    for (v1, v2) in output1.vertex_iter().zip(output2.vertex_iter()) {
+        let v1 = v1.get();
+        let v2 = v2.get();
+        assert!(almost_equal(v1.x(), v2.x(), v1.y(), v2.y()));
+    }
+    for (e1, e2) in output1.edge_iter().zip(output2.edge_iter()) {
+        let e1 = e1.get();
+        let e2 = e2.get();
+        assert_eq!(e1.vertex0(), e2.vertex0());
+        assert_eq!(e1.cell()?, e2.cell()?);
+        assert_eq!(e1.is_curved(), e2.is_curved());
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+#![allow(clippy::bool_assert_comparison, clippy::ptr_arg)]
 use boostvoronoi::builder::{to_points, to_segments, Builder};
 use boostvoronoi::diagram as VD;
 use boostvoronoi::geometry::{Line, Point};
@@ -146,6 +147,7 @@ fn large_segment_1() -> Result<(), BvError> {
         vb.with_segments(_s.iter())?;
         (vb.build()?, _v, _s)
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 161);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
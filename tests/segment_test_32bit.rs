@@ -35,5 +35,74 @@ fn segment_32bit_1() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    _output.validate_topology()?;
+    Ok(())
+}
+
+#[test]
+// f32 output is the internal f64 result, rounded
+fn segment_32bit_2() -> Result<(), BvError> {
+    let points = VB::to_points::<I, I>(&[[428, 263], [-1041, 77], [3, 5003], [911, -317]]);
+    let segments = VB::to_segments::<I, I>(&[
+        [0, 0, 1000, 0],
+        [1000, 0, 1000, 1000],
+        [1000, 1000, 0, 1000],
+        [0, 1000, 0, 0],
+        [400, 503, 607, 497],
+        [2041, 440, 1367, 2945],
+    ]);
+    let output_32 = {
+        let mut vb = VB::Builder::<I, f32>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    let output_64 = {
+        let mut vb = VB::Builder::<I, f64>::default();
+        vb.with_vertices(points.iter())?;
+        vb.with_segments(segments.iter())?;
+        vb.build()?
+    };
+    output_32.validate_topology()?;
+    assert_eq!(output_32.counts(), output_64.counts());
+    for (v32, v64) in output_32.vertices().iter().zip(output_64.vertices().iter()) {
+        let (v32, v64) = (v32.get(), v64.get());
+        assert!(v32.x().is_finite() && v32.y().is_finite());
+        assert_eq!(v32.x(), v64.x() as f32);
+        assert_eq!(v32.y(), v64.y() as f32);
+    }
+    Ok(())
+}
+
+#[test]
+// small integer input with f32 output, e.g. for memory constrained targets
+fn segment_32bit_3() -> Result<(), BvError> {
+    let points: [[i16; 2]; 3] = [[5, 5], [-250, 37], [128, 1003]];
+    let segments: [[i16; 4]; 4] = [
+        [0, 0, 100, 0],
+        [100, 0, 100, 100],
+        [100, 100, 0, 100],
+        [0, 100, 0, 0],
+    ];
+    let output_16 = {
+        let mut vb = VB::Builder::<i16, f32>::default();
+        vb.with_vertices(VB::to_points::<i16, i16>(&points).iter())?;
+        vb.with_segments(VB::to_segments::<i16, i16>(&segments).iter())?;
+        vb.build()?
+    };
+    let output_32 = {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.with_vertices(VB::to_points::<i16, I>(&points).iter())?;
+        vb.with_segments(VB::to_segments::<i16, I>(&segments).iter())?;
+        vb.build()?
+    };
+    output_16.validate_topology()?;
+    assert_eq!(output_16.counts(), output_32.counts());
+    for (v16, v32) in output_16.vertices().iter().zip(output_32.vertices().iter()) {
+        let (v16, v32) = (v16.get(), v32.get());
+        assert!(!v16.x().is_nan() && !v16.y().is_nan());
+        assert_eq!(v16.x(), v32.x());
+        assert_eq!(v16.y(), v32.y());
+    }
     Ok(())
 }
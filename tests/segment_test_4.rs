@@ -1,3 +1,4 @@
+#![allow(clippy::bool_assert_comparison, clippy::ptr_arg)]
 use boostvoronoi::builder as VB;
 use boostvoronoi::builder::{to_points, to_segments};
 use boostvoronoi::diagram as VD;
@@ -54,11 +55,137 @@ fn segment_4_1() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    _output.validate_topology()?;
     for v in _output.vertices().iter() {
         let v = v.get();
         assert!(!v.x().is_nan());
         assert!(!v.y().is_nan());
     }
+    // every segment endpoint is connected to its segment by secondary edges
+    let primary_edges: Vec<VD::EdgeIndex> = _output.primary_edges().collect();
+    assert!(primary_edges.len() < _output.edges().len());
+    for edge in _output.edges().iter() {
+        let edge = edge.get();
+        assert_eq!(edge.is_primary(), primary_edges.contains(&edge.id()));
+        assert_eq!(edge.is_primary(), !edge.is_secondary());
+    }
+    // the four half-diagonals of the square meet at the center
+    let center = _output
+        .vertex_iter()
+        .map(|v| v.get())
+        .find(|v| v.x() == 300.0 && v.y() == 300.0)
+        .unwrap();
+    assert_eq!(center.incident_edges(&_output).count(), 4);
+    // the center is 100 away from the four sides of the square
+    assert!((center.clearance() - 100.0).abs() < 1e-9);
+    for v in _output.vertex_iter() {
+        let v = v.get();
+        let edges: Vec<VD::EdgeIndex> = v.incident_edges(&_output).collect();
+        assert_eq!(edges.len(), _output.vertex_degree(v.get_id())?);
+        for (i, e) in edges.iter().enumerate() {
+            assert!(!edges[i + 1..].contains(e));
+            assert_eq!(_output.edge_get_vertex0(*e)?, Some(v.get_id()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn segment_4_1_drop_secondary_edges() -> Result<(), BvError> {
+    let segments: [[I; 4]; 9] = [
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+        [680, 608, 669, 291],
+        [572, 96, 363, 51],
+        [147, 103, 96, 170],
+    ];
+    let build = |drop_secondary_edges| {
+        let mut vb = VB::Builder::<I, F>::default();
+        vb.drop_secondary_edges(drop_secondary_edges)
+            .with_segments(to_segments::<I, I>(&segments).iter())?;
+        vb.build()
+    };
+    let with_secondary = build(false)?;
+    let output = build(true)?;
+    output.validate_topology()?;
+    assert_eq!(output.num_cells(), with_secondary.num_cells());
+    assert_eq!(output.num_edges(), with_secondary.primary_edges().count());
+    assert!(output.num_edges() < with_secondary.num_edges());
+    assert!(output.num_vertices() <= with_secondary.num_vertices());
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        assert!(edge.is_primary());
+        assert_eq!(output.get_edge(edge.twin()?)?.get().twin()?, edge.id());
+    }
+    // the edges of every cell still form a closed loop, in both directions
+    for cell in output.cell_iter() {
+        let cell = cell.get();
+        let edges: Vec<VD::EdgeIndex> = output.cell_edge_iterator(cell.id()).collect();
+        for (i, e) in edges.iter().enumerate() {
+            let e = output.get_edge(*e)?.get();
+            assert_eq!(e.cell()?, cell.id());
+            assert_eq!(e.next()?, edges[(i + 1) % edges.len()]);
+            assert_eq!(output.get_edge(e.next()?)?.get().prev()?, e.id());
+        }
+    }
+    for v in output.vertex_iter() {
+        let v = v.get();
+        assert_eq!(
+            output.edge_get_vertex0(v.get_incident_edge()?)?,
+            Some(v.get_id())
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn segment_4_1_i64() -> Result<(), BvError> {
+    // segment_4_1 scaled up, the largest scale is beyond the range of i32.
+    // The diagram should just scale along.
+    let segments: [[i32; 4]; 9] = [
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+        [529, 242, 367, 107],
+        [94, 628, 512, 632],
+        [680, 608, 669, 291],
+        [572, 96, 363, 51],
+        [147, 103, 96, 170],
+    ];
+    let small = {
+        let mut vb = VB::Builder::<i32, F>::default();
+        vb.with_segments(to_segments::<i32, i32>(&segments).iter())?;
+        vb.build()?
+    };
+    for scale in [100000_i64, 10000000] {
+        let scaled: Vec<[i64; 4]> = segments
+            .iter()
+            .map(|s| s.map(|c| c as i64 * scale))
+            .collect();
+        let large = {
+            let mut vb = VB::Builder::<i64, F>::default();
+            vb.with_segments(to_segments::<i64, i64>(&scaled).iter())?;
+            vb.build()?
+        };
+        large.validate_topology()?;
+        assert_eq!(small.counts(), large.counts());
+        for (a, b) in small.vertices().iter().zip(large.vertices().iter()) {
+            let (a, b) = (a.get(), b.get());
+            assert!(!b.x().is_nan() && !b.y().is_nan());
+            almost_equal(a.x(), b.x() / scale as F, a.y(), b.y() / scale as F);
+        }
+        for (a, b) in small.edges().iter().zip(large.edges().iter()) {
+            let (a, b) = (a.get(), b.get());
+            assert_eq!(a.vertex0(), b.vertex0());
+            assert_eq!(a.cell()?, b.cell()?);
+            assert_eq!(a.twin()?, b.twin()?);
+        }
+    }
     Ok(())
 }
 
@@ -87,6 +214,24 @@ fn vertical_segment_1() -> Result<(), BvError> {
     }
 
     assert_eq!(output.cells().len(), 9);
+    // the cells of the sites on the convex hull are unbounded, the vertical segment is inside
+    let hull = [[147, 103], [512, 632], [94, 628], [96, 170]];
+    let mut bounded = 0;
+    for cell in output.cell_iter() {
+        let cell = cell.get();
+        let (source_index, cat) = cell.source_index_2();
+        let p = retrieve_point(&_v, &_s, (source_index, cat));
+        if cell.contains_point() && hull.contains(&[p.x, p.y]) {
+            assert!(!cell.is_bounded(&output));
+        }
+        if cell.contains_segment() && source_index == 0 {
+            assert!(cell.is_bounded(&output));
+        }
+        if cell.is_bounded(&output) {
+            bounded += 1;
+        }
+    }
+    assert!(bounded > 0 && bounded < output.cells().len());
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
     let (source_index, cat) = cell.source_index_2();
@@ -217,10 +362,62 @@ fn segment_4_2() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    _output.validate_topology()?;
     for v in _output.vertices().iter() {
         let v = v.get();
         assert!(!v.x().is_nan());
         assert!(!v.y().is_nan());
     }
+    let stats = _output.stats();
+    assert_eq!(stats.points, 0);
+    assert_eq!(stats.segments, 7);
+    assert_eq!(stats.cells, _output.num_cells());
+    assert_eq!(stats.vertices, _output.num_vertices());
+    assert_eq!(stats.edges, _output.num_edges());
+    assert!(stats.primary_edges > 0 && stats.primary_edges < stats.edges);
+    assert!(stats.curved_edges > 0);
+    assert!(stats.infinite_edges > 0);
+    let text = stats.to_string();
+    assert_eq!(text.lines().count(), 4);
+    assert_eq!(text.lines().next(), Some("input: 0 points, 7 segments"));
+    assert!(text.ends_with(&format!(
+        "exact circle events: {}",
+        stats.exact_circle_events
+    )));
+    Ok(())
+}
+
+#[test]
+/// Cell::source_point() and Cell::source_segment() should agree with retrieve_point()
+fn source_geometry_1() -> Result<(), BvError> {
+    let points: [[I; 2]; 2] = [[250, 300], [300, 250]];
+    let segments: [[I; 4]; 5] = [
+        [200, 200, 200, 400],
+        [200, 400, 400, 400],
+        [400, 400, 400, 200],
+        [400, 200, 200, 200],
+        [94, 628, 512, 632],
+    ];
+    let _v = to_points::<I, I>(&points);
+    let _s = to_segments::<I, I>(&segments);
+
+    let mut vb = VB::Builder::<I, F>::default();
+    vb.with_vertices(_v.iter())?;
+    vb.with_segments(_s.iter())?;
+    let output = vb.build()?;
+
+    for cell in output.cell_iter() {
+        let cell = cell.get();
+        let source = cell.source_index_2();
+        assert_eq!(
+            cell.source_point(&_v, &_s),
+            retrieve_point(&_v, &_s, source)
+        );
+        if cell.contains_segment() {
+            assert_eq!(cell.source_segment(&_v, &_s), Some(_s[source.0 - _v.len()]));
+        } else {
+            assert_eq!(cell.source_segment(&_v, &_s), None);
+        }
+    }
     Ok(())
 }
@@ -1,3 +1,4 @@
+#![allow(clippy::bool_assert_comparison, clippy::excessive_precision)]
 use boostvoronoi::builder::{to_segments, Builder};
 use boostvoronoi::diagram as VD;
 use boostvoronoi::BvError;
@@ -30,6 +31,7 @@ fn segment_5_1() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 8);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -145,6 +147,7 @@ fn segment_5_2() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    _output.validate_topology()?;
     Ok(())
 }
 
@@ -173,6 +176,7 @@ fn segment_5_3() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 24);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -456,6 +460,7 @@ fn segment_5_4() -> Result<(), BvError> {
         vb.with_segments(segments.iter())?;
         vb.build()?
     };
+    output.validate_topology()?;
     assert_eq!(output.cells().len(), 5);
     let cell = output.cells()[0].get();
     assert_eq!(cell.id().0, 0);
@@ -517,3 +522,88 @@ fn segment_5_4() -> Result<(), BvError> {
     assert_eq!(v.get_incident_edge()?.0, 13);
     Ok(())
 }
+
+/// The input site of the cell, as a pair of end points (equal for point sites).
+fn site(segments: &[[I; 4]], cell: &VD::Cell<I, F>) -> ([F; 2], [F; 2]) {
+    let (index, category) = cell.source_index_2();
+    let s = segments[index];
+    let (a, b) = ([s[0] as F, s[1] as F], [s[2] as F, s[3] as F]);
+    match category {
+        VD::SourceCategory::SegmentStart => (a, a),
+        VD::SourceCategory::SegmentEnd => (b, b),
+        _ => (a, b),
+    }
+}
+
+/// The distance from `p` to the input site of the cell.
+fn site_distance(segments: &[[I; 4]], cell: &VD::Cell<I, F>, p: [F; 2]) -> F {
+    let (a, b) = site(segments, cell);
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 > 0.0 {
+        (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p[0] - a[0] - t * dx).hypot(p[1] - a[1] - t * dy)
+}
+
+/// An open path: the cells at the free ends must produce rays bisecting their sites
+#[test]
+fn segment_5_open_path_1() -> Result<(), BvError> {
+    let segments: [[I; 4]; 3] = [[0, 0, 10, 0], [10, 0, 10, 10], [10, 10, 20, 10]];
+    let output = {
+        let mut vb = Builder::<I, F>::default();
+        vb.with_segments(to_segments(&segments).iter())?;
+        vb.build()?
+    };
+    output.validate_topology()?;
+    // 3 segments and 4 endpoints
+    assert_eq!(output.cells().len(), 7);
+
+    let mut rays = 0;
+    for edge in output.edges().iter() {
+        let edge = edge.get();
+        let v0 = match output.edge_vertices(edge.id())? {
+            (Some(v0), None) => output.vertex_get(v0)?.get(),
+            _ => continue,
+        };
+        rays += 1;
+        let cell = output.get_cell(edge.cell()?)?.get();
+        let twin_cell = output
+            .get_cell(output.get_edge(edge.twin()?)?.get().cell()?)?
+            .get();
+        let (a0, a1) = site(&segments, &cell);
+        let (b0, b1) = site(&segments, &twin_cell);
+        // the ray leaves v0 with its own cell on the left and the twin cell on the right
+        let direction = match (cell.contains_point(), twin_cell.contains_point()) {
+            (true, true) => [a0[1] - b0[1], b0[0] - a0[0]],
+            (true, false) => {
+                // the segment direction, seen from the point
+                let end = if a0 == b0 { b1 } else { b0 };
+                [a0[1] - end[1], end[0] - a0[0]]
+            }
+            (false, true) => {
+                let end = if b0 == a0 { a1 } else { a0 };
+                [end[1] - b0[1], b0[0] - end[0]]
+            }
+            (false, false) => panic!("unexpected infinite edge between two segment cells"),
+        };
+        let length = direction[0].hypot(direction[1]);
+        assert!(length > 0.0);
+        let far = 1000.0;
+        let p = [
+            v0.x() + far * direction[0] / length,
+            v0.y() + far * direction[1] / length,
+        ];
+        // the far point is equally close to both sites, and no other site is closer
+        let d = site_distance(&segments, &cell, p);
+        assert!((d - site_distance(&segments, &twin_cell, p)).abs() < 1e-6);
+        for other in output.cells().iter() {
+            assert!(site_distance(&segments, &other.get(), p) >= d - 1e-6);
+        }
+    }
+    // perpendicular rays at the ends of the two outer segments, and two rays between end points
+    assert_eq!(rays, 6);
+    Ok(())
+}
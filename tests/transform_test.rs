@@ -45,3 +45,30 @@ fn transform_test_2() -> Result<(), BvError> {
 
     Ok(())
 }
+
+#[test]
+fn transform_test_3() -> Result<(), BvError> {
+    let points = boostvoronoi::builder::to_points::<i32, i32>(&[[0, 0], [10, 0], [5, 10], [5, 4]]);
+    let mut vb = boostvoronoi::builder::Builder::<i32, f32>::default();
+    vb.with_vertices(points.iter())?;
+    let mut diagram = vb.build()?;
+    let original: Vec<[f32; 2]> = diagram
+        .vertex_iter()
+        .map(|v| [v.get().x(), v.get().y()])
+        .collect();
+
+    let mut aabb_source = VU::Aabb2::<i32, f32>::default();
+    let mut aabb_dest = VU::Aabb2::<i32, f32>::default();
+    aabb_source.update_vertex(0., 0.);
+    aabb_source.update_vertex(10., 10.);
+    aabb_dest.update_vertex(100., 100.);
+    aabb_dest.update_vertex(200., 200.);
+    let transform = VU::SimpleAffine::new(&aabb_source, &aabb_dest)?;
+
+    diagram.map_vertices(|p| transform.transform_f(p));
+    for (v, o) in diagram.vertex_iter().zip(original.iter()) {
+        let v = v.get();
+        assert_eq!([v.x(), v.y()], transform.transform_f(*o));
+    }
+    Ok(())
+}